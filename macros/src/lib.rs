@@ -119,17 +119,82 @@ pub fn derive_rbac(input: TokenStream) -> TokenStream {
 ///
 /// The storage key prefix for the fields can be optionally specified (default:
 /// `"~$141"`) using `#[nep141(storage_key = "<expression>")]`.
+///
+/// Fields:
+/// - `auto_register`: Flag. Requires NEP-145. `ft_transfer` and
+/// `ft_transfer_call` accept deposits above one yocto, and register unregistered
+/// receivers with the minimum storage balance, paid from the attached deposit
+/// and then the storage balance of the sender. Leftover deposit is refunded.
 #[proc_macro_derive(Nep141, attributes(nep141))]
 pub fn derive_nep141(input: TokenStream) -> TokenStream {
     make_derive(input, standard::nep141::expand)
 }
 
+/// Adds share-based (rebasing) NEP-141 fungible token core functionality to a
+/// contract. Balances are derived from shares of a contract-controlled pooled
+/// amount. Exposes `ft_*` functions (including `ft_shares_of` and
+/// `ft_total_shares`) to the public blockchain, implements internal
+/// controller and receiver functionality. Use instead of `Nep141`, not in
+/// addition to it.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$141s"`) using `#[nep141_shares(storage_key = "<expression>")]`.
+#[proc_macro_derive(Nep141Shares, attributes(nep141_shares))]
+pub fn derive_nep141_shares(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::nep141_shares::expand)
+}
+
+/// Adds a supply cap and mint rate limit to a NEP-141 fungible token. Exposes
+/// `ft_max_supply`, `ft_mint_rate_limit`, and `ft_remaining_mintable_supply`
+/// view functions to the blockchain.
+///
+/// Limits are only enforced if `SupplyCapHook` is installed as a NEP-141 mint
+/// hook, e.g. `#[nep141(mint_hook = "SupplyCapHook")]`.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$141c"`) using `#[supply_cap(storage_key = "<expression>")]`.
+#[proc_macro_derive(SupplyCap, attributes(supply_cap))]
+pub fn derive_supply_cap(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::supply_cap::expand)
+}
+
+/// Adds account freezing, forced transfers, and clawback to a NEP-141
+/// contract. Exposes `cmpl_*` functions to the public blockchain, implements
+/// internal controller functionality.
+///
+/// Freezes are only enforced if `ComplianceHook` is installed as a NEP-141
+/// hook, e.g. `#[nep141(all_hooks = "ComplianceHook")]`.
+///
+/// The compliance authority is specified using
+/// `#[compliance(authority = "owner")]` (requires `Owner`) or
+/// `#[compliance(authority = "role(<expression>)")]` (requires `Rbac`). If
+/// omitted, `ComplianceAuthority` must be implemented manually.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$141f"`) using `#[compliance(storage_key = "<expression>")]`.
+#[proc_macro_derive(Compliance, attributes(compliance))]
+pub fn derive_compliance(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::compliance::expand)
+}
+
+/// Adds wrapped NEAR deposit and withdrawal functionality to a contract.
+/// Exposes `near_deposit` and `near_withdraw` functions to the public
+/// blockchain. Requires NEP-141 and NEP-145 (e.g. `FungibleToken`).
+#[proc_macro_derive(WrappedNear, attributes(wrapped_near))]
+pub fn derive_wrapped_near(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::wrapped_near::expand)
+}
+
 /// Adds NEP-145 fungible token core functionality to a contract. Exposes
 /// `storage_*` functions to the public blockchain, implements internal
 /// controller functionality.
 ///
 /// The storage key prefix for the fields can be optionally specified (default:
 /// `"~$145"`) using `#[nep145(storage_key = "<expression>")]`.
+///
+/// Fields:
+/// - `sponsorship`: Flag. Also exposes the `storage_sponsor_*` functions that
+/// let sponsors pay for the storage of other accounts.
 #[proc_macro_derive(Nep145, attributes(nep145))]
 pub fn derive_nep145(input: TokenStream) -> TokenStream {
     make_derive(input, standard::nep145::expand)
@@ -153,6 +218,11 @@ pub fn derive_nep148(input: TokenStream) -> TokenStream {
 /// Attributes are generally the union of those from the constituent derive
 /// macros.
 /// Specify attributes with `#[fungible_token(...)]`.
+///
+/// Fields:
+/// - `transfer_on_force_unregister`: Flag. On forced storage unregistration,
+/// transfer the account's tokens to the force unregister beneficiary instead
+/// of burning them.
 #[proc_macro_derive(FungibleToken, attributes(fungible_token))]
 pub fn derive_fungible_token(input: TokenStream) -> TokenStream {
     make_derive(input, standard::fungible_token::expand)
@@ -170,6 +240,21 @@ pub fn derive_fungible_token(input: TokenStream) -> TokenStream {
 /// transfer hooks.
 /// - `token_data`: specify the token metadata loading extensions invoked by
 /// `nft_token`.
+/// - `check_external_transfer`: specify the `CheckExternalTransfer`
+/// implementation that validates `nft_transfer` and `nft_transfer_call`.
+/// Checkers can be composed using tuples, e.g.
+/// `"(DefaultCheckExternalTransfer, (Soulbound, Pausable))"`.
+/// - `event_version`: version of the NEP-171 event standard advertised by
+/// emitted events: `"1.0.0"`, `"1.1.0"`, or `"1.2.0"` (default). Metadata
+/// update events are not emitted before `"1.1.0"`.
+/// - `transfer_hook_log_length`: upper bound, in bytes, of the logs emitted by
+/// the transfer hooks for a single token (default: `0`). `nft_batch_transfer`
+/// rejects batches whose events and hook logs would exceed the log budget of
+/// a receipt.
+/// - `auto_register`: Flag. Requires NEP-145. The transfer functions accept
+/// deposits above one yocto, and register unregistered receivers with the minimum
+/// storage balance, paid from the attached deposit and then the storage
+/// balance of the sender. Leftover deposit is refunded.
 #[proc_macro_derive(Nep171, attributes(nep171))]
 pub fn derive_nep171(input: TokenStream) -> TokenStream {
     make_derive(input, standard::nep171::expand)
@@ -184,10 +269,71 @@ pub fn derive_nep177(input: TokenStream) -> TokenStream {
     make_derive(input, standard::nep177::expand)
 }
 
+/// Adds NFT rentals (a time-limited user role, ERC-4907 style) to a
+/// contract. Exposes `nft_set_user`, `nft_user_of`, and `nft_user_expires`
+/// to the public blockchain, implements internal controller functionality.
+/// Requires NEP-171.
+///
+/// Rentals are only cleared on transfer and burn if `TokenRental` is installed
+/// as a NEP-171 hook, and only appear in `nft_token` if it is installed as
+/// token data, e.g.
+/// `#[non_fungible_token(transfer_hook = "TokenRental", burn_hook = "TokenRental", token_data = "TokenRental")]`.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$171r"`) using `#[nft_rental(storage_key = "<expression>")]`.
+#[proc_macro_derive(NftRental, attributes(nft_rental))]
+pub fn derive_nft_rental(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::nft_rental::expand)
+}
+
+/// Adds NFT series (editions) functionality to a contract. Exposes
+/// `nft_mint_from_series` and series enumeration functions to the public
+/// blockchain, implements internal controller functionality. Requires
+/// NEP-171 and NEP-177 (e.g. `NonFungibleToken`). Series are created using
+/// `NftSeries::create_series`.
+///
+/// The caller of `nft_mint_from_series` pays the price and the storage fee of
+/// the minted token from the attached deposit. If NEP-171 mints are also
+/// subject to NEP-145 storage accounting, the receiver's storage balance is
+/// charged as well.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$177s"`) using `#[nft_series(storage_key = "<expression>")]`.
+#[proc_macro_derive(NftSeries, attributes(nft_series))]
+pub fn derive_nft_series(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::nft_series::expand)
+}
+
+/// Adds lazy minting with creator-signed vouchers to a contract. Exposes
+/// `nft_redeem_voucher` and creator key management functions to the public
+/// blockchain, implements internal controller functionality. Requires NEP-171
+/// and NEP-177 (e.g. `NonFungibleToken`).
+///
+/// The authority that registers creator keys is specified using
+/// `#[nft_voucher(authority = "owner")]` (requires `Owner`) or
+/// `#[nft_voucher(authority = "role(<expression>)")]` (requires `Rbac`). If
+/// omitted, `NftVoucherAuthority` must be implemented manually.
+///
+/// The price of redeemed vouchers is paid to the creator, unless another
+/// `VoucherPayout` implementation is specified using
+/// `#[nft_voucher(payout = "<type>")]`.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$177v"`) using `#[nft_voucher(storage_key = "<expression>")]`.
+#[proc_macro_derive(NftVoucher, attributes(nft_voucher))]
+pub fn derive_nft_voucher(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::nft_voucher::expand)
+}
+
 /// Adds NEP-178 non-fungible token approvals functionality to a contract.
+/// Also exposes the `nft_approve_with_expiry` extension, which creates
+/// approvals that expire.
 ///
 /// The storage key prefix for the fields can be optionally specified (default:
 /// `"~$178"`) using `#[nep178(storage_key = "<expression>")]`.
+///
+/// The maximum number of approvals per token can be optionally specified
+/// (default: 32) using `#[nep178(max_approvals = "<expression>")]`.
 #[proc_macro_derive(Nep178, attributes(nep178))]
 pub fn derive_nep178(input: TokenStream) -> TokenStream {
     make_derive(input, standard::nep178::expand)
@@ -197,12 +343,45 @@ pub fn derive_nep178(input: TokenStream) -> TokenStream {
 ///
 /// The storage key prefix for the fields can be optionally specified (default:
 /// `"~$181"`) using `#[nep181(storage_key = "<expression>")]`.
+///
+/// Secondary indexes are specified using `#[nep181(index = "<type>")]`, once
+/// per index. Each type must implement `TokenIndex`, and is exposed through
+/// the `nft_supply_by_index` and `nft_tokens_by_index` view methods. The
+/// storage key prefix for the indexes can be optionally specified (default:
+/// `"~$181i"`) using `#[nep181(index_storage_key = "<expression>")]`.
+///
+/// The `TokenIndexing<T>` hook must be installed on NEP-171 for each index.
+/// The `NonFungibleToken` derive macro does this automatically.
 #[proc_macro_derive(Nep181, attributes(nep181))]
 pub fn derive_nep181(input: TokenStream) -> TokenStream {
     make_derive(input, standard::nep181::expand)
 }
 
+/// Adds NEP-393 soulbound token (SBT) issuer functionality to a contract.
+/// Exposes `sbt_*` functions to the public blockchain, implements internal
+/// controller functionality.
+///
+/// The issuer authority is specified using `#[nep393(authority = "owner")]`
+/// (requires `Owner`) or `#[nep393(authority = "role(<expression>)")]`
+/// (requires `Rbac`). If omitted, `Nep393Authority` must be implemented
+/// manually.
+///
+/// Hooks may be specified using `mint_hook`, `revoke_hook`, `renew_hook`,
+/// `recover_hook`, and `all_hooks`.
+///
+/// The storage key prefix for the fields can be optionally specified (default:
+/// `"~$393"`) using `#[nep393(storage_key = "<expression>")]`.
+#[proc_macro_derive(Nep393, attributes(nep393))]
+pub fn derive_nep393(input: TokenStream) -> TokenStream {
+    make_derive(input, standard::nep393::expand)
+}
+
 /// Implements all NFT functionality at once, like `#[derive(Nep171, Nep177, Nep178, Nep181)]`.
+///
+/// Fields:
+/// - `transfer_on_force_unregister`: Flag. On forced storage unregistration,
+/// transfer the account's tokens to the force unregister beneficiary instead
+/// of burning them.
 #[proc_macro_derive(NonFungibleToken, attributes(non_fungible_token))]
 pub fn derive_non_fungible_token(input: TokenStream) -> TokenStream {
     make_derive(input, standard::non_fungible_token::expand)
@@ -210,11 +389,23 @@ pub fn derive_non_fungible_token(input: TokenStream) -> TokenStream {
 
 /// Migrate a contract's default struct from one schema to another.
 ///
+/// Exposes the `migrate_schema_version` function, which returns the schema
+/// version of the contract state.
+///
 /// Fields may be specified in the `#[migrate(...)]` attribute.
 ///
 /// Fields include:
-///  - `from` Old default struct type to convert from. (required)
+///  - `from` Old default struct type to convert from. Exposes the `migrate`
+///     function. `MigrateHook` must be implemented manually, and may declare
+///     `FROM_VERSION` and `TO_VERSION`. (required, unless `batched` is
+///     specified)
 ///  - `to` New default struct type to convert into. (optional, default: `Self`)
+///  - `batched` Flag. Also implements batched migration, exposing the
+///     `migrate_start`, `migrate_step`, and `migrate_status` functions.
+///     `BatchedMigrateHook` must be implemented manually, and may declare
+///     `FROM_VERSION` and `TO_VERSION`.
+///  - `storage_key` Storage prefix for the schema version and the batched
+///     migration status and cursor (optional, default: `b"~mig"`)
 ///  - `convert` Identifier of a function that converts from the old schema to
 ///     the new schema. Mutually exclusive with `convert_with_args`. (optional,
 ///     default: `<Self::NewSchema as From<Self::OldSchema>>::from`)
@@ -1,12 +1,15 @@
-use darling::FromDeriveInput;
+use darling::{util::Flag, FromDeriveInput};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
+use syn::Expr;
 
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(migrate), supports(struct_named))]
 pub struct MigrateMeta {
-    pub from: syn::Type,
+    pub from: Option<syn::Type>,
     pub to: Option<syn::Type>,
+    pub batched: Flag,
+    pub storage_key: Option<Expr>,
 
     pub ident: syn::Ident,
     pub generics: syn::Generics,
@@ -22,6 +25,8 @@ pub fn expand(meta: MigrateMeta) -> Result<TokenStream, darling::Error> {
     let MigrateMeta {
         from,
         to,
+        batched,
+        storage_key,
 
         ident,
         generics,
@@ -30,28 +35,82 @@ pub fn expand(meta: MigrateMeta) -> Result<TokenStream, darling::Error> {
         near_sdk,
     } = meta;
 
+    if from.is_none() && !batched.is_present() {
+        return Err(darling::Error::custom(
+            "Expected `from` (schema migration) or `batched` (batched migration)",
+        ));
+    }
+
     let (imp, ty, wh) = generics.split_for_impl();
 
-    let to = to.map_or_else(
-        || quote! { Self }.to_token_stream(),
-        |t| t.to_token_stream(),
-    );
+    let schema = from.map(|from| {
+        let to = to.map_or_else(
+            || quote! { Self }.to_token_stream(),
+            |t| t.to_token_stream(),
+        );
+
+        quote! {
+            impl #imp #me::migrate::MigrateController for #ident #ty #wh {
+                type OldSchema = #from;
+                type NewSchema = #to;
+            }
+
+            #[#near_sdk::near]
+            impl #imp #ident #ty #wh {
+                #[init(ignore_state)]
+                pub fn migrate() -> Self {
+                    <#ident as #me::migrate::MigrateController>::perform_migration()
+                }
+            }
+        }
+    });
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    let batched = batched.is_present().then(|| {
+        quote! {
+            impl #imp #me::migrate::BatchedMigrateInternal for #ident #ty #wh {}
+
+            #[#near_sdk::near]
+            impl #imp #me::migrate::BatchedMigrateExternal for #ident #ty #wh {
+                #[private]
+                fn migrate_start(&mut self, limit: u32) -> #me::migrate::MigrationStatus {
+                    use #me::migrate::BatchedMigrateController;
+
+                    self.start_migration();
+                    self.step_migration(limit)
+                }
+
+                fn migrate_step(&mut self, limit: u32) -> #me::migrate::MigrationStatus {
+                    #me::migrate::BatchedMigrateController::step_migration(self, limit)
+                }
+
+                fn migrate_status(&self) -> #me::migrate::MigrationStatus {
+                    <Self as #me::migrate::BatchedMigrateController>::migration_status()
+                }
+            }
+        }
+    });
 
     Ok(quote! {
-        impl #imp #me::migrate::MigrateController for #ident #ty #wh {
-            type OldSchema = #from;
-            type NewSchema = #to;
+        impl #imp #me::migrate::MigrateInternal for #ident #ty #wh {
+            #root
         }
 
         #[#near_sdk::near]
         impl #imp #ident #ty #wh {
-            #[init(ignore_state)]
-            pub fn migrate() -> Self {
-                let old_state = <#ident as #me::migrate::MigrateController>::deserialize_old_schema();
-                <#ident as #me::migrate::MigrateHook>::on_migrate(
-                    old_state,
-                )
+            pub fn migrate_schema_version(&self) -> u32 {
+                <#ident as #me::migrate::MigrateInternal>::schema_version()
             }
         }
+
+        #schema
+        #batched
     })
 }
@@ -0,0 +1,71 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Expr;
+
+/// Who may call the privileged methods of a component, e.g.
+/// `authority = "owner"` or `authority = "role(Role::Issuer)"`. When omitted,
+/// the contract implements the authority trait itself.
+#[derive(Debug, Clone)]
+pub enum Authority {
+    Custom,
+    Owner,
+    Role(Box<syn::Expr>),
+}
+
+impl FromMeta for Authority {
+    fn from_none() -> Option<Self> {
+        Some(Self::Custom)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if value == "owner" {
+            Ok(Authority::Owner)
+        } else if let Some(a) = value
+            .strip_prefix("role(")
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| syn::parse_str::<Expr>(s).ok())
+            .map(|e| Authority::Role(Box::new(e)))
+        {
+            Ok(a)
+        } else {
+            Err(darling::Error::custom(format!(
+                r#"Invalid value "{value}", expected "owner" or "role(...)""#,
+            )))
+        }
+    }
+}
+
+impl Authority {
+    /// Implements `authority_trait` for the contract, with `method` requiring
+    /// the configured authority. Returns `None` for [`Authority::Custom`].
+    pub fn expand(
+        &self,
+        me: &syn::Path,
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        authority_trait: &TokenStream,
+        method: &str,
+    ) -> Option<TokenStream> {
+        let (imp, ty, wher) = generics.split_for_impl();
+        let method = format_ident!("{method}");
+
+        let body = match self {
+            Authority::Custom => return None,
+            Authority::Owner => quote! {
+                <Self as #me::owner::Owner>::require_owner();
+            },
+            Authority::Role(role) => quote! {
+                <Self as #me::rbac::Rbac>::require_role(&#role);
+            },
+        };
+
+        Some(quote! {
+            impl #imp #authority_trait for #ident #ty #wher {
+                fn #method(&self) {
+                    #body
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+
+use super::authority::Authority;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(compliance), supports(struct_named))]
+pub struct ComplianceMeta {
+    pub storage_key: Option<Expr>,
+    pub authority: Authority,
+
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: ComplianceMeta) -> Result<TokenStream, darling::Error> {
+    let ComplianceMeta {
+        storage_key,
+        authority,
+
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    let authority_implementation = authority.expand(
+        &me,
+        &ident,
+        &generics,
+        &quote! { #me::standard::nep141::compliance::ComplianceAuthority },
+        "require_compliance_authority",
+    );
+
+    Ok(quote! {
+        impl #imp #me::standard::nep141::compliance::ComplianceInternal for #ident #ty #wher {
+            #root
+        }
+
+        #authority_implementation
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep141::compliance::ComplianceExternal for #ident #ty #wher {
+            fn cmpl_is_frozen(&self, account_id: #near_sdk::AccountId) -> bool {
+                #me::standard::nep141::compliance::Compliance::is_frozen(self, &account_id)
+            }
+
+            #[payable]
+            fn cmpl_freeze(&mut self, account_id: #near_sdk::AccountId) {
+                use #me::standard::nep141::compliance::*;
+
+                #near_sdk::assert_one_yocto();
+                ComplianceAuthority::require_compliance_authority(self);
+                Compliance::freeze(self, &account_id);
+            }
+
+            #[payable]
+            fn cmpl_unfreeze(&mut self, account_id: #near_sdk::AccountId) {
+                use #me::standard::nep141::compliance::*;
+
+                #near_sdk::assert_one_yocto();
+                ComplianceAuthority::require_compliance_authority(self);
+                Compliance::unfreeze(self, &account_id);
+            }
+
+            #[payable]
+            fn cmpl_force_transfer(
+                &mut self,
+                sender_id: #near_sdk::AccountId,
+                receiver_id: #near_sdk::AccountId,
+                amount: #near_sdk::json_types::U128,
+                memo: Option<String>,
+            ) {
+                use #me::standard::nep141::compliance::*;
+
+                #near_sdk::assert_one_yocto();
+                ComplianceAuthority::require_compliance_authority(self);
+                Compliance::force_transfer(self, &sender_id, &receiver_id, amount.into(), memo.as_deref())
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+
+            #[payable]
+            fn cmpl_clawback(
+                &mut self,
+                account_id: #near_sdk::AccountId,
+                amount: #near_sdk::json_types::U128,
+                memo: Option<String>,
+            ) {
+                use #me::standard::nep141::compliance::*;
+
+                #near_sdk::assert_one_yocto();
+                ComplianceAuthority::require_compliance_authority(self);
+                Compliance::clawback(self, &account_id, amount.into(), memo.as_deref())
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+        }
+    })
+}
@@ -1,4 +1,4 @@
-use darling::FromDeriveInput;
+use darling::{util::Flag, FromDeriveInput};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Expr, Type};
@@ -23,6 +23,9 @@ pub struct FungibleTokenMeta {
     // NEP-145 fields
     pub storage_management_storage_key: Option<Expr>,
     pub force_unregister_hook: Option<Type>,
+    pub sponsorship: Flag,
+    pub auto_register: Flag,
+    pub transfer_on_force_unregister: Flag,
 
     // darling
     pub generics: syn::Generics,
@@ -47,6 +50,9 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
 
         storage_management_storage_key,
         force_unregister_hook,
+        sponsorship,
+        auto_register,
+        transfer_on_force_unregister,
 
         generics,
         ident,
@@ -57,6 +63,11 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
 
     let all_hooks_or_unit = unitify(all_hooks.clone());
     let force_unregister_hook_or_unit = unitify(force_unregister_hook);
+    let on_force_unregister = if transfer_on_force_unregister.is_present() {
+        quote! { #me::standard::nep141::hooks::TransferNep141OnForceUnregisterHook }
+    } else {
+        quote! { #me::standard::nep141::hooks::BurnNep141OnForceUnregisterHook }
+    };
 
     let expand_nep141 = nep141::expand(nep141::Nep141Meta {
         storage_key: core_storage_key,
@@ -66,6 +77,7 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
         mint_hook,
         transfer_hook,
         burn_hook,
+        auto_register,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -78,8 +90,9 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
         storage_key: storage_management_storage_key,
         all_hooks,
         force_unregister_hook: Some(
-            syn::parse_quote! { (#force_unregister_hook_or_unit, #me::standard::nep141::hooks::BurnNep141OnForceUnregisterHook) },
+            syn::parse_quote! { (#force_unregister_hook_or_unit, #on_force_unregister) },
         ),
+        sponsorship,
         generics: generics.clone(),
         ident: ident.clone(),
 
@@ -3,6 +3,7 @@ pub mod fungible_token;
 pub mod non_fungible_token;
 
 pub mod nep141;
+pub mod nep141_shares;
 pub mod nep145;
 pub mod nep148;
 pub mod nep171;
@@ -10,3 +11,12 @@ pub mod nep177;
 pub mod nep178;
 pub mod nep181;
 pub mod nep297;
+pub mod nep393;
+
+pub mod authority;
+pub mod compliance;
+pub mod nft_rental;
+pub mod nft_series;
+pub mod nft_voucher;
+pub mod supply_cap;
+pub mod wrapped_near;
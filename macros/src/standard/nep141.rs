@@ -1,4 +1,4 @@
-use darling::FromDeriveInput;
+use darling::{util::Flag, FromDeriveInput};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Expr, Type};
@@ -11,6 +11,7 @@ pub struct Nep141Meta {
     pub mint_hook: Option<Type>,
     pub transfer_hook: Option<Type>,
     pub burn_hook: Option<Type>,
+    pub auto_register: Flag,
     pub generics: syn::Generics,
     pub ident: syn::Ident,
 
@@ -28,6 +29,7 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
         mint_hook,
         transfer_hook,
         burn_hook,
+        auto_register,
         generics,
         ident,
 
@@ -51,6 +53,34 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
 
     let default_hook = all_hooks.map_or_else(|| quote! { () }, |h| quote! { #h });
 
+    let check_deposit = if auto_register.is_present() {
+        quote! {
+            // The refund promise is scheduled when dropped.
+            let _ = #me::standard::nep145::register_receiver_with_deposit(
+                self,
+                &#near_sdk::env::predecessor_account_id(),
+                &receiver_id,
+            );
+        }
+    } else {
+        quote! { #near_sdk::assert_one_yocto(); }
+    };
+
+    let external = expand_external(
+        &ident,
+        &generics,
+        &me,
+        &near_sdk,
+        &quote! { #me::standard::nep141::Nep141Controller },
+        &check_deposit,
+        &quote! {
+            // The receiver is only credited with the amount after fees.
+            transfer
+                .amount
+                .saturating_sub(Nep141Controller::transfer_fee(self, &transfer))
+        },
+    );
+
     Ok(quote! {
         impl #imp #me::standard::nep141::Nep141ControllerInternal for #ident #ty #wher {
             type MintHook = (#mint_hook, #default_hook);
@@ -60,6 +90,28 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
             #root
         }
 
+        #external
+    })
+}
+
+/// Expands the NEP-141 external interface (`Nep141` and `Nep141Resolver`)
+/// on top of `controller`, which must provide `transfer`, `total_supply`, and
+/// `balance_of` functions with the same signatures as `Nep141Controller`.
+/// `received_amount` is an expression evaluating to the amount credited to
+/// the receiver of `transfer` in `ft_transfer_call`. `check_deposit` is a
+/// statement validating the attached deposit of the transfer functions.
+pub fn expand_external(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    me: &syn::Path,
+    near_sdk: &syn::Path,
+    controller: &TokenStream,
+    check_deposit: &TokenStream,
+    received_amount: &TokenStream,
+) -> TokenStream {
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    quote! {
         #[#near_sdk::near]
         impl #imp #me::standard::nep141::Nep141 for #ident #ty #wher {
             #[payable]
@@ -71,7 +123,7 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
             ) {
                 use #me::standard::nep141::*;
 
-                #near_sdk::assert_one_yocto();
+                #check_deposit
                 let sender_id = #near_sdk::env::predecessor_account_id();
                 let amount: u128 = amount.into();
 
@@ -82,9 +134,10 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                     memo: memo.map(Into::into),
                     msg: None,
                     revert: false,
+                    forced: false,
                 };
 
-                Nep141Controller::transfer(self, &transfer)
+                #controller::transfer(self, &transfer)
                     .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
             }
 
@@ -105,7 +158,7 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                     MORE_GAS_FAIL_MESSAGE,
                 );
 
-                #near_sdk::assert_one_yocto();
+                #check_deposit
                 let sender_id = #near_sdk::env::predecessor_account_id();
                 let amount: u128 = amount.into();
 
@@ -116,9 +169,12 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                     memo: memo.map(Into::into),
                     msg: Some(msg.clone().into()),
                     revert: false,
+                    forced: false,
                 };
 
-                Nep141Controller::transfer(self, &transfer)
+                let received_amount = #received_amount;
+
+                #controller::transfer(self, &transfer)
                     .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
 
                 let receiver_gas = prepaid_gas
@@ -128,24 +184,24 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                 // Initiating receiver's call and the callback
                 ext_nep141_receiver::ext(transfer.receiver_id.clone().into())
                     .with_static_gas(receiver_gas)
-                    .ft_on_transfer(transfer.sender_id.clone().into(), transfer.amount.into(), msg)
+                    .ft_on_transfer(transfer.sender_id.clone().into(), received_amount.into(), msg)
                     .then(
                         ext_nep141_resolver::ext(#near_sdk::env::current_account_id())
                             .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
                             .ft_resolve_transfer(
                                 transfer.sender_id.clone().into(),
                                 transfer.receiver_id.clone().into(),
-                                transfer.amount.into(),
+                                received_amount.into(),
                             ),
                     )
             }
 
             fn ft_total_supply(&self) -> #near_sdk::json_types::U128 {
-                #me::standard::nep141::Nep141Controller::total_supply(self).into()
+                #controller::total_supply(self).into()
             }
 
             fn ft_balance_of(&self, account_id: #near_sdk::AccountId) -> #near_sdk::json_types::U128 {
-                #me::standard::nep141::Nep141Controller::balance_of(self, &account_id).into()
+                #controller::balance_of(self, &account_id).into()
             }
         }
 
@@ -178,7 +234,7 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                 };
 
                 let refunded_amount = if unused_amount > 0 {
-                    let receiver_balance = Nep141Controller::balance_of(self, &receiver_id);
+                    let receiver_balance = #controller::balance_of(self, &receiver_id);
                     if receiver_balance > 0 {
                         let refund_amount = std::cmp::min(receiver_balance, unused_amount);
                         let transfer = Nep141Transfer {
@@ -188,9 +244,10 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                             memo: None,
                             msg: None,
                             revert: true,
+                            forced: false,
                         };
 
-                        Nep141Controller::transfer(self, &transfer)
+                        #controller::transfer(self, &transfer)
                             .unwrap_or_else(|e| env::panic_str(&e.to_string()));
 
                         refund_amount
@@ -205,5 +262,5 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                 U128(amount - refunded_amount)
             }
         }
-    })
+    }
 }
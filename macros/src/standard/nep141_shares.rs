@@ -0,0 +1,88 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Type};
+
+use super::nep141;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(nep141_shares), supports(struct_named))]
+pub struct Nep141SharesMeta {
+    pub storage_key: Option<Expr>,
+    pub all_hooks: Option<Type>,
+    pub mint_hook: Option<Type>,
+    pub transfer_hook: Option<Type>,
+    pub burn_hook: Option<Type>,
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: Nep141SharesMeta) -> Result<TokenStream, darling::Error> {
+    let Nep141SharesMeta {
+        storage_key,
+        all_hooks,
+        mint_hook,
+        transfer_hook,
+        burn_hook,
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    let mint_hook = mint_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let transfer_hook = transfer_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let burn_hook = burn_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+
+    let default_hook = all_hooks.map_or_else(|| quote! { () }, |h| quote! { #h });
+
+    let external = nep141::expand_external(
+        &ident,
+        &generics,
+        &me,
+        &near_sdk,
+        &quote! { #me::standard::nep141::shares::Nep141SharesController },
+        &quote! { #near_sdk::assert_one_yocto(); },
+        &quote! { transfer.amount },
+    );
+
+    Ok(quote! {
+        impl #imp #me::standard::nep141::shares::Nep141SharesControllerInternal for #ident #ty #wher {
+            type MintHook = (#mint_hook, #default_hook);
+            type TransferHook = (#transfer_hook, #default_hook);
+            type BurnHook = (#burn_hook, #default_hook);
+
+            #root
+        }
+
+        #external
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep141::shares::Nep141SharesExternal for #ident #ty #wher {
+            fn ft_shares_of(&self, account_id: #near_sdk::AccountId) -> #near_sdk::json_types::U128 {
+                #me::standard::nep141::shares::Nep141SharesController::shares_of(self, &account_id).into()
+            }
+
+            fn ft_total_shares(&self) -> #near_sdk::json_types::U128 {
+                #me::standard::nep141::shares::Nep141SharesController::total_shares(self).into()
+            }
+        }
+    })
+}
@@ -1,4 +1,4 @@
-use darling::FromDeriveInput;
+use darling::{util::Flag, FromDeriveInput};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Expr, Type};
@@ -9,6 +9,7 @@ pub struct Nep145Meta {
     pub storage_key: Option<Expr>,
     pub all_hooks: Option<Type>,
     pub force_unregister_hook: Option<Type>,
+    pub sponsorship: Flag,
     pub generics: syn::Generics,
     pub ident: syn::Ident,
 
@@ -24,6 +25,7 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
         storage_key,
         all_hooks,
         force_unregister_hook,
+        sponsorship,
         generics,
         ident,
 
@@ -45,6 +47,131 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
     let force_unregister_hook =
         force_unregister_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
 
+    let sponsorship = sponsorship.is_present().then(|| {
+        quote! {
+            #[#near_sdk::near]
+            impl #imp #me::standard::nep145::Nep145Sponsorship for #ident #ty #wher {
+                #[payable]
+                fn storage_sponsor_deposit(
+                    &mut self,
+                    policy: Option<#me::standard::nep145::SponsorPolicy>,
+                    account_cap: Option<#near_sdk::NearToken>,
+                ) -> #me::standard::nep145::SponsorPool {
+                    use #me::standard::nep145::*;
+                    use #near_sdk::env;
+
+                    let predecessor = env::predecessor_account_id();
+
+                    let pool = Nep145Controller::deposit_to_sponsor_pool(
+                        self,
+                        &predecessor,
+                        env::attached_deposit(),
+                    )
+                    .unwrap_or_else(|e| env::panic_str(&format!("Sponsor deposit error: {}", e)));
+
+                    if policy.is_none() && account_cap.is_none() {
+                        return pool;
+                    }
+
+                    Nep145Controller::set_sponsor_policy(
+                        self,
+                        &predecessor,
+                        policy.unwrap_or(pool.policy),
+                        account_cap.or(pool.account_cap),
+                    )
+                    .unwrap_or_else(|e| env::panic_str(&e.to_string()))
+                }
+
+                #[payable]
+                fn storage_sponsor_withdraw(
+                    &mut self,
+                    amount: Option<#near_sdk::NearToken>,
+                ) -> #me::standard::nep145::SponsorPool {
+                    use #me::standard::nep145::*;
+                    use #near_sdk::{env, Promise};
+
+                    #near_sdk::assert_one_yocto();
+
+                    let predecessor = env::predecessor_account_id();
+
+                    let pool = Nep145Controller::get_sponsor_pool(self, &predecessor)
+                        .unwrap_or_else(|| {
+                            env::panic_str(&error::SponsorPoolNotFoundError(predecessor.clone()).to_string())
+                        });
+
+                    let amount = amount.unwrap_or(pool.available);
+
+                    if amount.is_zero() {
+                        return pool;
+                    }
+
+                    let new_pool = Nep145Controller::withdraw_from_sponsor_pool(self, &predecessor, amount)
+                        .unwrap_or_else(|e| env::panic_str(&format!("Sponsor withdraw error: {}", e)));
+
+                    Promise::new(predecessor).transfer(amount);
+
+                    new_pool
+                }
+
+                #[payable]
+                fn storage_sponsor_configure(
+                    &mut self,
+                    policy: #me::standard::nep145::SponsorPolicy,
+                    account_cap: Option<#near_sdk::NearToken>,
+                ) -> #me::standard::nep145::SponsorPool {
+                    use #me::standard::nep145::*;
+                    use #near_sdk::env;
+
+                    #near_sdk::assert_one_yocto();
+
+                    Nep145Controller::set_sponsor_policy(
+                        self,
+                        &env::predecessor_account_id(),
+                        policy,
+                        account_cap,
+                    )
+                    .unwrap_or_else(|e| env::panic_str(&e.to_string()))
+                }
+
+                #[payable]
+                fn storage_sponsor_add(&mut self, account_id: #near_sdk::AccountId) {
+                    use #me::standard::nep145::*;
+                    use #near_sdk::env;
+
+                    #near_sdk::assert_one_yocto();
+
+                    Nep145Controller::sponsor_account(self, &env::predecessor_account_id(), &account_id)
+                        .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+                }
+
+                #[payable]
+                fn storage_sponsor_join(&mut self, sponsor_id: #near_sdk::AccountId) {
+                    use #me::standard::nep145::*;
+                    use #near_sdk::env;
+
+                    #near_sdk::assert_one_yocto();
+
+                    Nep145Controller::join_sponsor_pool(self, &sponsor_id, &env::predecessor_account_id())
+                        .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+                }
+
+                fn storage_sponsor_pool(
+                    &self,
+                    sponsor_id: #near_sdk::AccountId,
+                ) -> Option<#me::standard::nep145::SponsorPool> {
+                    #me::standard::nep145::Nep145Controller::get_sponsor_pool(self, &sponsor_id)
+                }
+
+                fn storage_sponsorship_of(
+                    &self,
+                    account_id: #near_sdk::AccountId,
+                ) -> Option<#me::standard::nep145::Sponsorship> {
+                    #me::standard::nep145::Nep145Controller::get_sponsorship(self, &account_id)
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::standard::nep145::Nep145ControllerInternal for #ident #ty #wher {
             type ForceUnregisterHook = (#force_unregister_hook, #all_hooks);
@@ -152,5 +279,7 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
                 #me::standard::nep145::Nep145Controller::get_storage_balance_bounds(self)
             }
         }
+
+        #sponsorship
     })
 }
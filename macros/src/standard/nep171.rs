@@ -1,10 +1,28 @@
-use darling::FromDeriveInput;
+use darling::{util::Flag, FromDeriveInput, FromMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_quote, Expr, Type};
 
 use crate::unitify;
 
+/// Version of the NEP-171 event standard, e.g. `"1.1.0"`. Expands to the
+/// name of the corresponding `Nep171EventVersion` variant.
+#[derive(Debug, Clone)]
+pub struct EventVersion(syn::Ident);
+
+impl FromMeta for EventVersion {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "1.0.0" => Ok(Self(parse_quote! { V1_0_0 })),
+            "1.1.0" => Ok(Self(parse_quote! { V1_1_0 })),
+            "1.2.0" => Ok(Self(parse_quote! { V1_2_0 })),
+            _ => Err(darling::Error::custom(format!(
+                r#"Unsupported NEP-171 event version "{value}", expected "1.0.0", "1.1.0", or "1.2.0""#,
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(nep171), supports(struct_named))]
 pub struct Nep171Meta {
@@ -15,6 +33,9 @@ pub struct Nep171Meta {
     pub burn_hook: Option<Type>,
     pub check_external_transfer: Option<Type>,
     pub token_data: Option<Type>,
+    pub event_version: Option<EventVersion>,
+    pub transfer_hook_log_length: Option<Expr>,
+    pub auto_register: Flag,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -35,6 +56,9 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
         burn_hook,
         check_external_transfer,
         token_data,
+        event_version,
+        transfer_hook_log_length,
+        auto_register,
 
         generics,
         ident,
@@ -51,6 +75,19 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
         parse_quote! { #me::standard::nep171::DefaultCheckExternalTransfer }
     });
 
+    let check_deposit = if auto_register.is_present() {
+        quote! {
+            // The refund promise is scheduled when dropped.
+            let _ = #me::standard::nep145::register_receiver_with_deposit(
+                self,
+                &#near_sdk::env::predecessor_account_id(),
+                &receiver_id,
+            );
+        }
+    } else {
+        quote! { #near_sdk::assert_one_yocto(); }
+    };
+
     let root = storage_key.map(|storage_key| {
         quote! {
             fn root() -> #me::slot::Slot<()> {
@@ -59,6 +96,19 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let event_version = event_version.map(|EventVersion(version)| {
+        quote! {
+            const EVENT_VERSION: #me::standard::nep171::event::Nep171EventVersion =
+                #me::standard::nep171::event::Nep171EventVersion::#version;
+        }
+    });
+
+    let transfer_hook_log_length = transfer_hook_log_length.map(|length| {
+        quote! {
+            const TRANSFER_HOOK_LOG_LENGTH: usize = #length;
+        }
+    });
+
     let all_hooks = unitify(all_hooks);
     let mint_hook = unitify(mint_hook);
     let transfer_hook = unitify(transfer_hook);
@@ -73,6 +123,9 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             type CheckExternalTransfer = #check_external_transfer;
             type LoadTokenMetadata = #token_data;
 
+            #event_version
+            #transfer_hook_log_length
+
             #root
         }
 
@@ -122,6 +175,48 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             }
         }
 
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep171::Nep171BatchTransfer for #ident #ty #wher {
+            #[payable]
+            fn nft_batch_transfer(
+                &mut self,
+                receiver_id: #near_sdk::AccountId,
+                token_ids: Vec<#me::standard::nep171::TokenId>,
+                approval_ids: Option<Vec<Option<u32>>>,
+                memo: Option<String>,
+            ) {
+                use #me::standard::nep171::*;
+
+                #check_deposit
+
+                let approval_ids = approval_ids.unwrap_or_else(|| vec![None; token_ids.len()]);
+
+                #near_sdk::require!(
+                    approval_ids.len() == token_ids.len(),
+                    "approval_ids must have the same length as token_ids",
+                );
+
+                let sender_id = #near_sdk::env::predecessor_account_id();
+
+                let transfers = token_ids
+                    .into_iter()
+                    .zip(approval_ids)
+                    .map(|(token_id, approval_id)| action::Nep171Transfer {
+                        token_id,
+                        authorization: approval_id.map(Nep171TransferAuthorization::ApprovalId).unwrap_or(Nep171TransferAuthorization::Owner),
+                        sender_id: sender_id.clone().into(),
+                        receiver_id: receiver_id.clone().into(),
+                        memo: memo.clone().map(Into::into),
+                        msg: None,
+                        revert: false,
+                    })
+                    .collect::<Vec<_>>();
+
+                <Self as Nep171Controller>::external_batch_transfer(self, &transfers)
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+        }
+
         #[#near_sdk::near]
         impl #imp #me::standard::nep171::Nep171 for #ident #ty #wher {
             #[payable]
@@ -134,7 +229,7 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             ) {
                 use #me::standard::nep171::*;
 
-                #near_sdk::assert_one_yocto();
+                #check_deposit
 
                 let sender_id = #near_sdk::env::predecessor_account_id();
 
@@ -163,7 +258,7 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             ) -> #near_sdk::PromiseOrValue<bool> {
                 use #me::standard::nep171::*;
 
-                #near_sdk::assert_one_yocto();
+                #check_deposit
 
                 #near_sdk::require!(
                     #near_sdk::env::prepaid_gas() >= GAS_FOR_NFT_TRANSFER_CALL,
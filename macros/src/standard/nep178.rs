@@ -13,6 +13,7 @@ pub struct Nep178Meta {
     pub approve_hook: Option<Type>,
     pub revoke_hook: Option<Type>,
     pub revoke_all_hook: Option<Type>,
+    pub max_approvals: Option<Expr>,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -31,6 +32,7 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
         approve_hook,
         revoke_hook,
         revoke_all_hook,
+        max_approvals,
 
         generics,
         ident,
@@ -49,49 +51,80 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let max_approvals = max_approvals.map(|max_approvals| {
+        quote! {
+            const MAX_APPROVALS: u64 = #max_approvals;
+        }
+    });
+
     let all_hooks = unitify(all_hooks);
     let approve_hook = unitify(approve_hook);
     let revoke_hook = unitify(revoke_hook);
     let revoke_all_hook = unitify(revoke_all_hook);
 
+    let approve_body = quote! {
+        use #me::standard::nep178::*;
+
+        #me::utils::assert_nonzero_deposit();
+
+        let predecessor = #near_sdk::env::predecessor_account_id();
+
+        let action = action::Nep178Approve {
+            token_id: token_id.clone(),
+            current_owner_id: predecessor.clone().into(),
+            account_id: account_id.clone().into(),
+            expires_at,
+        };
+
+        let approval_id = Nep178Controller::approve(self, &action)
+            .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+
+        msg.map_or(#near_sdk::PromiseOrValue::Value(()), |msg| {
+            ext_nep178_receiver::ext(account_id)
+                .nft_on_approve(token_id, predecessor, approval_id, msg)
+                .into()
+        })
+    };
+
     Ok(quote! {
         impl #imp #me::standard::nep178::Nep178ControllerInternal for #ident #ty #wher {
             type ApproveHook = (#approve_hook, #all_hooks);
             type RevokeHook = (#revoke_hook, #all_hooks);
             type RevokeAllHook = (#revoke_all_hook, #all_hooks);
 
+            #max_approvals
+
             #root
         }
 
         #[#near_sdk::near]
-        impl #imp #me::standard::nep178::Nep178 for #ident #ty #wher {
+        impl #imp #me::standard::nep178::Nep178Expiry for #ident #ty #wher {
             #[payable]
-            fn nft_approve(
+            fn nft_approve_with_expiry(
                 &mut self,
                 token_id: #me::standard::nep171::TokenId,
                 account_id: #near_sdk::AccountId,
+                expires_at: #near_sdk::json_types::U64,
                 msg: Option<String>,
             ) -> #near_sdk::PromiseOrValue<()> {
-                use #me::standard::nep178::*;
-
-                #me::utils::assert_nonzero_deposit();
+                let expires_at = Some(expires_at.0);
 
-                let predecessor = #near_sdk::env::predecessor_account_id();
-
-                let action = action::Nep178Approve {
-                    token_id: token_id.clone(),
-                    current_owner_id: predecessor.clone().into(),
-                    account_id: account_id.clone().into(),
-                };
+                #approve_body
+            }
+        }
 
-                let approval_id = Nep178Controller::approve(self, &action)
-                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep178::Nep178 for #ident #ty #wher {
+            #[payable]
+            fn nft_approve(
+                &mut self,
+                token_id: #me::standard::nep171::TokenId,
+                account_id: #near_sdk::AccountId,
+                msg: Option<String>,
+            ) -> #near_sdk::PromiseOrValue<()> {
+                let expires_at = None;
 
-                msg.map_or(#near_sdk::PromiseOrValue::Value(()), |msg| {
-                    ext_nep178_receiver::ext(account_id)
-                        .nft_on_approve(token_id, predecessor, approval_id, msg)
-                        .into()
-                })
+                #approve_body
             }
 
             #[payable]
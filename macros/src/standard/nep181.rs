@@ -1,12 +1,15 @@
 use darling::FromDeriveInput;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Expr;
+use syn::{Expr, Type};
 
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(nep181), supports(struct_named))]
 pub struct Nep181Meta {
     pub storage_key: Option<Expr>,
+    pub index_storage_key: Option<Expr>,
+    #[darling(multiple, rename = "index")]
+    pub indexes: Vec<Type>,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -21,6 +24,8 @@ pub struct Nep181Meta {
 pub fn expand(meta: Nep181Meta) -> Result<TokenStream, darling::Error> {
     let Nep181Meta {
         storage_key,
+        index_storage_key,
+        indexes,
 
         generics,
         ident,
@@ -39,11 +44,84 @@ pub fn expand(meta: Nep181Meta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let index = (!indexes.is_empty()).then(|| {
+        let root = index_storage_key.map(|storage_key| {
+            quote! {
+                fn root() -> #me::slot::Slot<()> {
+                    #me::slot::Slot::root(#storage_key)
+                }
+            }
+        });
+
+        quote! {
+            impl #imp #me::standard::nep181::index::Nep181IndexInternal for #ident #ty #wher {
+                #root
+            }
+
+            #[#near_sdk::near]
+            impl #imp #me::standard::nep181::index::Nep181IndexExternal for #ident #ty #wher {
+                fn nft_supply_by_index(
+                    &self,
+                    index: String,
+                    key: Option<String>,
+                ) -> #near_sdk::json_types::U128 {
+                    use #me::standard::nep181::index::{Nep181Index, TokenIndex};
+
+                    #near_sdk::require!(
+                        [#(<#indexes as TokenIndex<Self>>::NAME),*].contains(&index.as_str()),
+                        "Unknown index",
+                    );
+
+                    u128::from(Nep181Index::index_supply(self, &index, key.as_deref())).into()
+                }
+
+                fn nft_tokens_by_index(
+                    &self,
+                    index: String,
+                    key: Option<String>,
+                    from_index: Option<#near_sdk::json_types::U128>,
+                    limit: Option<u32>,
+                ) -> Vec<#me::standard::nep171::Token> {
+                    use #me::standard::{
+                        nep171::Nep171Controller,
+                        nep181::index::{Nep181Index, TokenIndex},
+                    };
+
+                    #near_sdk::require!(
+                        [#(<#indexes as TokenIndex<Self>>::NAME),*].contains(&index.as_str()),
+                        "Unknown index",
+                    );
+
+                    let from_index = from_index.map_or(0, |i| {
+                        usize::try_from(i.0).unwrap_or_else(|_| {
+                            #near_sdk::env::panic_str("from_index is out of range")
+                        })
+                    });
+
+                    Nep181Index::index_tokens(
+                        self,
+                        &index,
+                        key.as_deref(),
+                        from_index,
+                        limit.map(|l| l as usize),
+                    )
+                    .into_iter()
+                    .map(|token_id| Nep171Controller::load_token(self, &token_id).unwrap_or_else(|| {
+                        #near_sdk::env::panic_str(&format!("Inconsistent state: Token `{}` is in index `{}` but its metadata could not be loaded.", token_id, index))
+                    }))
+                    .collect()
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::standard::nep181::Nep181ControllerInternal for #ident #ty #wher {
             #root
         }
 
+        #index
+
         #[#near_sdk::near]
         impl #imp #me::standard::nep181::Nep181 for #ident #ty #wher {
             fn nft_total_supply(&self) -> #near_sdk::json_types::U128 {
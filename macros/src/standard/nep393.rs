@@ -0,0 +1,268 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Type};
+
+use super::authority::Authority;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(nep393), supports(struct_named))]
+pub struct Nep393Meta {
+    pub storage_key: Option<Expr>,
+    pub authority: Authority,
+    pub all_hooks: Option<Type>,
+    pub mint_hook: Option<Type>,
+    pub revoke_hook: Option<Type>,
+    pub renew_hook: Option<Type>,
+    pub recover_hook: Option<Type>,
+
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: Nep393Meta) -> Result<TokenStream, darling::Error> {
+    let Nep393Meta {
+        storage_key,
+        authority,
+        all_hooks,
+        mint_hook,
+        revoke_hook,
+        renew_hook,
+        recover_hook,
+
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    let mint_hook = mint_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let revoke_hook = revoke_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let renew_hook = renew_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let recover_hook = recover_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+
+    let default_hook = all_hooks.map_or_else(|| quote! { () }, |h| quote! { #h });
+
+    let authority_implementation = authority.expand(
+        &me,
+        &ident,
+        &generics,
+        &quote! { #me::standard::nep393::Nep393Authority },
+        "require_issuer",
+    );
+
+    Ok(quote! {
+        impl #imp #me::standard::nep393::Nep393ControllerInternal for #ident #ty #wher {
+            type MintHook = (#mint_hook, #default_hook);
+            type RevokeHook = (#revoke_hook, #default_hook);
+            type RenewHook = (#renew_hook, #default_hook);
+            type RecoverHook = (#recover_hook, #default_hook);
+
+            #root
+        }
+
+        #authority_implementation
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep393::Nep393 for #ident #ty #wher {
+            #[payable]
+            fn sbt_mint(
+                &mut self,
+                token_spec: Vec<(#near_sdk::AccountId, Vec<#me::standard::nep393::TokenMetadata>)>,
+            ) -> Vec<#me::standard::nep393::TokenId> {
+                use #me::standard::nep393::{action::Nep393Mint, *};
+
+                Nep393Authority::require_issuer(self);
+
+                let initial_storage_usage = #near_sdk::env::storage_usage();
+                let mut token_ids = vec![];
+
+                for (owner_id, metadata) in token_spec {
+                    token_ids.extend(
+                        Nep393Controller::mint(self, &Nep393Mint::new(owner_id, metadata))
+                            .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string())),
+                    );
+                }
+
+                // The refund promise is scheduled when dropped.
+                let _ = #me::utils::apply_storage_fee_and_refund(initial_storage_usage, 0);
+
+                token_ids
+            }
+
+            #[payable]
+            fn sbt_recover(
+                &mut self,
+                from: #near_sdk::AccountId,
+                to: #near_sdk::AccountId,
+            ) -> (u32, bool) {
+                use #me::standard::nep393::{action::Nep393Recover, *};
+
+                Nep393Authority::require_issuer(self);
+
+                let initial_storage_usage = #near_sdk::env::storage_usage();
+
+                let token_ids = Nep393Controller::recover(self, &Nep393Recover::new(from, to))
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+
+                // The refund promise is scheduled when dropped.
+                let _ = #me::utils::apply_storage_fee_and_refund(initial_storage_usage, 0);
+
+                // Recovery is never partial.
+                (token_ids.len().try_into().unwrap_or(u32::MAX), true)
+            }
+
+            #[payable]
+            fn sbt_renew(&mut self, tokens: Vec<#me::standard::nep393::TokenId>, expires_at: u64) {
+                use #me::standard::nep393::{action::Nep393Renew, *};
+
+                #near_sdk::assert_one_yocto();
+                Nep393Authority::require_issuer(self);
+
+                Nep393Controller::renew(self, &Nep393Renew::new(tokens, expires_at))
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+
+            #[payable]
+            fn sbt_revoke(&mut self, tokens: Vec<#me::standard::nep393::TokenId>, burn: bool) {
+                use #me::standard::nep393::{action::Nep393Revoke, *};
+
+                #near_sdk::assert_one_yocto();
+                Nep393Authority::require_issuer(self);
+
+                Nep393Controller::revoke(self, &Nep393Revoke::new(tokens, burn))
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+
+            fn sbt(
+                &self,
+                issuer: #near_sdk::AccountId,
+                token: #me::standard::nep393::TokenId,
+            ) -> Option<#me::standard::nep393::Token> {
+                if issuer != #near_sdk::env::current_account_id() {
+                    return None;
+                }
+
+                #me::standard::nep393::Nep393Controller::token(self, token)
+            }
+
+            fn sbt_supply(&self, issuer: #near_sdk::AccountId) -> u64 {
+                if issuer != #near_sdk::env::current_account_id() {
+                    return 0;
+                }
+
+                #me::standard::nep393::Nep393Controller::supply(self)
+            }
+
+            fn sbt_supply_by_owner(
+                &self,
+                account: #near_sdk::AccountId,
+                issuer: #near_sdk::AccountId,
+                class: Option<#me::standard::nep393::ClassId>,
+            ) -> u64 {
+                use #me::standard::nep393::Nep393Controller;
+
+                if issuer != #near_sdk::env::current_account_id() {
+                    return 0;
+                }
+
+                match class {
+                    Some(class) => {
+                        Nep393Controller::token_by_owner_class(self, &account, class).map_or(0, |_| 1)
+                    }
+                    None => Nep393Controller::supply_by_owner(self, &account),
+                }
+            }
+
+            fn sbt_supply_by_class(
+                &self,
+                issuer: #near_sdk::AccountId,
+                class: #me::standard::nep393::ClassId,
+            ) -> u64 {
+                if issuer != #near_sdk::env::current_account_id() {
+                    return 0;
+                }
+
+                #me::standard::nep393::Nep393Controller::supply_by_class(self, class)
+            }
+
+            fn sbt_tokens(
+                &self,
+                issuer: #near_sdk::AccountId,
+                from_token: Option<#me::standard::nep393::TokenId>,
+                limit: Option<u32>,
+                with_expired: bool,
+            ) -> Vec<#me::standard::nep393::Token> {
+                use #me::standard::nep393::{Nep393Controller, MAX_SBT_TOKENS_SCANNED};
+
+                if issuer != #near_sdk::env::current_account_id() {
+                    return vec![];
+                }
+
+                let now = #near_sdk::env::block_timestamp_ms();
+                let limit = limit.map_or(usize::MAX, |l| l as usize);
+                let from_token = from_token.unwrap_or(1).max(1);
+                let to_token = Nep393Controller::last_token_id(self)
+                    .min(from_token.saturating_add(MAX_SBT_TOKENS_SCANNED - 1));
+
+                (from_token..=to_token)
+                    .filter_map(|token_id| Nep393Controller::token(self, token_id))
+                    .filter(|token| with_expired || !token.metadata.is_expired(now))
+                    .take(limit)
+                    .collect()
+            }
+
+            fn sbt_tokens_by_owner(
+                &self,
+                account: #near_sdk::AccountId,
+                issuer: Option<#near_sdk::AccountId>,
+                from_class: Option<#me::standard::nep393::ClassId>,
+                limit: Option<u32>,
+                with_expired: bool,
+            ) -> Vec<(#near_sdk::AccountId, Vec<#me::standard::nep393::OwnedToken>)> {
+                use #me::standard::nep393::Nep393Controller;
+
+                let current_account_id = #near_sdk::env::current_account_id();
+
+                if issuer.is_some_and(|issuer| issuer != current_account_id) {
+                    return vec![];
+                }
+
+                let now = #near_sdk::env::block_timestamp_ms();
+                let from_class = from_class.unwrap_or(0);
+                let limit = limit.map_or(usize::MAX, |l| l as usize);
+
+                let tokens = Nep393Controller::tokens_for_owner(self, &account)
+                    .into_iter()
+                    .filter(|token| token.metadata.class >= from_class)
+                    .filter(|token| with_expired || !token.metadata.is_expired(now))
+                    .take(limit)
+                    .map(Into::into)
+                    .collect::<Vec<_>>();
+
+                if tokens.is_empty() {
+                    vec![]
+                } else {
+                    vec![(current_account_id, tokens)]
+                }
+            }
+        }
+    })
+}
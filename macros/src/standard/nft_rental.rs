@@ -0,0 +1,97 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Type};
+
+use crate::unitify;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(nft_rental), supports(struct_named))]
+pub struct NftRentalMeta {
+    pub storage_key: Option<Expr>,
+    pub set_user_hook: Option<Type>,
+
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: NftRentalMeta) -> Result<TokenStream, darling::Error> {
+    let NftRentalMeta {
+        storage_key,
+        set_user_hook,
+
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    let set_user_hook = unitify(set_user_hook);
+
+    Ok(quote! {
+        impl #imp #me::standard::nft_rental::NftRentalInternal for #ident #ty #wher {
+            type SetUserHook = #set_user_hook;
+
+            #root
+        }
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nft_rental::NftRentalExternal for #ident #ty #wher {
+            #[payable]
+            fn nft_set_user(
+                &mut self,
+                token_id: #me::standard::nep171::TokenId,
+                user_id: #near_sdk::AccountId,
+                expires_at: #near_sdk::json_types::U64,
+            ) {
+                use #me::standard::nft_rental::*;
+
+                #near_sdk::assert_one_yocto();
+
+                let predecessor = #near_sdk::env::predecessor_account_id();
+
+                let action = NftSetUser {
+                    token_id,
+                    current_owner_id: predecessor.into(),
+                    user_id: user_id.into(),
+                    expires_at: expires_at.0,
+                };
+
+                NftRental::set_user(self, &action)
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+
+            fn nft_user_of(
+                &self,
+                token_id: #me::standard::nep171::TokenId,
+            ) -> Option<#near_sdk::AccountId> {
+                #me::standard::nft_rental::NftRental::user_of(self, &token_id)
+            }
+
+            fn nft_user_expires(
+                &self,
+                token_id: #me::standard::nep171::TokenId,
+            ) -> Option<#near_sdk::json_types::U64> {
+                #me::standard::nft_rental::NftRental::rental_of(self, &token_id)
+                    .map(|rental| rental.expires_at)
+            }
+        }
+    })
+}
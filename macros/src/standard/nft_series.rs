@@ -0,0 +1,120 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(nft_series), supports(struct_named))]
+pub struct NftSeriesMeta {
+    pub storage_key: Option<Expr>,
+
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: NftSeriesMeta) -> Result<TokenStream, darling::Error> {
+    let NftSeriesMeta {
+        storage_key,
+
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #imp #me::standard::nep177::series::NftSeriesInternal for #ident #ty #wher {
+            #root
+        }
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep177::series::NftSeriesExternal for #ident #ty #wher {
+            #[payable]
+            fn nft_mint_from_series(
+                &mut self,
+                series_id: #me::standard::nep177::series::SeriesId,
+                receiver_id: Option<#near_sdk::AccountId>,
+            ) -> #me::standard::nep171::TokenId {
+                use #me::standard::nep177::series::*;
+                use #near_sdk::{env, NearToken, Promise};
+
+                let series = NftSeries::series(self, series_id)
+                    .unwrap_or_else(|| env::panic_str(&SeriesDoesNotExistError { series_id }.to_string()));
+
+                let price = series.price.unwrap_or(NearToken::from_yoctonear(0));
+                let attached = env::attached_deposit();
+                if attached < price {
+                    env::panic_str(&format!(
+                        "Attached deposit {} is less than price {}",
+                        attached, price,
+                    ));
+                }
+
+                let predecessor = env::predecessor_account_id();
+                let initial_storage_usage = env::storage_usage();
+
+                let token_id = NftSeries::mint_from_series(
+                    self,
+                    series_id,
+                    receiver_id.as_ref().unwrap_or(&predecessor),
+                )
+                .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+                // The refund promise is scheduled when dropped.
+                let _ = #me::utils::apply_storage_fee_and_refund(
+                    initial_storage_usage,
+                    price.as_yoctonear(),
+                );
+
+                if !price.is_zero() {
+                    Promise::new(series.creator_id).transfer(price);
+                }
+
+                token_id
+            }
+
+            fn nft_get_series(
+                &self,
+                series_id: #me::standard::nep177::series::SeriesId,
+            ) -> Option<#me::standard::nep177::series::Series> {
+                #me::standard::nep177::series::NftSeries::series(self, series_id)
+            }
+
+            fn nft_series_count(&self) -> #me::standard::nep177::series::SeriesId {
+                #me::standard::nep177::series::NftSeries::series_count(self)
+            }
+
+            fn nft_series(
+                &self,
+                from_index: Option<#me::standard::nep177::series::SeriesId>,
+                limit: Option<#me::standard::nep177::series::SeriesId>,
+            ) -> Vec<#me::standard::nep177::series::Series> {
+                use #me::standard::nep177::series::*;
+
+                let from_index = from_index.unwrap_or(0);
+                let end = limit.map_or(SeriesId::MAX, |limit| from_index.saturating_add(limit));
+
+                (from_index..end.min(NftSeries::series_count(self)))
+                    .filter_map(|series_id| NftSeries::series(self, series_id))
+                    .collect()
+            }
+        }
+    })
+}
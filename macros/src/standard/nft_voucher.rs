@@ -0,0 +1,142 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Expr, Type};
+
+use super::authority::Authority;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(nft_voucher), supports(struct_named))]
+pub struct NftVoucherMeta {
+    pub storage_key: Option<Expr>,
+    pub authority: Authority,
+    pub payout: Option<Type>,
+
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: NftVoucherMeta) -> Result<TokenStream, darling::Error> {
+    let NftVoucherMeta {
+        storage_key,
+        authority,
+        payout,
+
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    let payout =
+        payout.unwrap_or_else(|| parse_quote! { #me::standard::nep177::voucher::CreatorPayout });
+
+    let authority_implementation = authority.expand(
+        &me,
+        &ident,
+        &generics,
+        &quote! { #me::standard::nep177::voucher::NftVoucherAuthority },
+        "require_voucher_authority",
+    );
+
+    Ok(quote! {
+        impl #imp #me::standard::nep177::voucher::NftVoucherInternal for #ident #ty #wher {
+            type Payout = #payout;
+
+            #root
+        }
+
+        #authority_implementation
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep177::voucher::NftVoucherExternal for #ident #ty #wher {
+            #[payable]
+            fn nft_redeem_voucher(
+                &mut self,
+                voucher: #me::standard::nep177::voucher::MintVoucher,
+                metadata: #me::standard::nep177::TokenMetadata,
+                signature: #near_sdk::json_types::Base64VecU8,
+                receiver_id: Option<#near_sdk::AccountId>,
+            ) -> #me::standard::nep171::TokenId {
+                use #me::standard::nep177::voucher::*;
+                use #near_sdk::{env, Promise};
+
+                let attached = env::attached_deposit();
+                let refund = attached.checked_sub(voucher.price).unwrap_or_else(|| {
+                    env::panic_str(&format!(
+                        "Attached deposit {} is less than price {}",
+                        attached, voucher.price,
+                    ))
+                });
+
+                let predecessor = env::predecessor_account_id();
+
+                let payouts = NftVoucher::redeem_voucher(
+                    self,
+                    &voucher,
+                    &metadata,
+                    &signature.0,
+                    receiver_id.as_ref().unwrap_or(&predecessor),
+                )
+                .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+                for (account_id, amount) in payouts {
+                    if !amount.is_zero() {
+                        Promise::new(account_id).transfer(amount);
+                    }
+                }
+
+                if !refund.is_zero() {
+                    Promise::new(predecessor).transfer(refund);
+                }
+
+                voucher.token_id
+            }
+
+            #[payable]
+            fn nft_set_creator_key(
+                &mut self,
+                creator_id: #near_sdk::AccountId,
+                public_key: Option<#near_sdk::PublicKey>,
+            ) {
+                use #me::standard::nep177::voucher::*;
+
+                #near_sdk::assert_one_yocto();
+                NftVoucherAuthority::require_voucher_authority(self);
+                NftVoucher::set_creator_key(self, &creator_id, public_key.as_ref())
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+
+            fn nft_creator_key(
+                &self,
+                creator_id: #near_sdk::AccountId,
+            ) -> Option<#near_sdk::PublicKey> {
+                #me::standard::nep177::voucher::NftVoucher::creator_key(self, &creator_id)
+            }
+
+            fn nft_is_voucher_consumed(
+                &self,
+                voucher: #me::standard::nep177::voucher::MintVoucher,
+            ) -> bool {
+                #me::standard::nep177::voucher::NftVoucher::is_voucher_consumed(self, &voucher)
+            }
+        }
+    })
+}
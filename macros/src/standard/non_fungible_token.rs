@@ -1,4 +1,4 @@
-use darling::FromDeriveInput;
+use darling::{util::Flag, FromDeriveInput};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_quote, Expr, Type};
@@ -15,6 +15,9 @@ pub struct NonFungibleTokenMeta {
     // NEP-145 fields
     pub storage_management_storage_key: Option<Expr>,
     pub force_unregister_hook: Option<Type>,
+    pub sponsorship: Flag,
+    pub auto_register: Flag,
+    pub transfer_on_force_unregister: Flag,
 
     // NEP-171 fields
     pub core_storage_key: Option<Expr>,
@@ -23,6 +26,7 @@ pub struct NonFungibleTokenMeta {
     pub burn_hook: Option<Type>,
     pub token_data: Option<Type>,
     pub check_external_transfer: Option<Type>,
+    pub event_version: Option<nep171::EventVersion>,
 
     // NEP-177 fields
     pub metadata_storage_key: Option<Expr>,
@@ -32,9 +36,13 @@ pub struct NonFungibleTokenMeta {
     pub approve_hook: Option<Type>,
     pub revoke_hook: Option<Type>,
     pub revoke_all_hook: Option<Type>,
+    pub max_approvals: Option<Expr>,
 
     // NEP-181 fields
     pub enumeration_storage_key: Option<Expr>,
+    pub index_storage_key: Option<Expr>,
+    #[darling(multiple, rename = "index")]
+    pub indexes: Vec<Type>,
 
     // darling
     pub generics: syn::Generics,
@@ -53,6 +61,9 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
         storage_management_storage_key,
         force_unregister_hook,
+        sponsorship,
+        auto_register,
+        transfer_on_force_unregister,
 
         core_storage_key,
         mint_hook,
@@ -60,6 +71,7 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         burn_hook,
         token_data,
         check_external_transfer,
+        event_version,
 
         metadata_storage_key,
 
@@ -67,8 +79,11 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         approve_hook,
         revoke_hook,
         revoke_all_hook,
+        max_approvals,
 
         enumeration_storage_key,
+        index_storage_key,
+        indexes,
 
         generics,
         ident,
@@ -77,15 +92,21 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         near_sdk,
     } = meta;
 
-    let all_hooks_inner = unitify(all_hooks.clone());
+    let all_hooks_inner = unitify(all_hooks);
     let force_unregister_hook = unitify(force_unregister_hook);
+    let on_force_unregister = if transfer_on_force_unregister.is_present() {
+        quote! { #me::standard::nep171::hooks::TransferNep171OnForceUnregisterHook }
+    } else {
+        quote! { #me::standard::nep171::hooks::BurnNep171OnForceUnregisterHook }
+    };
 
     let expand_nep145 = nep145::expand(nep145::Nep145Meta {
         storage_key: storage_management_storage_key,
         all_hooks: Some(all_hooks_inner.clone()),
         force_unregister_hook: Some(
-            parse_quote! { (#force_unregister_hook, #me::standard::nep171::hooks::BurnNep171OnForceUnregisterHook) },
+            parse_quote! { (#force_unregister_hook, #on_force_unregister) },
         ),
+        sponsorship,
         generics: generics.clone(),
         ident: ident.clone(),
         me: me.clone(),
@@ -94,6 +115,10 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
     let token_data = unitify(token_data);
 
+    let index_hooks = indexes.iter().rev().fold(quote! { () }, |rest, index| {
+        quote! { (#me::standard::nep181::index::TokenIndexing<#index>, #rest) }
+    });
+
     let expand_nep171 = nep171::expand(nep171::Nep171Meta {
         storage_key: core_storage_key,
         all_hooks: Some(parse_quote! { (
@@ -102,7 +127,7 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
                 #me::standard::nep145::hooks::Nep171StorageAccountingHook,
                 (
                     #me::standard::nep178::TokenApprovals,
-                    #me::standard::nep181::TokenEnumeration,
+                    (#me::standard::nep181::TokenEnumeration, #index_hooks),
                 ),
             ),
         ) }),
@@ -116,6 +141,11 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
             #token_data,
             (#me::standard::nep177::TokenMetadata, #me::standard::nep178::TokenApprovals),
         ) }),
+        event_version,
+        transfer_hook_log_length: Some(parse_quote! {
+            #me::standard::nep145::event::MAX_STORAGE_ACCOUNTING_LOG_LENGTH
+        }),
+        auto_register,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -136,10 +166,14 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
     let expand_nep178 = nep178::expand(nep178::Nep178Meta {
         storage_key: approval_storage_key,
-        all_hooks,
+        all_hooks: Some(parse_quote! { (
+            #all_hooks_inner,
+            #me::standard::nep145::hooks::Nep178StorageAccountingHook,
+        ) }),
         approve_hook,
         revoke_hook,
         revoke_all_hook,
+        max_approvals,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -149,6 +183,8 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
     let expand_nep181 = nep181::expand(nep181::Nep181Meta {
         storage_key: enumeration_storage_key,
+        index_storage_key,
+        indexes,
         generics,
         ident,
         me,
@@ -0,0 +1,63 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(supply_cap), supports(struct_named))]
+pub struct SupplyCapMeta {
+    pub storage_key: Option<Expr>,
+
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: SupplyCapMeta) -> Result<TokenStream, darling::Error> {
+    let SupplyCapMeta {
+        storage_key,
+
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    let root = storage_key.map(|storage_key| {
+        quote! {
+            fn root() -> #me::slot::Slot<()> {
+                #me::slot::Slot::root(#storage_key)
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #imp #me::standard::nep141::supply_cap::SupplyCapInternal for #ident #ty #wher {
+            #root
+        }
+
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep141::supply_cap::SupplyCapExternal for #ident #ty #wher {
+            fn ft_max_supply(&self) -> Option<#near_sdk::json_types::U128> {
+                #me::standard::nep141::supply_cap::SupplyCap::max_supply(self).map(Into::into)
+            }
+
+            fn ft_mint_rate_limit(&self) -> Option<#me::standard::nep141::supply_cap::MintRateLimit> {
+                #me::standard::nep141::supply_cap::SupplyCap::mint_rate_limit(self)
+            }
+
+            fn ft_remaining_mintable_supply(&self) -> Option<#near_sdk::json_types::U128> {
+                #me::standard::nep141::supply_cap::SupplyCap::remaining_mintable_supply(self)
+                    .map(Into::into)
+            }
+        }
+    })
+}
@@ -0,0 +1,58 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(wrapped_near), supports(struct_named))]
+pub struct WrappedNearMeta {
+    pub generics: syn::Generics,
+    pub ident: syn::Ident,
+
+    // crates
+    #[darling(rename = "crate", default = "crate::default_crate_name")]
+    pub me: syn::Path,
+    #[darling(default = "crate::default_near_sdk")]
+    pub near_sdk: syn::Path,
+}
+
+pub fn expand(meta: WrappedNearMeta) -> Result<TokenStream, darling::Error> {
+    let WrappedNearMeta {
+        generics,
+        ident,
+
+        me,
+        near_sdk,
+    } = meta;
+
+    let (imp, ty, wher) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[#near_sdk::near]
+        impl #imp #me::standard::nep141::wrapped_near::WrappedNearExternal for #ident #ty #wher {
+            #[payable]
+            fn near_deposit(&mut self) -> #near_sdk::json_types::U128 {
+                use #me::standard::nep141::wrapped_near::WrappedNear;
+                use #near_sdk::env;
+
+                WrappedNear::deposit_near(
+                    self,
+                    &env::predecessor_account_id(),
+                    env::attached_deposit(),
+                )
+                .unwrap_or_else(|e| env::panic_str(&e.to_string()))
+                .into()
+            }
+
+            #[payable]
+            fn near_withdraw(&mut self, amount: #near_sdk::json_types::U128) -> #near_sdk::Promise {
+                use #me::standard::nep141::wrapped_near::WrappedNear;
+                use #near_sdk::env;
+
+                #near_sdk::assert_one_yocto();
+
+                WrappedNear::withdraw_near(self, &env::predecessor_account_id(), amount.into())
+                    .unwrap_or_else(|e| env::panic_str(&e.to_string()))
+            }
+        }
+    })
+}
@@ -31,6 +31,24 @@ pub enum DefaultStorageKey {
     Rbac,
     /// Default storage key for [`escrow::EscrowInternal::root`]
     Escrow,
+    /// Default storage key for [`standard::nep141::supply_cap::SupplyCapInternal::root`].
+    SupplyCap,
+    /// Default storage key for [`standard::nep141::compliance::ComplianceInternal::root`].
+    Compliance,
+    /// Default storage key for [`standard::nep141::shares::Nep141SharesControllerInternal::root`].
+    Nep141Shares,
+    /// Default storage key for [`standard::nep177::series::NftSeriesInternal::root`].
+    Nep177Series,
+    /// Default storage key for [`standard::nep393::Nep393ControllerInternal::root`].
+    Nep393,
+    /// Default storage key for [`standard::nft_rental::NftRentalInternal::root`].
+    NftRental,
+    /// Default storage key for [`standard::nep177::voucher::NftVoucherInternal::root`].
+    Nep177Voucher,
+    /// Default storage key for [`standard::nep181::index::Nep181IndexInternal::root`].
+    Nep181Index,
+    /// Default storage key for [`migrate::BatchedMigrateInternal::root`].
+    Migrate,
 }
 
 impl near_sdk::IntoStorageKey for DefaultStorageKey {
@@ -48,6 +66,15 @@ impl near_sdk::IntoStorageKey for DefaultStorageKey {
             DefaultStorageKey::Pause => b"~p".to_vec(),
             DefaultStorageKey::Rbac => b"~r".to_vec(),
             DefaultStorageKey::Escrow => b"~es".to_vec(),
+            DefaultStorageKey::SupplyCap => b"~$141c".to_vec(),
+            DefaultStorageKey::Compliance => b"~$141f".to_vec(),
+            DefaultStorageKey::Nep141Shares => b"~$141s".to_vec(),
+            DefaultStorageKey::Nep177Series => b"~$177s".to_vec(),
+            DefaultStorageKey::Nep393 => b"~$393".to_vec(),
+            DefaultStorageKey::NftRental => b"~$171r".to_vec(),
+            DefaultStorageKey::Nep177Voucher => b"~$177v".to_vec(),
+            DefaultStorageKey::Nep181Index => b"~$181i".to_vec(),
+            DefaultStorageKey::Migrate => b"~mig".to_vec(),
         }
     }
 }
@@ -75,9 +102,9 @@ pub mod nft {
                 StorageBalance, StorageBalanceBounds,
             },
             nep171::{
-                self, action::*, ext_nep171, ext_nep171_receiver, ext_nep171_resolver, Nep171,
-                Nep171Controller, Nep171ControllerInternal, Nep171Receiver, Nep171Resolver, Token,
-                TokenId,
+                self, action::*, ext_nep171, ext_nep171_batch_transfer, ext_nep171_receiver,
+                ext_nep171_resolver, Nep171, Nep171BatchTransfer, Nep171Controller,
+                Nep171ControllerInternal, Nep171Receiver, Nep171Resolver, Token, TokenId,
             },
             nep177::{
                 self, ext_nep177, ContractMetadata, Nep177, Nep177Controller,
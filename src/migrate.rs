@@ -13,6 +13,32 @@
 //! failing. For a complete example checkout [upgrade_new.rs](https://github.com/near/near-sdk-contract-tools/blob/develop/workspaces-tests/src/bin/upgrade_new.rs)
 //! in workspace-tests.
 //!
+//! # Schema versions
+//!
+//! The schema version of the contract state is stored in a reserved slot
+//! (see [`MigrateInternal::slot_schema_version`]), and starts at 0.
+//! [`MigrateHook`] and [`BatchedMigrateHook`] declare the version they
+//! migrate from and to. Migrating from any other version is refused, and a
+//! successful (or, for batched migrations, completed) migration emits a
+//! [`MigrateEvent::Migrate`] event.
+//!
+//! Records that are too numerous to migrate in a single call (e.g. one per
+//! account) can instead be stored in a [`VersionedSlot`](crate::slot::VersionedSlot),
+//! which upgrades each record lazily when it is read.
+//!
+//! # Batched migration
+//!
+//! When there is too much state to migrate within the gas limit of a single
+//! call, [`BatchedMigrateController`] migrates it in steps instead. The
+//! migration is started with [`BatchedMigrateController::start_migration`]
+//! (e.g. by the [`PostUpgrade`](crate::upgrade::PostUpgrade) call of an
+//! upgrade), and [`BatchedMigrateController::step_migration`] is called
+//! repeatedly until the migration is complete. Each step invokes
+//! [`BatchedMigrateHook::migrate_batch`] with a cursor that is persisted
+//! between steps. Install [`hooks::RequireNotMigrating`] on components (or
+//! call [`BatchedMigrateController::require_not_migrating`]) to block
+//! normal methods while a migration is in progress.
+//!
 //! # Safety
 //! The contract state must conform to the old schema otherwise deserializing it
 //! will fail and throw an error.
@@ -20,15 +46,78 @@
 
 use near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
-    env, ext_contract,
+    env, ext_contract, near, require, BorshStorageKey,
 };
+use near_sdk_contract_tools_macros::event;
+
+use crate::{slot::Slot, standard::nep297::Event, DefaultStorageKey};
+
+const MIGRATION_IN_PROGRESS_FAIL_MESSAGE: &str = "Migration in progress";
+const MIGRATION_NOT_IN_PROGRESS_FAIL_MESSAGE: &str = "No migration in progress";
+const MIGRATION_LIMIT_FAIL_MESSAGE: &str = "Migration step limit must be greater than zero";
+
+/// Events emitted by migrations.
+#[event(
+    standard = "x-migrate",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum MigrateEvent {
+    /// Emitted when the contract state is migrated to a new schema.
+    Migrate {
+        /// Schema version before the migration.
+        from_version: u32,
+        /// Schema version after the migration.
+        to_version: u32,
+    },
+}
+
+/// Storage shared by schema and batched migrations. Using these methods may
+/// result in unexpected behavior.
+pub trait MigrateInternal {
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Migrate)
+    }
+
+    /// Storage slot for the schema version of the contract state.
+    #[must_use]
+    fn slot_schema_version() -> Slot<u32> {
+        Self::root().field(StorageKey::SchemaVersion)
+    }
+
+    /// Returns the schema version of the contract state. Contracts that have
+    /// never been migrated are at version 0.
+    #[must_use]
+    fn schema_version() -> u32 {
+        Self::slot_schema_version().read().unwrap_or(0)
+    }
+}
+
+fn require_schema_version<C: MigrateInternal + ?Sized>(expected: u32) {
+    let current = C::schema_version();
+
+    require!(
+        current == expected,
+        format!("Cannot migrate from schema version {current}: expected version {expected}"),
+    );
+}
 
-// TODO: Migration events?
-// *Possibly* unnecessary, since the salient occurence will probably be the instigating event (e.g. a code upgrade)
-// Alternative solution: post-migration hook/callback so that the author can implement their own events if desired
+fn record_schema_version<C: MigrateInternal + ?Sized>(from_version: u32, to_version: u32) {
+    C::slot_schema_version().write(&to_version);
+
+    MigrateEvent::Migrate {
+        from_version,
+        to_version,
+    }
+    .emit();
+}
 
 /// Conversion between two storage schemas
-pub trait MigrateController {
+pub trait MigrateController: MigrateInternal {
     /// Schema that currently exists in storage, to convert from
     type OldSchema: BorshDeserialize;
     /// Schema that will be used henceforth, to convert into
@@ -42,11 +131,40 @@ pub trait MigrateController {
         env::state_read::<Self::OldSchema>()
             .unwrap_or_else(|| env::panic_str("Failed to deserialize old state"))
     }
+
+    /// Migrates the contract state from the old schema to the new schema
+    /// using [`MigrateHook::on_migrate`]. Records the new schema version and
+    /// emits a [`MigrateEvent::Migrate`] event.
+    ///
+    /// # Panics
+    ///
+    /// - If the schema version is not [`MigrateHook::FROM_VERSION`].
+    /// - If the old schema cannot be deserialized.
+    #[must_use]
+    fn perform_migration() -> Self::NewSchema
+    where
+        Self: MigrateHook,
+    {
+        let from_version = <Self as MigrateHook>::FROM_VERSION;
+        require_schema_version::<Self>(from_version);
+
+        let new_schema = <Self as MigrateHook>::on_migrate(Self::deserialize_old_schema());
+
+        record_schema_version::<Self>(from_version, <Self as MigrateHook>::TO_VERSION);
+
+        new_schema
+    }
 }
 
 /// Called on migration. Must be implemented by the user. (The derive macro
 /// does not implement this for you.)
 pub trait MigrateHook: MigrateController {
+    /// Schema version that this migration converts from. Migration is
+    /// refused if the contract state is at any other version.
+    const FROM_VERSION: u32 = 0;
+    /// Schema version that this migration converts to.
+    const TO_VERSION: u32 = Self::FROM_VERSION + 1;
+
     /// Receives the old schema deserialized from storage as well as optional
     /// arguments from caller, and replaces it with the new schema.
     fn on_migrate(
@@ -59,4 +177,181 @@ pub trait MigrateHook: MigrateController {
 pub trait MigrateExternal {
     /// Perform the migration with optional arguments
     fn migrate();
+
+    /// Returns the schema version of the contract state.
+    fn migrate_schema_version(&self) -> u32;
+}
+
+/// Status of a batched migration.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub enum MigrationStatus {
+    /// No migration has been started.
+    #[default]
+    Idle,
+    /// A migration has been started and is not complete.
+    InProgress {
+        /// Number of steps performed so far.
+        steps: u32,
+    },
+    /// The most recent migration is complete.
+    Complete,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey {
+    Status,
+    Cursor,
+    SchemaVersion,
+}
+
+/// Migrates a batch of records. Must be implemented by the user.
+pub trait BatchedMigrateHook {
+    /// Position from which to resume the migration, persisted between steps.
+    type Cursor: BorshSerialize + BorshDeserialize;
+
+    /// Schema version that this migration converts from. Starting the
+    /// migration is refused if the contract state is at any other version.
+    const FROM_VERSION: u32 = 0;
+    /// Schema version that this migration converts to, recorded when the
+    /// migration is complete.
+    const TO_VERSION: u32 = Self::FROM_VERSION + 1;
+
+    /// Migrates up to `limit` records, starting from `cursor` (`None` for
+    /// the first batch). Returns the cursor from which to resume, or `None`
+    /// if there is nothing left to migrate.
+    fn migrate_batch(&mut self, cursor: Option<Self::Cursor>, limit: u32) -> Option<Self::Cursor>;
+
+    /// Called once, after the last batch has been migrated.
+    fn on_migration_complete(&mut self) {}
+}
+
+/// Internal functions for [`BatchedMigrateController`]. Using these methods
+/// may result in unexpected behavior.
+pub trait BatchedMigrateInternal: BatchedMigrateHook + MigrateInternal {
+    /// Storage slot for the migration status.
+    #[must_use]
+    fn slot_migration_status() -> Slot<MigrationStatus> {
+        Self::root().field(StorageKey::Status)
+    }
+
+    /// Storage slot for the migration cursor.
+    #[must_use]
+    fn slot_migration_cursor() -> Slot<Self::Cursor> {
+        Self::root().field(StorageKey::Cursor)
+    }
+}
+
+/// Batched, resumable migration.
+pub trait BatchedMigrateController {
+    /// Returns the status of the migration.
+    fn migration_status() -> MigrationStatus;
+
+    /// Starts a new migration from the beginning, discarding the cursor of
+    /// any previous migration.
+    ///
+    /// # Panics
+    ///
+    /// - If a migration is already in progress.
+    /// - If the schema version is not [`BatchedMigrateHook::FROM_VERSION`].
+    fn start_migration(&mut self);
+
+    /// Migrates the next batch of up to `limit` records. Completes the
+    /// migration if there are no more records, recording
+    /// [`BatchedMigrateHook::TO_VERSION`] as the schema version and emitting
+    /// a [`MigrateEvent::Migrate`] event.
+    ///
+    /// # Panics
+    ///
+    /// - If no migration is in progress.
+    /// - If `limit` is zero.
+    fn step_migration(&mut self, limit: u32) -> MigrationStatus;
+
+    /// Rejects if a migration is in progress.
+    fn require_not_migrating();
+}
+
+impl<T: BatchedMigrateInternal> BatchedMigrateController for T {
+    fn migration_status() -> MigrationStatus {
+        Self::slot_migration_status().read().unwrap_or_default()
+    }
+
+    fn start_migration(&mut self) {
+        Self::require_not_migrating();
+        require_schema_version::<Self>(Self::FROM_VERSION);
+
+        Self::slot_migration_cursor().remove();
+        Self::slot_migration_status().write(&MigrationStatus::InProgress { steps: 0 });
+    }
+
+    fn step_migration(&mut self, limit: u32) -> MigrationStatus {
+        require!(limit > 0, MIGRATION_LIMIT_FAIL_MESSAGE);
+
+        let MigrationStatus::InProgress { steps } = Self::migration_status() else {
+            env::panic_str(MIGRATION_NOT_IN_PROGRESS_FAIL_MESSAGE);
+        };
+
+        let mut cursor_slot = Self::slot_migration_cursor();
+        let cursor = cursor_slot.read();
+
+        let status = if let Some(next) = self.migrate_batch(cursor, limit) {
+            cursor_slot.write(&next);
+            MigrationStatus::InProgress { steps: steps + 1 }
+        } else {
+            cursor_slot.remove();
+            self.on_migration_complete();
+            record_schema_version::<Self>(Self::FROM_VERSION, Self::TO_VERSION);
+            MigrationStatus::Complete
+        };
+
+        Self::slot_migration_status().write(&status);
+
+        status
+    }
+
+    fn require_not_migrating() {
+        require!(
+            !matches!(Self::migration_status(), MigrationStatus::InProgress { .. }),
+            MIGRATION_IN_PROGRESS_FAIL_MESSAGE,
+        );
+    }
+}
+
+/// Contracts with batched migration expose this trait publicly.
+#[ext_contract(ext_batched_migrate)]
+pub trait BatchedMigrateExternal {
+    /// Starts a new migration and performs the first step. Private: usually
+    /// called by the contract itself after an upgrade. Rejects if a
+    /// migration is already in progress, or if the contract state is not at
+    /// the schema version the migration converts from.
+    fn migrate_start(&mut self, limit: u32) -> MigrationStatus;
+
+    /// Performs the next step of the migration in progress.
+    fn migrate_step(&mut self, limit: u32) -> MigrationStatus;
+
+    /// Returns the status of the migration.
+    fn migrate_status(&self) -> MigrationStatus;
+}
+
+pub mod hooks {
+    //! Hooks to integrate batched migration with other components.
+
+    use crate::hook::Hook;
+
+    use super::BatchedMigrateController;
+
+    /// Ensures that no batched migration is in progress before calling a
+    /// method.
+    pub struct RequireNotMigrating;
+
+    impl<C, A> Hook<C, A> for RequireNotMigrating
+    where
+        C: BatchedMigrateController,
+    {
+        fn hook<R>(contract: &mut C, _args: &A, f: impl FnOnce(&mut C) -> R) -> R {
+            C::require_not_migrating();
+            f(contract)
+        }
+    }
 }
@@ -2,7 +2,11 @@
 //!
 //! Makes it easy to create and manage storage keys and avoid unnecessary
 //! writes to contract storage. This reduces transaction IO  and saves on gas.
-use std::{marker::PhantomData, ops::Deref};
+use std::{
+    cell::OnceCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
@@ -176,6 +180,390 @@ impl<T: BorshSerialize + BorshDeserialize> Slot<T> {
     }
 }
 
+impl<T: BorshSerialize + BorshDeserialize> Slot<T> {
+    /// Wraps this slot in a [`CachedSlot`].
+    #[must_use]
+    pub fn cached(self) -> CachedSlot<T> {
+        CachedSlot::new(self)
+    }
+}
+
+/// A write-back cache for a single [`Slot`], for use within one method call.
+///
+/// The value is read from storage (and deserialized) at most once, the first
+/// time it is accessed. Writes are kept in memory and written to storage
+/// once, when [`CachedSlot::flush`] is called or the [`CachedSlot`] is
+/// dropped. Repeatedly reading and updating the same slot, e.g. a counter
+/// in a loop, therefore costs one storage read and one storage write.
+///
+/// # Warning
+///
+/// - Storage is not updated until the cache is flushed. Reading or writing
+///     the underlying key by other means (e.g. another [`Slot`] with the same
+///     key) while the cache holds unflushed or stale data is not coherent.
+/// - Storage accounting (e.g. NEP-145 hooks) that measures
+///     [`env::storage_usage`] only sees the writes once the cache has been
+///     flushed, so flush before the measurement ends.
+///
+/// A [`CachedSlot`] cannot be stored in contract state, so it never outlives
+/// the call that created it. Since each receipt (including cross-contract
+/// callbacks) executes after the previous call has returned, callbacks
+/// always observe the flushed value. If the call panics, the pending writes
+/// are discarded along with the rest of the state changes of the call.
+#[derive(Debug)]
+pub struct CachedSlot<T: BorshSerialize + BorshDeserialize> {
+    slot: Slot<T>,
+    value: OnceCell<Option<T>>,
+    dirty: bool,
+}
+
+impl<T: BorshSerialize + BorshDeserialize> CachedSlot<T> {
+    /// Creates a cache for `slot`. Does not read from storage.
+    #[must_use]
+    pub fn new(slot: Slot<T>) -> Self {
+        Self {
+            slot,
+            value: OnceCell::new(),
+            dirty: false,
+        }
+    }
+
+    /// The underlying slot.
+    #[must_use]
+    pub fn slot(&self) -> &Slot<T> {
+        &self.slot
+    }
+
+    /// Returns `true` if the cache holds writes that have not been flushed.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn load(&self) -> &Option<T> {
+        self.value.get_or_init(|| self.slot.read())
+    }
+
+    fn load_mut(&mut self) -> &mut Option<T> {
+        self.load();
+        // unwrap is safe because the cell was initialized by `load`
+        self.value.get_mut().unwrap()
+    }
+
+    /// Returns the value, reading it from storage on first access.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh deserialization fails.
+    #[must_use]
+    pub fn get(&self) -> Option<&T> {
+        self.load().as_ref()
+    }
+
+    /// Returns a mutable reference to the value, reading it from storage on
+    /// first access. The cache is marked as dirty when the value is
+    /// dereferenced mutably, not when it is only read.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh deserialization fails.
+    pub fn get_mut(&mut self) -> Option<CachedSlotMut<'_, T>> {
+        self.load();
+
+        let Self { value, dirty, .. } = self;
+        // unwrap is safe because the cell was initialized by `load`
+        value
+            .get_mut()
+            .unwrap()
+            .as_mut()
+            .map(|value| CachedSlotMut { value, dirty })
+    }
+
+    /// Sets the value, without reading from storage. `None` removes the key
+    /// from storage when flushed.
+    pub fn set(&mut self, value: Option<T>) {
+        self.dirty = true;
+        self.value = OnceCell::from(value);
+    }
+
+    /// Sets the value, without reading from storage.
+    pub fn write(&mut self, value: T) {
+        self.set(Some(value));
+    }
+
+    /// Removes the value, returning the previous value.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh deserialization fails.
+    pub fn take(&mut self) -> Option<T> {
+        let value = self.load_mut().take();
+        if value.is_some() {
+            self.dirty = true;
+        }
+        value
+    }
+
+    /// Writes pending changes to storage, if any.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh serialization fails.
+    pub fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+
+        match self.value.get() {
+            Some(Some(value)) => {
+                self.slot.write(value);
+            }
+            Some(None) => {
+                self.slot.remove();
+            }
+            None => {}
+        }
+
+        self.dirty = false;
+    }
+}
+
+/// Mutable reference to the value of a [`CachedSlot`], returned by
+/// [`CachedSlot::get_mut`]. Marks the cache as dirty when dereferenced
+/// mutably.
+#[derive(Debug)]
+pub struct CachedSlotMut<'a, T> {
+    value: &'a mut T,
+    dirty: &'a mut bool,
+}
+
+impl<T> Deref for CachedSlotMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for CachedSlotMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        *self.dirty = true;
+        self.value
+    }
+}
+
+impl<T: BorshSerialize + BorshDeserialize> Drop for CachedSlot<T> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// A type whose values are stored with a version tag by a [`VersionedSlot`].
+///
+/// When a record type changes, define the new type with a greater
+/// [`Versioned::VERSION`] and convert values written by older versions in
+/// [`Versioned::upgrade`]:
+///
+/// ```
+/// use near_sdk::{borsh::BorshDeserialize, near};
+/// use near_sdk_contract_tools::slot::Versioned;
+///
+/// #[near]
+/// struct RecordV1 {
+///     amount: u64,
+/// }
+///
+/// #[near]
+/// struct Record {
+///     amount: u128,
+///     memo: Option<String>,
+/// }
+///
+/// impl Versioned for Record {
+///     const VERSION: u8 = 2;
+///
+///     fn upgrade(version: u8, bytes: &[u8]) -> Option<Self> {
+///         match version {
+///             1 => RecordV1::try_from_slice(bytes).ok().map(|v1| Record {
+///                 amount: v1.amount.into(),
+///                 memo: None,
+///             }),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait Versioned: BorshSerialize + BorshDeserialize {
+    /// The version of this type. Values are always written with this version.
+    const VERSION: u8;
+
+    /// Converts the serialized value of an older `version` to this type.
+    /// Returns `None` if the version is not supported.
+    fn upgrade(version: u8, bytes: &[u8]) -> Option<Self>;
+
+    /// Converts a value that was written without a version tag (e.g. by a
+    /// plain [`Slot`], before the slot was versioned) to this type. Returns
+    /// `None` (the default) if untagged values are not supported.
+    ///
+    /// Only called if the stored bytes cannot be read as a tagged value, so
+    /// the untagged type should not be mistakable for a tagged value (e.g. it
+    /// has a fixed size that differs from every tagged version).
+    #[must_use]
+    fn upgrade_untagged(bytes: &[u8]) -> Option<Self> {
+        let _ = bytes;
+        None
+    }
+}
+
+/// A storage slot whose value is tagged with the version of its type.
+///
+/// Values written by older versions of `T` are upgraded lazily, using
+/// [`Versioned::upgrade`], when they are read. Writes always use the newest
+/// version. This allows record types to evolve without migrating every
+/// record at once.
+///
+/// # Layout
+///
+/// The value is stored as `[version] ++ borsh(value)`, where `version` is a
+/// single byte. Untagged values (e.g. those written by a plain [`Slot`] before
+/// the slot was versioned) are read using [`Versioned::upgrade_untagged`],
+/// and are tagged the next time they are written.
+#[derive(Clone, Debug)]
+#[near]
+pub struct VersionedSlot<T> {
+    /// The underlying slot.
+    pub slot: Slot<()>,
+    #[borsh(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: Versioned> VersionedSlot<T> {
+    /// Creates a new [`VersionedSlot`] that controls the given storage key.
+    pub fn new(key: impl IntoStorageKey) -> Self {
+        Self {
+            slot: Slot::root(key),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the key is present in storage.
+    #[must_use]
+    pub fn exists(&self) -> bool {
+        self.slot.exists()
+    }
+
+    /// Returns the version of the stored value, if present and tagged.
+    ///
+    /// # Panics
+    ///
+    /// - If the stored value cannot be read.
+    #[must_use]
+    pub fn stored_version(&self) -> Option<u8> {
+        self.slot
+            .read_raw()
+            .and_then(|bytes| Self::decode(&bytes).0)
+    }
+
+    fn decode_tagged(bytes: &[u8]) -> Option<(u8, T)> {
+        let (&version, data) = bytes.split_first()?;
+
+        let value = if version == T::VERSION {
+            T::try_from_slice(data).ok()
+        } else {
+            T::upgrade(version, data)
+        }?;
+
+        Some((version, value))
+    }
+
+    /// Decodes a stored value, returning its version tag (`None` if it is
+    /// untagged) and the upgraded value.
+    fn decode(bytes: &[u8]) -> (Option<u8>, T) {
+        if let Some((version, value)) = Self::decode_tagged(bytes) {
+            return (Some(version), value);
+        }
+
+        if let Some(value) = T::upgrade_untagged(bytes) {
+            return (None, value);
+        }
+
+        match bytes.first() {
+            Some(version) => env::panic_str(&format!("Unsupported version {version}")),
+            None => env::panic_str("Missing version tag"),
+        }
+    }
+
+    fn encode(value: &T) -> Vec<u8> {
+        let mut bytes = vec![T::VERSION];
+        value.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Reads a value from storage, if present, upgrading it to the newest
+    /// version. Does not write the upgraded value.
+    ///
+    /// # Panics
+    ///
+    /// - If the version tag is missing or not supported.
+    /// - If Borsh deserialization fails.
+    #[must_use]
+    pub fn read(&self) -> Option<T> {
+        self.slot.read_raw().map(|v| Self::decode(&v).1)
+    }
+
+    /// Writes a value to storage with the newest version.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh serialization fails.
+    pub fn write(&mut self, value: &T) -> bool {
+        self.slot.write_raw(&Self::encode(value))
+    }
+
+    /// Removes the key from storage.
+    pub fn remove(&mut self) -> bool {
+        self.slot.remove()
+    }
+
+    /// Removes a value from storage and returns it, upgraded, if present.
+    ///
+    /// # Panics
+    ///
+    /// - If the version tag is missing or not supported.
+    /// - If Borsh deserialization fails.
+    #[must_use]
+    pub fn take(&mut self) -> Option<T> {
+        if self.slot.remove() {
+            // unwrap should be safe if remove returns true
+            Some(Self::decode(&env::storage_get_evicted().unwrap()).1)
+        } else {
+            None
+        }
+    }
+
+    /// Rewrites the stored value with the newest version if it was written
+    /// by an older version, or without a version tag. Returns `true` if the
+    /// value was upgraded.
+    ///
+    /// # Panics
+    ///
+    /// - If the version tag is missing or not supported.
+    /// - If Borsh (de)serialization fails.
+    pub fn upgrade(&mut self) -> bool {
+        let Some(bytes) = self.slot.read_raw() else {
+            return false;
+        };
+
+        match Self::decode(&bytes) {
+            (Some(version), _) if version == T::VERSION => false,
+            (_, value) => {
+                self.write(&value);
+                true
+            }
+        }
+    }
+}
+
 impl<T> IntoStorageKey for Slot<T> {
     fn into_storage_key(self) -> Vec<u8> {
         self.key
@@ -188,9 +576,159 @@ impl<T, U> PartialEq<Slot<U>> for Slot<T> {
     }
 }
 
+/// A keyed collection of [`Slot`]s that share a root key.
+///
+/// Unlike the collections in `near_sdk`, a [`SlotMap`] has no in-memory state
+/// besides its root key: every operation reads or writes exactly the slots
+/// it touches, and nothing is cached or flushed.
+///
+/// # Layout
+///
+/// The layout is stable, so existing data remains readable by later
+/// versions of this library.
+///
+/// - The value for key `k` is stored at `root ++ b"v" ++ borsh(k)`, where
+///     `k` is always serialized as `K` (e.g. a `[u8; 4]` key has no length
+///     prefix, unlike a `Vec<u8>` key).
+/// - If length tracking is enabled, the number of entries is stored as a
+///     Borsh-serialized `u64` at `root ++ b"l"`.
+///
+/// Entries cannot be enumerated.
+#[derive(Clone, Debug)]
+#[near]
+pub struct SlotMap<K, V> {
+    /// The root slot of the map.
+    pub root: Slot<()>,
+    /// Whether the number of entries is tracked.
+    pub track_length: bool,
+    #[borsh(skip)]
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> SlotMap<K, V> {
+    /// Creates a new map rooted at `key`, without length tracking.
+    pub fn new(key: impl IntoStorageKey) -> Self {
+        Self {
+            root: Slot::root(key),
+            track_length: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new map rooted at `key` that tracks the number of entries.
+    /// Costs an additional read and write when an entry is inserted or
+    /// removed.
+    pub fn with_length(key: impl IntoStorageKey) -> Self {
+        Self {
+            root: Slot::root(key),
+            track_length: true,
+            _marker: PhantomData,
+        }
+    }
+
+    fn slot_length(&self) -> Slot<u64> {
+        self.root.field(b"l")
+    }
+
+    /// Returns the number of entries, if length tracking is enabled.
+    #[must_use]
+    pub fn len(&self) -> Option<u64> {
+        self.track_length
+            .then(|| self.slot_length().read().unwrap_or(0))
+    }
+
+    /// Returns whether the map is empty, if length tracking is enabled.
+    #[must_use]
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    fn update_length(&self, f: impl FnOnce(u64) -> u64) {
+        if self.track_length {
+            let mut slot = self.slot_length();
+            let len = f(slot.read().unwrap_or(0));
+            if len == 0 {
+                slot.remove();
+            } else {
+                slot.write(&len);
+            }
+        }
+    }
+}
+
+impl<K: BorshSerialize, V> SlotMap<K, V> {
+    /// Returns the slot that stores the value for `key`.
+    ///
+    /// # Warning
+    ///
+    /// Writing to the slot directly bypasses length tracking.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh serialization of `key` fails.
+    #[must_use]
+    pub fn slot(&self, key: &K) -> Slot<V> {
+        self.root
+            .field(prefix_key(b"v", &borsh::to_vec(key).unwrap()))
+    }
+
+    /// Returns `true` if the map contains a value for `key`.
+    #[must_use]
+    pub fn contains(&self, key: &K) -> bool {
+        self.slot(key).exists()
+    }
+}
+
+impl<K: BorshSerialize, V: BorshDeserialize> SlotMap<K, V> {
+    /// Reads the value for `key`, if present.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh (de)serialization fails.
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.slot(key).read()
+    }
+
+    /// Removes the value for `key` and returns it, if present.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh (de)serialization fails.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.slot(key).take();
+        if value.is_some() {
+            self.update_length(|len| len.saturating_sub(1));
+        }
+        value
+    }
+}
+
+impl<K: BorshSerialize, V: BorshSerialize + BorshDeserialize> SlotMap<K, V> {
+    /// Writes the value for `key`, returning the previous value, if present.
+    ///
+    /// # Panics
+    ///
+    /// If Borsh (de)serialization fails.
+    pub fn insert(&mut self, key: &K, value: &V) -> Option<V> {
+        let previous = self.slot(key).swap(value);
+        if previous.is_none() {
+            self.update_length(|len| len + 1);
+        }
+        previous
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Slot;
+    use near_sdk::{
+        borsh::{self, BorshDeserialize},
+        env, near,
+        test_utils::VMContextBuilder,
+        testing_env, Gas,
+    };
+
+    use super::{Slot, SlotMap, Versioned, VersionedSlot};
 
     #[test]
     fn partialeq() {
@@ -200,4 +738,221 @@ mod tests {
         let b = Slot::<u32>::new(b"b");
         assert_ne!(a1, b);
     }
+
+    #[test]
+    fn cached_slot() {
+        let mut slot = Slot::<u32>::new(b"c");
+        slot.write(&1);
+
+        let mut cached = slot.clone().cached();
+        assert_eq!(cached.get(), Some(&1));
+
+        slot.write(&2);
+        assert_eq!(cached.get(), Some(&1));
+        assert!(!cached.is_dirty());
+
+        let value = cached.get_mut().unwrap();
+        assert_eq!(*value, 1);
+        assert!(!cached.is_dirty());
+
+        *cached.get_mut().unwrap() += 10;
+        assert!(cached.is_dirty());
+        assert_eq!(slot.read(), Some(2));
+
+        cached.flush();
+        assert_eq!(slot.read(), Some(11));
+
+        cached.take();
+        drop(cached);
+        assert_eq!(slot.read(), None);
+
+        let mut cached = slot.clone().cached();
+        cached.write(5);
+        drop(cached);
+        assert_eq!(slot.read(), Some(5));
+    }
+
+    #[test]
+    fn cached_slot_clean_take() {
+        let mut cached = Slot::<u32>::new(b"e").cached();
+        assert_eq!(cached.take(), None);
+        assert!(!cached.is_dirty());
+        assert!(cached.get_mut().is_none());
+        assert!(!cached.is_dirty());
+    }
+
+    fn measure(f: impl FnOnce()) -> Gas {
+        testing_env!(VMContextBuilder::new().build());
+        let start = env::used_gas();
+        f();
+        env::used_gas().saturating_sub(start)
+    }
+
+    #[test]
+    fn cached_slot_uses_less_gas() {
+        let uncached = measure(|| {
+            let mut slot = Slot::<u64>::new(b"g");
+            for _ in 0..20 {
+                let next = slot.read().unwrap_or(0) + 1;
+                slot.write(&next);
+            }
+        });
+        assert_eq!(Slot::<u64>::new(b"g").read(), Some(20));
+
+        let cached = measure(|| {
+            let mut slot = Slot::<u64>::new(b"g").cached();
+            for _ in 0..20 {
+                let next = slot.get().copied().unwrap_or(0) + 1;
+                slot.write(next);
+            }
+        });
+        assert_eq!(Slot::<u64>::new(b"g").read(), Some(40));
+
+        assert!(
+            cached < uncached,
+            "cached: {cached:?}, uncached: {uncached:?}",
+        );
+    }
+
+    #[near]
+    #[derive(Debug, PartialEq, Eq)]
+    struct RecordV1 {
+        amount: u32,
+    }
+
+    #[near]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Record {
+        amount: u64,
+        frozen: bool,
+    }
+
+    impl Versioned for Record {
+        const VERSION: u8 = 2;
+
+        fn upgrade(version: u8, bytes: &[u8]) -> Option<Self> {
+            match version {
+                1 => RecordV1::try_from_slice(bytes).ok().map(|v1| Record {
+                    amount: v1.amount.into(),
+                    frozen: false,
+                }),
+                _ => None,
+            }
+        }
+
+        fn upgrade_untagged(bytes: &[u8]) -> Option<Self> {
+            u64::try_from_slice(bytes).ok().map(|amount| Record {
+                amount,
+                frozen: false,
+            })
+        }
+    }
+
+    #[test]
+    fn versioned_slot() {
+        let mut raw = Slot::<()>::new(b"r".as_slice());
+        let mut bytes = vec![1];
+        bytes.extend(borsh::to_vec(&RecordV1 { amount: 7 }).unwrap());
+        raw.write_raw(&bytes);
+
+        let mut slot = VersionedSlot::<Record>::new(b"r".as_slice());
+        let upgraded = Record {
+            amount: 7,
+            frozen: false,
+        };
+
+        assert_eq!(slot.stored_version(), Some(1));
+        assert_eq!(slot.read(), Some(upgraded));
+        assert_eq!(slot.stored_version(), Some(1));
+
+        assert!(slot.upgrade());
+        assert!(!slot.upgrade());
+        assert_eq!(slot.stored_version(), Some(2));
+
+        slot.write(&Record {
+            amount: 8,
+            frozen: true,
+        });
+        assert_eq!(
+            slot.take(),
+            Some(Record {
+                amount: 8,
+                frozen: true,
+            }),
+        );
+        assert_eq!(slot.read(), None);
+    }
+
+    #[test]
+    fn versioned_slot_untagged() {
+        let mut legacy = Slot::<u64>::new(b"l".as_slice());
+        legacy.write(&1);
+
+        let mut slot = VersionedSlot::<Record>::new(b"l".as_slice());
+        let upgraded = Record {
+            amount: 1,
+            frozen: false,
+        };
+
+        assert_eq!(slot.stored_version(), None);
+        assert!(slot.exists());
+        assert_eq!(slot.read(), Some(upgraded.clone()));
+
+        assert!(slot.upgrade());
+        assert!(!slot.upgrade());
+        assert_eq!(slot.stored_version(), Some(2));
+        assert_eq!(slot.read(), Some(upgraded));
+        assert_eq!(
+            slot.slot.read_raw(),
+            Some(vec![2, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        );
+    }
+
+    #[test]
+    #[should_panic = "Unsupported version 3"]
+    fn versioned_slot_unsupported() {
+        Slot::<()>::new(b"u".as_slice()).write_raw(&[3, 0]);
+        let _ = VersionedSlot::<Record>::new(b"u".as_slice()).read();
+    }
+
+    #[test]
+    fn slot_map() {
+        let mut map = SlotMap::<String, u32>::with_length(b"m");
+        let [a, b, c] = ["a", "b", "c"].map(String::from);
+
+        assert_eq!(map.len(), Some(0));
+        assert_eq!(map.insert(&a, &1), None);
+        assert_eq!(map.insert(&b, &2), None);
+        assert_eq!(map.insert(&a, &3), Some(1));
+        assert_eq!(map.len(), Some(2));
+
+        assert_eq!(map.get(&a), Some(3));
+        assert!(map.contains(&b));
+        assert!(!map.contains(&c));
+
+        assert_eq!(map.remove(&b), Some(2));
+        assert_eq!(map.remove(&b), None);
+        assert_eq!(map.len(), Some(1));
+        assert_eq!(map.is_empty(), Some(false));
+
+        assert_eq!(map.slot(&a).key, b"mv\x01\x00\x00\x00a");
+        assert_eq!(SlotMap::<String, u32>::new(b"m").len(), None);
+    }
+
+    #[test]
+    fn slot_map_layout() {
+        let mut array_map = SlotMap::<[u8; 4], u32>::new(b"a");
+        array_map.insert(&[1, 2, 3, 4], &1);
+        assert_eq!(array_map.slot(&[1, 2, 3, 4]).key, b"av\x01\x02\x03\x04");
+
+        let vec_map = SlotMap::<Vec<u8>, u32>::new(b"a");
+        assert_eq!(
+            vec_map.slot(&vec![1, 2, 3, 4]).key,
+            b"av\x04\x00\x00\x00\x01\x02\x03\x04",
+        );
+        assert_eq!(vec_map.get(&vec![1, 2, 3, 4]), None);
+
+        let tuple_map = SlotMap::<(u32, bool), u32>::new(b"t");
+        assert_eq!(tuple_map.slot(&(7, true)).key, b"tv\x07\x00\x00\x00\x01",);
+    }
 }
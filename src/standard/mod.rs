@@ -8,3 +8,6 @@ pub mod nep177;
 pub mod nep178;
 pub mod nep181;
 pub mod nep297;
+pub mod nep393;
+
+pub mod nft_rental;
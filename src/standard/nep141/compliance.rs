@@ -0,0 +1,352 @@
+//! Account freezing and clawback for regulated NEP-141 tokens.
+//!
+//! [`Compliance`] keeps a set of frozen accounts. [`ComplianceHook`] rejects
+//! transfers, mints, and burns involving a frozen account when installed as a
+//! hook on a [`Nep141Controller`] implementation. A compliance authority may
+//! move tokens out of any account using [`Compliance::force_transfer`], or
+//! destroy them using [`Compliance::clawback`]. Both are marked as
+//! [`forced`](Nep141Transfer::forced), which bypasses the freeze and
+//! transfer fees, but otherwise behave like regular transfers and burns
+//! (invoking all other hooks, e.g. NEP-145 storage accounting), and emit
+//! [`ComplianceEvent`]s in addition to the regular NEP-141 events.
+//!
+//! The compliance authority is determined by an implementation of
+//! [`ComplianceAuthority`]. The [derive macro](near_sdk_contract_tools_macros::Compliance)
+//! can generate an implementation that defers to [`Owner`](crate::owner::Owner)
+//! or to an [`Rbac`](crate::rbac::Rbac) role.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The compliance root storage slot is not used or modified. The
+//!     default key is `~$141f`.
+//! * (UB) [`ComplianceHook`] is installed as a hook for all NEP-141
+//!     operations. Otherwise, the freeze is not enforced.
+//! * (ERR) Frozen accounts cannot send, receive, mint, or burn tokens, except
+//!     through [`Compliance::force_transfer`] and [`Compliance::clawback`].
+//! * (ERR) The external functions may only be called by the compliance
+//!     authority, as determined by [`ComplianceAuthority`].
+
+use near_sdk::{
+    borsh::BorshSerialize, env, json_types::U128, AccountId, AccountIdRef, BorshStorageKey,
+};
+use near_sdk_contract_tools_macros::event;
+use thiserror::Error;
+
+use crate::{
+    hook::Hook,
+    slot::Slot,
+    standard::{
+        nep141::{
+            Nep141Burn, Nep141Controller, Nep141Mint, Nep141Transfer, TransferError, WithdrawError,
+        },
+        nep297::Event,
+    },
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+/// Events emitted by the compliance component.
+#[event(
+    standard = "x-cmpl",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum ComplianceEvent {
+    /// Emitted when an account is frozen.
+    Freeze {
+        /// The frozen account.
+        account_id: AccountId,
+    },
+    /// Emitted when an account is unfrozen.
+    Unfreeze {
+        /// The unfrozen account.
+        account_id: AccountId,
+    },
+    /// Emitted when tokens are forcibly moved between accounts.
+    ForceTransfer {
+        /// Account from which the tokens were taken.
+        sender_id: AccountId,
+        /// Account to which the tokens were moved.
+        receiver_id: AccountId,
+        /// Amount of tokens moved.
+        amount: U128,
+        /// Optional note, e.g. a reference to a court order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
+    },
+    /// Emitted when tokens are forcibly burned from an account.
+    Clawback {
+        /// Account from which the tokens were burned.
+        account_id: AccountId,
+        /// Amount of tokens burned.
+        amount: U128,
+        /// Optional note, e.g. a reference to a court order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
+    },
+}
+
+/// The account is frozen.
+#[derive(Debug, Error)]
+#[error("Account {account_id} is frozen.")]
+pub struct AccountFrozenError {
+    /// The frozen account.
+    pub account_id: AccountId,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey<'a> {
+    Frozen(&'a AccountIdRef),
+}
+
+/// Internal functions for [`Compliance`]. Using these methods may result in unexpected behavior.
+pub trait ComplianceInternal {
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Compliance)
+    }
+
+    /// Storage slot for the frozen flag of an account.
+    #[must_use]
+    fn slot_frozen(account_id: &AccountIdRef) -> Slot<bool> {
+        Self::root().field(StorageKey::Frozen(account_id))
+    }
+}
+
+/// Determines who may perform compliance actions.
+pub trait ComplianceAuthority {
+    /// Rejects if the predecessor is not the compliance authority. Called by
+    /// all external compliance functions.
+    fn require_compliance_authority(&self);
+}
+
+/// Account freezing and clawback for a fungible token. These functions do not
+/// check the compliance authority.
+pub trait Compliance {
+    /// Returns `true` if the account is frozen.
+    fn is_frozen(&self, account_id: &AccountIdRef) -> bool;
+
+    /// Freezes or unfreezes an account without emitting events.
+    fn set_frozen_unchecked(&mut self, account_id: &AccountIdRef, frozen: bool);
+
+    /// Freezes an account. Emits a [`ComplianceEvent::Freeze`] event if the
+    /// account was not already frozen.
+    fn freeze(&mut self, account_id: &AccountIdRef);
+
+    /// Unfreezes an account. Emits a [`ComplianceEvent::Unfreeze`] event if
+    /// the account was frozen.
+    fn unfreeze(&mut self, account_id: &AccountIdRef);
+
+    /// Requires that an account is not frozen.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is frozen.
+    fn require_not_frozen(&self, account_id: &AccountIdRef) -> Result<(), AccountFrozenError> {
+        if self.is_frozen(account_id) {
+            Err(AccountFrozenError {
+                account_id: account_id.to_owned(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Moves tokens from one account to another with a forced
+    /// [`Nep141Controller::transfer`], regardless of whether either account is
+    /// frozen. No transfer fee is charged, so the receiver receives the full
+    /// `amount`. Emits a [`ComplianceEvent::ForceTransfer`] event in addition
+    /// to the NEP-141 `ft_transfer` event.
+    ///
+    /// # Errors
+    ///
+    /// - Receiver balance overflow.
+    /// - Sender balance underflow.
+    fn force_transfer(
+        &mut self,
+        sender_id: &AccountIdRef,
+        receiver_id: &AccountIdRef,
+        amount: u128,
+        memo: Option<&str>,
+    ) -> Result<(), TransferError>;
+
+    /// Burns tokens from an account with a forced [`Nep141Controller::burn`],
+    /// regardless of whether it is frozen. Emits a
+    /// [`ComplianceEvent::Clawback`] event in addition to the NEP-141
+    /// `ft_burn` event.
+    ///
+    /// # Errors
+    ///
+    /// - Account balance underflow.
+    /// - Total supply underflow.
+    fn clawback(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+        memo: Option<&str>,
+    ) -> Result<(), WithdrawError>;
+}
+
+impl<T: ComplianceInternal + Nep141Controller> Compliance for T {
+    fn is_frozen(&self, account_id: &AccountIdRef) -> bool {
+        Self::slot_frozen(account_id).exists()
+    }
+
+    fn set_frozen_unchecked(&mut self, account_id: &AccountIdRef, frozen: bool) {
+        Self::slot_frozen(account_id).set(frozen.then_some(&true));
+    }
+
+    fn freeze(&mut self, account_id: &AccountIdRef) {
+        if !self.is_frozen(account_id) {
+            self.set_frozen_unchecked(account_id, true);
+            ComplianceEvent::Freeze {
+                account_id: account_id.to_owned(),
+            }
+            .emit();
+        }
+    }
+
+    fn unfreeze(&mut self, account_id: &AccountIdRef) {
+        if self.is_frozen(account_id) {
+            self.set_frozen_unchecked(account_id, false);
+            ComplianceEvent::Unfreeze {
+                account_id: account_id.to_owned(),
+            }
+            .emit();
+        }
+    }
+
+    fn force_transfer(
+        &mut self,
+        sender_id: &AccountIdRef,
+        receiver_id: &AccountIdRef,
+        amount: u128,
+        memo: Option<&str>,
+    ) -> Result<(), TransferError> {
+        let mut transfer = Nep141Transfer::new(amount, sender_id, receiver_id).forced();
+        if let Some(memo) = memo {
+            transfer = transfer.memo(memo);
+        }
+
+        self.transfer(&transfer)?;
+
+        ComplianceEvent::ForceTransfer {
+            sender_id: sender_id.to_owned(),
+            receiver_id: receiver_id.to_owned(),
+            amount: amount.into(),
+            memo: memo.map(ToString::to_string),
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    fn clawback(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+        memo: Option<&str>,
+    ) -> Result<(), WithdrawError> {
+        let mut burn = Nep141Burn::new(amount, account_id).forced();
+        if let Some(memo) = memo {
+            burn = burn.memo(memo);
+        }
+
+        self.burn(&burn)?;
+
+        ComplianceEvent::Clawback {
+            account_id: account_id.to_owned(),
+            amount: amount.into(),
+            memo: memo.map(ToString::to_string),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
+
+fn require_not_frozen(contract: &impl Compliance, account_id: &AccountIdRef) {
+    contract
+        .require_not_frozen(account_id)
+        .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+}
+
+/// Rejects NEP-141 operations involving frozen accounts.
+///
+/// Forced transfers and burns are allowed. Refunds of
+/// [`Nep141::ft_transfer_call`](crate::standard::nep141::Nep141::ft_transfer_call)
+/// transfers are also allowed, so that `ft_resolve_transfer` does not fail if
+/// an account is frozen while the transfer is in flight.
+pub struct ComplianceHook;
+
+impl<C: Compliance> Hook<C, Nep141Transfer<'_>> for ComplianceHook {
+    fn hook<R>(contract: &mut C, action: &Nep141Transfer<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        if !action.revert && !action.forced {
+            require_not_frozen(contract, &action.sender_id);
+            require_not_frozen(contract, &action.receiver_id);
+        }
+
+        f(contract)
+    }
+}
+
+impl<C: Compliance> Hook<C, Nep141Mint<'_>> for ComplianceHook {
+    fn hook<R>(contract: &mut C, action: &Nep141Mint<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        require_not_frozen(contract, &action.receiver_id);
+        f(contract)
+    }
+}
+
+impl<C: Compliance> Hook<C, Nep141Burn<'_>> for ComplianceHook {
+    fn hook<R>(contract: &mut C, action: &Nep141Burn<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        if !action.forced {
+            require_not_frozen(contract, &action.owner_id);
+        }
+
+        f(contract)
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::U128, AccountId};
+
+    /// External (public) methods for [`Compliance`](super::Compliance).
+    #[ext_contract(ext_compliance)]
+    pub trait ComplianceExternal {
+        /// Returns `true` if the account is frozen.
+        fn cmpl_is_frozen(&self, account_id: AccountId) -> bool;
+
+        /// Freezes an account. Compliance authority only.
+        fn cmpl_freeze(&mut self, account_id: AccountId);
+
+        /// Unfreezes an account. Compliance authority only.
+        fn cmpl_unfreeze(&mut self, account_id: AccountId);
+
+        /// Moves tokens between accounts, bypassing the freeze. Compliance
+        /// authority only.
+        fn cmpl_force_transfer(
+            &mut self,
+            sender_id: AccountId,
+            receiver_id: AccountId,
+            amount: U128,
+            memo: Option<String>,
+        );
+
+        /// Burns tokens from an account, bypassing the freeze. Compliance
+        /// authority only.
+        fn cmpl_clawback(&mut self, account_id: AccountId, amount: U128, memo: Option<String>);
+    }
+}
@@ -45,6 +45,9 @@ pub enum DepositError {
     /// The total supply would overflow u128.
     #[error(transparent)]
     TotalSupplyOverflow(#[from] TotalSupplyOverflowError),
+    /// Shares are outstanding, but the pool backing them is empty.
+    #[error(transparent)]
+    EmptyPool(#[from] EmptyPoolError),
 }
 
 /// The balance of the account would overflow u128.
@@ -59,6 +62,17 @@ pub struct BalanceOverflowError {
     pub amount: u128,
 }
 
+/// Tokens cannot be deposited into an empty pool while shares are
+/// outstanding, because the new shares would dilute the existing holders.
+#[derive(Debug, Error)]
+#[error("Cannot deposit {amount} into an empty pool with {total_shares} outstanding shares.")]
+pub struct EmptyPoolError {
+    /// The total number of outstanding shares.
+    pub total_shares: u128,
+    /// The amount of the failed deposit attempt.
+    pub amount: u128,
+}
+
 /// The total supply would overflow u128.
 #[derive(Debug, Error)]
 #[error("The total supply ({total_supply}) plus {amount} would overflow u128.")]
@@ -78,4 +92,17 @@ pub enum TransferError {
     /// The balance of the sender is insufficient.
     #[error("Balance of the sender is insufficient: {0}")]
     SenderBalanceUnderflow(#[from] BalanceUnderflowError),
+    /// The transfer fee is greater than the transferred amount.
+    #[error(transparent)]
+    FeeExceedsAmount(#[from] TransferFeeExceedsAmountError),
+}
+
+/// The transfer fee is greater than the transferred amount.
+#[derive(Debug, Error)]
+#[error("The transfer fee {fee} is greater than the transferred amount {amount}.")]
+pub struct TransferFeeExceedsAmountError {
+    /// The amount of the failed transfer attempt.
+    pub amount: u128,
+    /// The fee that would be charged for the transfer.
+    pub fee: u128,
 }
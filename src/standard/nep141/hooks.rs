@@ -1,8 +1,11 @@
 //! Hooks to integrate NEP-141 with other standards.
 
-use crate::{hook::Hook, standard::nep145::Nep145ForceUnregister};
+use crate::{
+    hook::Hook,
+    standard::nep145::{Nep145Controller, Nep145ForceUnregister},
+};
 
-use super::{Nep141Burn, Nep141Controller, Nep141ControllerInternal};
+use super::{Nep141Burn, Nep141Controller, Nep141ControllerInternal, Nep141Transfer};
 
 /// Hook that burns all tokens on NEP-145 force unregister.
 pub struct BurnNep141OnForceUnregisterHook;
@@ -34,3 +37,51 @@ impl<C: Nep141Controller + Nep141ControllerInternal> Hook<C, Nep145ForceUnregist
         r
     }
 }
+
+/// Hook that transfers all tokens to the
+/// [force unregister beneficiary](Nep145Controller::get_force_unregister_beneficiary)
+/// on NEP-145 force unregister. Runs the regular transfer hooks and emits a
+/// transfer event.
+///
+/// # Panics
+///
+/// - If no beneficiary is set.
+/// - If the transfer fails.
+pub struct TransferNep141OnForceUnregisterHook;
+
+impl<C> Hook<C, Nep145ForceUnregister<'_>> for TransferNep141OnForceUnregisterHook
+where
+    C: Nep141Controller + Nep141ControllerInternal + Nep145Controller,
+{
+    fn hook<R>(
+        contract: &mut C,
+        args: &Nep145ForceUnregister<'_>,
+        f: impl FnOnce(&mut C) -> R,
+    ) -> R {
+        let beneficiary_id = contract
+            .get_force_unregister_beneficiary()
+            .unwrap_or_else(|| {
+                near_sdk::env::panic_str("No beneficiary set for forced unregistration")
+            });
+
+        let balance = contract.balance_of(&args.account_id);
+        if balance > 0 {
+            contract
+                .transfer(
+                    &Nep141Transfer::new(balance, args.account_id.clone(), beneficiary_id)
+                        .memo("storage forced unregistration"),
+                )
+                .unwrap_or_else(|e| {
+                    near_sdk::env::panic_str(&format!(
+                        "Failed to transfer tokens during forced unregistration: {e}",
+                    ))
+                });
+        }
+
+        let r = f(contract);
+
+        <C as Nep141ControllerInternal>::slot_account(&args.account_id).remove();
+
+        r
+    }
+}
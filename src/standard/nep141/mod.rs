@@ -3,7 +3,10 @@
 
 use std::borrow::Cow;
 
-use near_sdk::{borsh::BorshSerialize, near, AccountIdRef, BorshStorageKey, Gas};
+use near_sdk::{
+    borsh::BorshSerialize, json_types::U128, near, require, AccountId, AccountIdRef,
+    BorshStorageKey, Gas,
+};
 
 use crate::{hook::Hook, slot::Slot, standard::nep297::*, DefaultStorageKey};
 
@@ -13,7 +16,11 @@ mod event;
 pub use event::*;
 mod ext;
 pub use ext::*;
+pub mod compliance;
 pub mod hooks;
+pub mod shares;
+pub mod supply_cap;
+pub mod wrapped_near;
 
 /// Gas value required for [`Nep141Resolver::ft_resolve_transfer`] call,
 /// independent of the amount of gas required for the preceding
@@ -25,12 +32,18 @@ pub const GAS_FOR_FT_TRANSFER_CALL: Gas =
     Gas::from_gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.as_gas());
 /// Error message for insufficient gas.
 pub const MORE_GAS_FAIL_MESSAGE: &str = "Insufficient gas attached.";
+/// Denominator for [`TransferFeePolicy::basis_points`].
+pub const BASIS_POINTS_DENOMINATOR: u16 = 10_000;
+
+const BASIS_POINTS_FAIL_MESSAGE: &str = "Transfer fee basis points must not exceed 10000";
 
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
 enum StorageKey<'a> {
     TotalSupply,
     Account(&'a AccountIdRef),
+    TransferFeePolicy,
+    TransferFeeExempt(&'a AccountIdRef),
 }
 
 /// Transfer metadata generic over both types of transfer (`ft_transfer` and
@@ -50,6 +63,9 @@ pub struct Nep141Transfer<'a> {
     pub msg: Option<Cow<'a, str>>,
     /// Is this transfer a revert as a result of a [`Nep141::ft_transfer_call`] -> [`Nep141Receiver::ft_on_transfer`] call?
     pub revert: bool,
+    /// Is this transfer forced by an authority, e.g. [`Compliance::force_transfer`](compliance::Compliance::force_transfer)?
+    /// Forced transfers are not charged transfer fees.
+    pub forced: bool,
 }
 
 impl<'a> Nep141Transfer<'a> {
@@ -66,6 +82,7 @@ impl<'a> Nep141Transfer<'a> {
             memo: None,
             msg: None,
             revert: false,
+            forced: false,
         }
     }
 
@@ -87,6 +104,15 @@ impl<'a> Nep141Transfer<'a> {
         }
     }
 
+    /// Mark the transfer as forced by an authority.
+    #[must_use]
+    pub fn forced(self) -> Self {
+        Self {
+            forced: true,
+            ..self
+        }
+    }
+
     /// Returns `true` if this transfer comes from a `ft_transfer_call`
     /// call, `false` otherwise.
     #[must_use]
@@ -137,6 +163,8 @@ pub struct Nep141Burn<'a> {
     pub owner_id: Cow<'a, AccountIdRef>,
     /// Optional memo string.
     pub memo: Option<Cow<'a, str>>,
+    /// Is this burn forced by an authority, e.g. [`Compliance::clawback`](compliance::Compliance::clawback)?
+    pub forced: bool,
 }
 
 impl<'a> Nep141Burn<'a> {
@@ -146,6 +174,7 @@ impl<'a> Nep141Burn<'a> {
             amount,
             owner_id: owner_id.into(),
             memo: None,
+            forced: false,
         }
     }
 
@@ -157,6 +186,79 @@ impl<'a> Nep141Burn<'a> {
             ..self
         }
     }
+
+    /// Mark the burn as forced by an authority.
+    #[must_use]
+    pub fn forced(self) -> Self {
+        Self {
+            forced: true,
+            ..self
+        }
+    }
+}
+
+/// Fee charged on every non-exempt transfer. The fee is deducted from the
+/// transferred amount: the receiver receives the transferred amount minus the
+/// fee, and the fee collector receives the fee.
+///
+/// The fee is `amount * basis_points / 10000` (rounded down) plus `flat_fee`.
+///
+/// Note: When combined with NEP-145 storage accounting, the storage used by
+/// the fee collector's balance record is charged to the receiver of the first
+/// transfer that pays a fee, so it is a good idea to give the fee collector a
+/// balance record (e.g. by minting 0 tokens) in advance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct TransferFeePolicy {
+    /// Proportional fee, in basis points (1/100th of a percent) of the
+    /// transferred amount.
+    pub basis_points: u16,
+    /// Fixed fee, charged on top of the proportional fee.
+    pub flat_fee: U128,
+    /// Account that receives the fees.
+    pub fee_collector_id: AccountId,
+}
+
+impl TransferFeePolicy {
+    /// Creates a new fee policy with no fee, paying to `fee_collector_id`.
+    pub fn new(fee_collector_id: impl Into<AccountId>) -> Self {
+        Self {
+            basis_points: 0,
+            flat_fee: U128(0),
+            fee_collector_id: fee_collector_id.into(),
+        }
+    }
+
+    /// Sets the proportional fee.
+    #[must_use]
+    pub fn basis_points(self, basis_points: u16) -> Self {
+        Self {
+            basis_points,
+            ..self
+        }
+    }
+
+    /// Sets the fixed fee.
+    #[must_use]
+    pub fn flat_fee(self, flat_fee: u128) -> Self {
+        Self {
+            flat_fee: U128(flat_fee),
+            ..self
+        }
+    }
+
+    /// Calculates the fee for transferring `amount` tokens. The result may
+    /// exceed `amount`.
+    #[must_use]
+    pub fn fee_for(&self, amount: u128) -> u128 {
+        let basis_points = u128::from(self.basis_points);
+        let denominator = u128::from(BASIS_POINTS_DENOMINATOR);
+        // split to avoid overflow of `amount * basis_points`
+        let proportional =
+            amount / denominator * basis_points + amount % denominator * basis_points / denominator;
+
+        proportional.saturating_add(self.flat_fee.0)
+    }
 }
 
 /// Internal functions for [`Nep141Controller`]. Using these methods may result in unexpected behavior.
@@ -191,6 +293,18 @@ pub trait Nep141ControllerInternal {
     fn slot_total_supply() -> Slot<u128> {
         Self::root().field(StorageKey::TotalSupply)
     }
+
+    /// Slot for the transfer fee policy.
+    #[must_use]
+    fn slot_transfer_fee_policy() -> Slot<TransferFeePolicy> {
+        Self::root().field(StorageKey::TransferFeePolicy)
+    }
+
+    /// Slot for flagging an account as exempt from transfer fees.
+    #[must_use]
+    fn slot_transfer_fee_exempt(account_id: &AccountIdRef) -> Slot<bool> {
+        Self::root().field(StorageKey::TransferFeeExempt(account_id))
+    }
 }
 
 /// Non-public implementations of functions for managing a fungible token.
@@ -255,13 +369,41 @@ pub trait Nep141Controller {
         amount: u128,
     ) -> Result<(), TransferError>;
 
+    /// Returns the transfer fee policy, if there is one.
+    fn transfer_fee_policy(&self) -> Option<TransferFeePolicy>;
+
+    /// Sets or removes the transfer fee policy.
+    ///
+    /// # Panics
+    ///
+    /// If the basis points of the policy exceed 10000.
+    fn set_transfer_fee_policy(&mut self, policy: Option<&TransferFeePolicy>);
+
+    /// Returns `true` if the account is exempt from transfer fees.
+    fn is_transfer_fee_exempt(&self, account_id: &AccountIdRef) -> bool;
+
+    /// Exempts an account from transfer fees, or removes the exemption.
+    /// Transfers from or to an exempt account are not charged a fee.
+    fn set_transfer_fee_exempt(&mut self, account_id: &AccountIdRef, exempt: bool);
+
+    /// Calculates the fee that [`Nep141Controller::transfer`] would charge for
+    /// a transfer. Reverts of [`Nep141::ft_transfer_call`] transfers, forced
+    /// transfers, transfers from or to an exempt account, and transfers from
+    /// or to the fee collector are not charged a fee.
+    fn transfer_fee(&self, transfer: &Nep141Transfer<'_>) -> u128;
+
     /// Performs an NEP-141 token transfer, with event emission. Invokes
     /// [`Nep141Controller::TransferHook`].
     ///
+    /// If a transfer fee is charged, the receiver receives the transferred
+    /// amount minus the fee, and the fee is transferred to the fee collector.
+    /// Both legs are included in the emitted event.
+    ///
     /// # Errors
     ///
     /// - Receiver balance overflow.
     /// - Sender balance underflow.
+    /// - Transfer fee greater than the transferred amount.
     fn transfer(&mut self, transfer: &Nep141Transfer<'_>) -> Result<(), TransferError>;
 
     /// Performs an NEP-141 token mint, with event emission. Invokes
@@ -395,21 +537,92 @@ impl<T: Nep141ControllerInternal> Nep141Controller for T {
         Ok(())
     }
 
+    fn transfer_fee_policy(&self) -> Option<TransferFeePolicy> {
+        Self::slot_transfer_fee_policy().read()
+    }
+
+    fn set_transfer_fee_policy(&mut self, policy: Option<&TransferFeePolicy>) {
+        if let Some(policy) = policy {
+            require!(
+                policy.basis_points <= BASIS_POINTS_DENOMINATOR,
+                BASIS_POINTS_FAIL_MESSAGE,
+            );
+        }
+
+        Self::slot_transfer_fee_policy().set(policy);
+    }
+
+    fn is_transfer_fee_exempt(&self, account_id: &AccountIdRef) -> bool {
+        Self::slot_transfer_fee_exempt(account_id).exists()
+    }
+
+    fn set_transfer_fee_exempt(&mut self, account_id: &AccountIdRef, exempt: bool) {
+        Self::slot_transfer_fee_exempt(account_id).set(exempt.then_some(&true));
+    }
+
+    fn transfer_fee(&self, transfer: &Nep141Transfer<'_>) -> u128 {
+        if transfer.revert || transfer.forced {
+            return 0;
+        }
+
+        let Some(policy) = self.transfer_fee_policy() else {
+            return 0;
+        };
+
+        if transfer.sender_id.as_ref() == policy.fee_collector_id
+            || transfer.receiver_id.as_ref() == policy.fee_collector_id
+            || self.is_transfer_fee_exempt(&transfer.sender_id)
+            || self.is_transfer_fee_exempt(&transfer.receiver_id)
+        {
+            return 0;
+        }
+
+        policy.fee_for(transfer.amount)
+    }
+
     fn transfer(&mut self, transfer: &Nep141Transfer<'_>) -> Result<(), TransferError> {
+        let fee = self.transfer_fee(transfer);
+        let fee_collector_id = if fee > 0 {
+            self.transfer_fee_policy()
+                .map(|policy| policy.fee_collector_id)
+        } else {
+            None
+        };
+
+        let Some(received_amount) = transfer.amount.checked_sub(fee) else {
+            return Err(TransferFeeExceedsAmountError {
+                amount: transfer.amount,
+                fee,
+            }
+            .into());
+        };
+
         Self::TransferHook::hook(self, transfer, |contract| {
             contract.transfer_unchecked(
                 &transfer.sender_id,
                 &transfer.receiver_id,
-                transfer.amount,
+                received_amount,
             )?;
 
-            Nep141Event::FtTransfer(vec![FtTransferData {
+            let mut transfers = vec![FtTransferData {
                 old_owner_id: transfer.sender_id.clone(),
                 new_owner_id: transfer.receiver_id.clone(),
-                amount: transfer.amount.into(),
+                amount: received_amount.into(),
                 memo: transfer.memo.clone(),
-            }])
-            .emit();
+            }];
+
+            if let Some(fee_collector_id) = fee_collector_id {
+                contract.transfer_unchecked(&transfer.sender_id, &fee_collector_id, fee)?;
+
+                transfers.push(FtTransferData {
+                    old_owner_id: transfer.sender_id.clone(),
+                    new_owner_id: Cow::Owned(fee_collector_id),
+                    amount: fee.into(),
+                    memo: Some("transfer fee".into()),
+                });
+            }
+
+            Nep141Event::FtTransfer(transfers).emit();
 
             Ok(())
         })
@@ -0,0 +1,541 @@
+//! Share-based (rebasing) NEP-141 balances.
+//!
+//! [`Nep141SharesController`] is an alternative to [`Nep141Controller`](super::Nep141Controller)
+//! for yield-bearing tokens, e.g. liquid staking tokens. Instead of token
+//! balances, it stores the number of shares each account holds, along with
+//! the total number of shares and a contract-controlled pooled amount of
+//! tokens. Balances and total supply are derived from shares:
+//!
+//! * `total_supply = pooled_amount`
+//! * `balance_of(account) = shares_of(account) * pooled_amount / total_shares`
+//!
+//! Updating the pooled amount (e.g. when rewards are received) changes the
+//! balances of all accounts at once, without writing to each account.
+//!
+//! When there are no shares, shares and tokens convert 1:1. If shares exist
+//! but the pooled amount drops to zero, the shares are worth nothing and
+//! deposits are rejected until the pool is replenished, so that new
+//! depositors do not share the pool with the existing holders.
+//!
+//! # Rounding
+//!
+//! Conversions between amounts and shares always round in favor of the
+//! other shareholders:
+//!
+//! * Minting `amount` tokens credits `amount` converted to shares, rounded
+//!     down.
+//! * Burning `amount` tokens debits `amount` converted to shares, rounded up.
+//! * Transferring `amount` tokens moves `amount` converted to shares, rounded
+//!     up. The receiver's balance therefore increases by at least `amount`,
+//!     and an account can always transfer its entire balance.
+//!
+//! Events report token amounts, as required by NEP-141.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The shares root storage slot is not used or modified. The default
+//!     key is `~$141s`.
+//! * (UB) The sum of all account shares equals the total shares.
+//! * (ERR) Shares, total shares, and the pooled amount never overflow
+//!     `u128`.
+//! * (ERR) Accounts cannot spend more shares than they hold.
+//! * (ERR) Tokens cannot be deposited while shares exist but the pooled
+//!     amount is zero.
+
+use near_sdk::{borsh::BorshSerialize, env, AccountIdRef, BorshStorageKey};
+
+use crate::{
+    hook::Hook,
+    slot::Slot,
+    standard::{
+        nep141::{
+            BalanceOverflowError, BalanceUnderflowError, DepositError, EmptyPoolError, FtBurnData,
+            FtMintData, FtTransferData, Nep141Burn, Nep141Event, Nep141Mint, Nep141Transfer,
+            TotalSupplyOverflowError, TotalSupplyUnderflowError, TransferError, WithdrawError,
+        },
+        nep297::Event,
+    },
+    utils::mul_div,
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+const CONVERSION_OVERFLOW_FAIL_MESSAGE: &str = "Share conversion overflows u128";
+const EMPTY_POOL_FAIL_MESSAGE: &str = "Cannot convert to shares while the pool is empty";
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey<'a> {
+    Shares(&'a AccountIdRef),
+    TotalShares,
+    PooledAmount,
+}
+
+/// Rounding direction of conversions between amounts and shares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round towards zero.
+    Down,
+    /// Round away from zero.
+    Up,
+}
+
+/// Internal functions for [`Nep141SharesController`]. Using these methods may
+/// result in unexpected behavior.
+pub trait Nep141SharesControllerInternal {
+    /// Hook for mint operations.
+    type MintHook: for<'a> Hook<Self, Nep141Mint<'a>>
+    where
+        Self: Sized;
+    /// Hook for transfer operations.
+    type TransferHook: for<'a> Hook<Self, Nep141Transfer<'a>>
+    where
+        Self: Sized;
+    /// Hook for burn operations.
+    type BurnHook: for<'a> Hook<Self, Nep141Burn<'a>>
+    where
+        Self: Sized;
+
+    /// Root storage slot.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Nep141Shares)
+    }
+
+    /// Slot for the shares held by an account.
+    #[must_use]
+    fn slot_shares(account_id: &AccountIdRef) -> Slot<u128> {
+        Self::root().field(StorageKey::Shares(account_id))
+    }
+
+    /// Slot for the total number of shares.
+    #[must_use]
+    fn slot_total_shares() -> Slot<u128> {
+        Self::root().field(StorageKey::TotalShares)
+    }
+
+    /// Slot for the pooled amount of tokens.
+    #[must_use]
+    fn slot_pooled_amount() -> Slot<u128> {
+        Self::root().field(StorageKey::PooledAmount)
+    }
+}
+
+/// Non-public implementations of functions for managing a share-based
+/// fungible token.
+pub trait Nep141SharesController {
+    /// Hook for mint operations.
+    type MintHook: for<'a> Hook<Self, Nep141Mint<'a>>
+    where
+        Self: Sized;
+    /// Hook for transfer operations.
+    type TransferHook: for<'a> Hook<Self, Nep141Transfer<'a>>
+    where
+        Self: Sized;
+    /// Hook for burn operations.
+    type BurnHook: for<'a> Hook<Self, Nep141Burn<'a>>
+    where
+        Self: Sized;
+
+    /// Returns the number of shares held by an account.
+    fn shares_of(&self, account_id: &AccountIdRef) -> u128;
+
+    /// Returns the total number of shares.
+    fn total_shares(&self) -> u128;
+
+    /// Returns the pooled amount of tokens, which is also the total supply.
+    fn pooled_amount(&self) -> u128;
+
+    /// Sets the pooled amount of tokens, changing the balances of all
+    /// accounts proportionally. No event emission.
+    fn set_pooled_amount(&mut self, amount: u128);
+
+    /// Converts an amount of tokens to shares at the current rate.
+    ///
+    /// # Panics
+    ///
+    /// - If the result overflows `u128`.
+    /// - If `amount` is non-zero and shares exist but the pooled amount is
+    ///     zero.
+    fn amount_to_shares(&self, amount: u128, rounding: Rounding) -> u128;
+
+    /// Converts shares to an amount of tokens at the current rate.
+    ///
+    /// # Panics
+    ///
+    /// If the result overflows `u128`.
+    fn shares_to_amount(&self, shares: u128, rounding: Rounding) -> u128;
+
+    /// Get the balance of an account, rounded down. Returns 0 if the account
+    /// does not exist.
+    fn balance_of(&self, account_id: &AccountIdRef) -> u128 {
+        self.shares_to_amount(self.shares_of(account_id), Rounding::Down)
+    }
+
+    /// Get the total circulating supply of the token.
+    fn total_supply(&self) -> u128 {
+        self.pooled_amount()
+    }
+
+    /// Adds `amount` tokens to the pool, crediting the account with the
+    /// corresponding shares (rounded down). Returns the number of shares
+    /// credited. No event emission or hook invocation.
+    ///
+    /// # Errors
+    ///
+    /// - Shares exist but the pooled amount is zero.
+    /// - Account shares overflow.
+    /// - Total supply overflow.
+    fn deposit_unchecked(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<u128, DepositError>;
+
+    /// Removes `amount` tokens from the pool, debiting the account the
+    /// corresponding shares (rounded up). Returns the number of shares
+    /// debited. No event emission or hook invocation.
+    ///
+    /// # Errors
+    ///
+    /// - Account balance underflow.
+    /// - Total supply underflow.
+    fn withdraw_unchecked(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<u128, WithdrawError>;
+
+    /// Moves shares between accounts. No change to total shares or the pooled
+    /// amount. No event emission or hook invocation.
+    ///
+    /// # Errors
+    ///
+    /// - Receiver shares overflow.
+    /// - Sender shares underflow.
+    fn transfer_shares_unchecked(
+        &mut self,
+        sender_account_id: &AccountIdRef,
+        receiver_account_id: &AccountIdRef,
+        shares: u128,
+    ) -> Result<(), TransferError>;
+
+    /// Moves `amount` tokens between accounts, converted to shares (rounded
+    /// up). Returns the number of shares moved. No event emission or hook
+    /// invocation.
+    ///
+    /// # Errors
+    ///
+    /// - Receiver shares overflow.
+    /// - Sender balance underflow.
+    fn transfer_unchecked(
+        &mut self,
+        sender_account_id: &AccountIdRef,
+        receiver_account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<u128, TransferError>;
+
+    /// Performs an NEP-141 token transfer, with event emission. Invokes
+    /// [`Nep141SharesController::TransferHook`].
+    ///
+    /// # Errors
+    ///
+    /// - Receiver shares overflow.
+    /// - Sender balance underflow.
+    fn transfer(&mut self, transfer: &Nep141Transfer<'_>) -> Result<(), TransferError>;
+
+    /// Performs an NEP-141 token mint, with event emission. Invokes
+    /// [`Nep141SharesController::MintHook`].
+    ///
+    /// # Errors
+    ///
+    /// - Account shares overflow.
+    /// - Total supply overflow.
+    fn mint(&mut self, mint: &Nep141Mint<'_>) -> Result<(), DepositError>;
+
+    /// Performs an NEP-141 token burn, with event emission. Invokes
+    /// [`Nep141SharesController::BurnHook`].
+    ///
+    /// # Errors
+    ///
+    /// - Account balance underflow.
+    /// - Total supply underflow.
+    fn burn(&mut self, burn: &Nep141Burn<'_>) -> Result<(), WithdrawError>;
+}
+
+impl<T: Nep141SharesControllerInternal> Nep141SharesController for T {
+    type MintHook = T::MintHook;
+    type TransferHook = T::TransferHook;
+    type BurnHook = T::BurnHook;
+
+    fn shares_of(&self, account_id: &AccountIdRef) -> u128 {
+        Self::slot_shares(account_id).read().unwrap_or(0)
+    }
+
+    fn total_shares(&self) -> u128 {
+        Self::slot_total_shares().read().unwrap_or(0)
+    }
+
+    fn pooled_amount(&self) -> u128 {
+        Self::slot_pooled_amount().read().unwrap_or(0)
+    }
+
+    fn set_pooled_amount(&mut self, amount: u128) {
+        Self::slot_pooled_amount().write(&amount);
+    }
+
+    fn amount_to_shares(&self, amount: u128, rounding: Rounding) -> u128 {
+        let total_shares = self.total_shares();
+        let pooled_amount = self.pooled_amount();
+
+        if total_shares == 0 || amount == 0 {
+            return amount;
+        }
+
+        if pooled_amount == 0 {
+            env::panic_str(EMPTY_POOL_FAIL_MESSAGE);
+        }
+
+        mul_div(
+            amount,
+            total_shares,
+            pooled_amount,
+            rounding == Rounding::Up,
+        )
+        .unwrap_or_else(|| env::panic_str(CONVERSION_OVERFLOW_FAIL_MESSAGE))
+    }
+
+    fn shares_to_amount(&self, shares: u128, rounding: Rounding) -> u128 {
+        let total_shares = self.total_shares();
+        let pooled_amount = self.pooled_amount();
+
+        if total_shares == 0 {
+            return shares;
+        }
+
+        mul_div(
+            shares,
+            pooled_amount,
+            total_shares,
+            rounding == Rounding::Up,
+        )
+        .unwrap_or_else(|| env::panic_str(CONVERSION_OVERFLOW_FAIL_MESSAGE))
+    }
+
+    fn deposit_unchecked(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<u128, DepositError> {
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let total_shares = self.total_shares();
+        let pooled_amount = self.pooled_amount();
+
+        if total_shares > 0 && pooled_amount == 0 {
+            return Err(EmptyPoolError {
+                total_shares,
+                amount,
+            }
+            .into());
+        }
+
+        let shares = self.amount_to_shares(amount, Rounding::Down);
+
+        let Some(new_pooled_amount) = pooled_amount.checked_add(amount) else {
+            return Err(TotalSupplyOverflowError {
+                total_supply: pooled_amount,
+                amount,
+            }
+            .into());
+        };
+
+        let account_shares = self.shares_of(account_id);
+        let (Some(new_account_shares), Some(new_total_shares)) = (
+            account_shares.checked_add(shares),
+            total_shares.checked_add(shares),
+        ) else {
+            return Err(BalanceOverflowError {
+                account_id: account_id.to_owned(),
+                balance: self.balance_of(account_id),
+                amount,
+            }
+            .into());
+        };
+
+        Self::slot_shares(account_id).write(&new_account_shares);
+        Self::slot_total_shares().write(&new_total_shares);
+        Self::slot_pooled_amount().write(&new_pooled_amount);
+
+        Ok(shares)
+    }
+
+    fn withdraw_unchecked(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<u128, WithdrawError> {
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let pooled_amount = self.pooled_amount();
+        let Some(new_pooled_amount) = pooled_amount.checked_sub(amount) else {
+            return Err(TotalSupplyUnderflowError {
+                total_supply: pooled_amount,
+                amount,
+            }
+            .into());
+        };
+
+        let shares = self.amount_to_shares(amount, Rounding::Up);
+
+        let Some(new_account_shares) = self.shares_of(account_id).checked_sub(shares) else {
+            return Err(BalanceUnderflowError {
+                account_id: account_id.to_owned(),
+                balance: self.balance_of(account_id),
+                amount,
+            }
+            .into());
+        };
+
+        Self::slot_shares(account_id).write(&new_account_shares);
+        // total shares >= account shares
+        Self::slot_total_shares().write(&(self.total_shares() - shares));
+        Self::slot_pooled_amount().write(&new_pooled_amount);
+
+        Ok(shares)
+    }
+
+    fn transfer_shares_unchecked(
+        &mut self,
+        sender_account_id: &AccountIdRef,
+        receiver_account_id: &AccountIdRef,
+        shares: u128,
+    ) -> Result<(), TransferError> {
+        let sender_shares = self.shares_of(sender_account_id);
+        let Some(new_sender_shares) = sender_shares.checked_sub(shares) else {
+            return Err(BalanceUnderflowError {
+                account_id: sender_account_id.to_owned(),
+                balance: sender_shares,
+                amount: shares,
+            }
+            .into());
+        };
+
+        let receiver_shares = self.shares_of(receiver_account_id);
+        let Some(new_receiver_shares) = receiver_shares.checked_add(shares) else {
+            return Err(BalanceOverflowError {
+                account_id: receiver_account_id.to_owned(),
+                balance: receiver_shares,
+                amount: shares,
+            }
+            .into());
+        };
+
+        Self::slot_shares(sender_account_id).write(&new_sender_shares);
+        Self::slot_shares(receiver_account_id).write(&new_receiver_shares);
+
+        Ok(())
+    }
+
+    fn transfer_unchecked(
+        &mut self,
+        sender_account_id: &AccountIdRef,
+        receiver_account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<u128, TransferError> {
+        // equivalent to comparing shares rounded up, and does not convert an
+        // amount the sender cannot cover
+        let balance = self.balance_of(sender_account_id);
+        if balance < amount {
+            return Err(BalanceUnderflowError {
+                account_id: sender_account_id.to_owned(),
+                balance,
+                amount,
+            }
+            .into());
+        }
+
+        let shares = self.amount_to_shares(amount, Rounding::Up);
+
+        self.transfer_shares_unchecked(sender_account_id, receiver_account_id, shares)?;
+
+        Ok(shares)
+    }
+
+    fn transfer(&mut self, transfer: &Nep141Transfer<'_>) -> Result<(), TransferError> {
+        Self::TransferHook::hook(self, transfer, |contract| {
+            contract.transfer_unchecked(
+                &transfer.sender_id,
+                &transfer.receiver_id,
+                transfer.amount,
+            )?;
+
+            Nep141Event::FtTransfer(vec![FtTransferData {
+                old_owner_id: transfer.sender_id.clone(),
+                new_owner_id: transfer.receiver_id.clone(),
+                amount: transfer.amount.into(),
+                memo: transfer.memo.clone(),
+            }])
+            .emit();
+
+            Ok(())
+        })
+    }
+
+    fn mint(&mut self, mint: &Nep141Mint<'_>) -> Result<(), DepositError> {
+        Self::MintHook::hook(self, mint, |contract| {
+            contract.deposit_unchecked(&mint.receiver_id, mint.amount)?;
+
+            Nep141Event::FtMint(vec![FtMintData {
+                owner_id: mint.receiver_id.clone(),
+                amount: mint.amount.into(),
+                memo: mint.memo.clone(),
+            }])
+            .emit();
+
+            Ok(())
+        })
+    }
+
+    fn burn(&mut self, burn: &Nep141Burn<'_>) -> Result<(), WithdrawError> {
+        Self::BurnHook::hook(self, burn, |contract| {
+            contract.withdraw_unchecked(&burn.owner_id, burn.amount)?;
+
+            Nep141Event::FtBurn(vec![FtBurnData {
+                owner_id: burn.owner_id.clone(),
+                amount: burn.amount.into(),
+                memo: burn.memo.clone(),
+            }])
+            .emit();
+
+            Ok(())
+        })
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::U128, AccountId};
+
+    /// External (public) share queries for [`Nep141SharesController`](super::Nep141SharesController).
+    #[ext_contract(ext_nep141_shares)]
+    pub trait Nep141SharesExternal {
+        /// Returns the number of shares held by an account.
+        fn ft_shares_of(&self, account_id: AccountId) -> U128;
+
+        /// Returns the total number of shares.
+        fn ft_total_shares(&self) -> U128;
+    }
+}
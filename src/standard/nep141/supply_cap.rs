@@ -0,0 +1,379 @@
+//! Supply cap and mint rate limits for NEP-141 tokens.
+//!
+//! [`SupplyCap`] stores an optional hard cap on the total supply of a token
+//! and an optional [`MintRateLimit`], which limits the amount of tokens that
+//! may be minted within a window of epochs. [`SupplyCapHook`] enforces both
+//! limits when installed as a mint hook on a [`Nep141Controller`]
+//! implementation.
+//!
+//! The cap applies to the circulating supply: burning tokens frees up room
+//! for new mints. The mint budget is consumed by the amount actually minted,
+//! and is reset at the start of every window.
+//!
+//! The crate exports a [derive macro](near_sdk_contract_tools_macros::SupplyCap)
+//! that derives a default implementation, including view functions.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The supply cap root storage slot is not used or modified. The
+//!     default key is `~$141c`.
+//! * (UB) [`SupplyCapHook`] is installed as a mint hook on the
+//!     [`Nep141Controller`] implementation. Otherwise, limits are not enforced.
+//! * (ERR) A mint may not increase the total supply above the maximum supply.
+//! * (ERR) A mint may not exceed the remaining budget of the current window.
+//! * (ERR) The window length of a [`MintRateLimit`] must be nonzero.
+
+use near_sdk::{borsh::BorshSerialize, env, json_types::U128, near, require, BorshStorageKey};
+use thiserror::Error;
+
+use crate::{
+    hook::Hook,
+    slot::Slot,
+    standard::nep141::{Nep141Controller, Nep141Mint},
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+const ZERO_WINDOW_FAIL_MESSAGE: &str = "Mint rate limit window must be at least one epoch";
+
+/// Limits the amount of tokens that may be minted within a window of epochs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct MintRateLimit {
+    /// Maximum amount of tokens that may be minted in a single window.
+    pub budget: U128,
+    /// Length of a window, in epochs.
+    pub window_epochs: u64,
+}
+
+impl MintRateLimit {
+    /// Creates a new rate limit of `budget` tokens every `window_epochs`
+    /// epochs.
+    #[must_use]
+    pub fn new(budget: u128, window_epochs: u64) -> Self {
+        Self {
+            budget: budget.into(),
+            window_epochs,
+        }
+    }
+
+    /// Index of the window that contains the given epoch.
+    #[must_use]
+    pub fn window_of(&self, epoch_height: u64) -> u64 {
+        epoch_height / self.window_epochs.max(1)
+    }
+}
+
+/// Amount of tokens minted during a window.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[near]
+pub struct MintWindow {
+    /// Index of the window, as calculated by [`MintRateLimit::window_of`].
+    pub window: u64,
+    /// Amount of tokens minted during the window.
+    pub minted: u128,
+}
+
+/// The total supply would exceed the maximum supply.
+#[derive(Debug, Error)]
+#[error("Minting {amount} would exceed the maximum supply {max_supply} (current total supply: {total_supply}).")]
+pub struct MaxSupplyExceededError {
+    /// The maximum supply.
+    pub max_supply: u128,
+    /// The current total supply.
+    pub total_supply: u128,
+    /// The amount of the failed mint attempt.
+    pub amount: u128,
+}
+
+/// The mint would exceed the budget of the current window.
+#[derive(Debug, Error)]
+#[error("Minting {amount} would exceed the mint budget {budget} of window {window} (already minted: {minted}).")]
+pub struct MintBudgetExceededError {
+    /// Index of the current window.
+    pub window: u64,
+    /// Mint budget per window.
+    pub budget: u128,
+    /// Amount already minted during the current window.
+    pub minted: u128,
+    /// The amount of the failed mint attempt.
+    pub amount: u128,
+}
+
+/// Errors that may occur when checking a mint against the supply limits.
+#[derive(Debug, Error)]
+pub enum SupplyCapError {
+    /// The total supply would exceed the maximum supply.
+    #[error(transparent)]
+    MaxSupplyExceeded(#[from] MaxSupplyExceededError),
+    /// The mint would exceed the budget of the current window.
+    #[error(transparent)]
+    MintBudgetExceeded(#[from] MintBudgetExceededError),
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey {
+    MaxSupply,
+    MintRateLimit,
+    MintWindow,
+}
+
+/// Internal functions for [`SupplyCap`]. Using these methods may result in unexpected behavior.
+pub trait SupplyCapInternal {
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::SupplyCap)
+    }
+
+    /// Storage slot for the maximum supply.
+    #[must_use]
+    fn slot_max_supply() -> Slot<u128> {
+        Self::root().field(StorageKey::MaxSupply)
+    }
+
+    /// Storage slot for the mint rate limit.
+    #[must_use]
+    fn slot_mint_rate_limit() -> Slot<MintRateLimit> {
+        Self::root().field(StorageKey::MintRateLimit)
+    }
+
+    /// Storage slot for the amount minted in the most recent window.
+    #[must_use]
+    fn slot_mint_window() -> Slot<MintWindow> {
+        Self::root().field(StorageKey::MintWindow)
+    }
+}
+
+/// Supply limits for a fungible token.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::{near, PanicOnDefault};
+/// use near_sdk_contract_tools::{
+///     ft::*,
+///     standard::nep141::supply_cap::{MintRateLimit, SupplyCap, SupplyCapHook},
+///     Nep141, SupplyCap,
+/// };
+///
+/// #[derive(Nep141, SupplyCap, PanicOnDefault)]
+/// #[nep141(mint_hook = "SupplyCapHook")]
+/// #[near(contract_state)]
+/// struct Contract {}
+///
+/// #[near]
+/// impl Contract {
+///     #[init]
+///     pub fn new() -> Self {
+///         let mut contract = Self {};
+///
+///         contract.set_max_supply(Some(1_000_000));
+///         contract.set_mint_rate_limit(Some(&MintRateLimit::new(1_000, 10)));
+///
+///         contract
+///     }
+/// }
+/// ```
+pub trait SupplyCap {
+    /// Returns the maximum supply, if there is one.
+    fn max_supply(&self) -> Option<u128>;
+
+    /// Sets or removes the maximum supply. Does not check the current total
+    /// supply.
+    fn set_max_supply(&mut self, max_supply: Option<u128>);
+
+    /// Returns the mint rate limit, if there is one.
+    fn mint_rate_limit(&self) -> Option<MintRateLimit>;
+
+    /// Sets or removes the mint rate limit. Resets the amount minted in the
+    /// current window.
+    fn set_mint_rate_limit(&mut self, mint_rate_limit: Option<&MintRateLimit>);
+
+    /// Returns the amount of tokens minted during the current window. Returns
+    /// 0 if there is no mint rate limit.
+    fn minted_in_current_window(&self) -> u128;
+
+    /// Returns the amount of tokens that may still be minted before reaching
+    /// the maximum supply, or `None` if there is no maximum supply.
+    fn remaining_supply(&self) -> Option<u128>;
+
+    /// Returns the amount of tokens that may still be minted during the
+    /// current window, or `None` if there is no mint rate limit.
+    fn remaining_mint_budget(&self) -> Option<u128>;
+
+    /// Returns the amount of tokens that may be minted right now, taking into
+    /// account both the maximum supply and the mint rate limit. Returns `None`
+    /// if neither limit is configured.
+    fn remaining_mintable_supply(&self) -> Option<u128> {
+        match (self.remaining_supply(), self.remaining_mint_budget()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Checks whether `amount` tokens may be minted.
+    ///
+    /// # Errors
+    ///
+    /// - If the total supply would exceed the maximum supply.
+    /// - If the mint would exceed the budget of the current window.
+    fn check_mint(&self, amount: u128) -> Result<(), SupplyCapError>;
+
+    /// Adds `amount` to the amount minted during the current window. Does
+    /// nothing if there is no mint rate limit. Does not check the budget.
+    fn record_mint_unchecked(&mut self, amount: u128);
+}
+
+impl<T: SupplyCapInternal + Nep141Controller> SupplyCap for T {
+    fn max_supply(&self) -> Option<u128> {
+        Self::slot_max_supply().read()
+    }
+
+    fn set_max_supply(&mut self, max_supply: Option<u128>) {
+        Self::slot_max_supply().set(max_supply.as_ref());
+    }
+
+    fn mint_rate_limit(&self) -> Option<MintRateLimit> {
+        Self::slot_mint_rate_limit().read()
+    }
+
+    fn set_mint_rate_limit(&mut self, mint_rate_limit: Option<&MintRateLimit>) {
+        if let Some(mint_rate_limit) = mint_rate_limit {
+            require!(mint_rate_limit.window_epochs > 0, ZERO_WINDOW_FAIL_MESSAGE);
+        }
+
+        Self::slot_mint_rate_limit().set(mint_rate_limit);
+        Self::slot_mint_window().remove();
+    }
+
+    fn minted_in_current_window(&self) -> u128 {
+        let Some(mint_rate_limit) = self.mint_rate_limit() else {
+            return 0;
+        };
+
+        let window = mint_rate_limit.window_of(env::epoch_height());
+
+        Self::slot_mint_window()
+            .read()
+            .filter(|w| w.window == window)
+            .map_or(0, |w| w.minted)
+    }
+
+    fn remaining_supply(&self) -> Option<u128> {
+        self.max_supply()
+            .map(|max_supply| max_supply.saturating_sub(self.total_supply()))
+    }
+
+    fn remaining_mint_budget(&self) -> Option<u128> {
+        self.mint_rate_limit().map(|mint_rate_limit| {
+            mint_rate_limit
+                .budget
+                .0
+                .saturating_sub(self.minted_in_current_window())
+        })
+    }
+
+    fn check_mint(&self, amount: u128) -> Result<(), SupplyCapError> {
+        if let Some(max_supply) = self.max_supply() {
+            let total_supply = self.total_supply();
+
+            if total_supply
+                .checked_add(amount)
+                .map_or(true, |new_total_supply| new_total_supply > max_supply)
+            {
+                return Err(MaxSupplyExceededError {
+                    max_supply,
+                    total_supply,
+                    amount,
+                }
+                .into());
+            }
+        }
+
+        if let Some(mint_rate_limit) = self.mint_rate_limit() {
+            let minted = self.minted_in_current_window();
+            let budget = mint_rate_limit.budget.0;
+
+            if minted
+                .checked_add(amount)
+                .map_or(true, |new_minted| new_minted > budget)
+            {
+                return Err(MintBudgetExceededError {
+                    window: mint_rate_limit.window_of(env::epoch_height()),
+                    budget,
+                    minted,
+                    amount,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_mint_unchecked(&mut self, amount: u128) {
+        let Some(mint_rate_limit) = self.mint_rate_limit() else {
+            return;
+        };
+
+        let window = mint_rate_limit.window_of(env::epoch_height());
+        let minted = self.minted_in_current_window().saturating_add(amount);
+
+        Self::slot_mint_window().write(&MintWindow { window, minted });
+    }
+}
+
+/// Rejects mints that exceed the maximum supply or the mint budget of the
+/// current window.
+///
+/// The amount recorded against the mint budget is the actual change in total
+/// supply, so mints that fail without panicking do not consume the budget.
+pub struct SupplyCapHook;
+
+impl<C: SupplyCap + Nep141Controller> Hook<C, Nep141Mint<'_>> for SupplyCapHook {
+    fn hook<R>(contract: &mut C, action: &Nep141Mint<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        contract
+            .check_mint(action.amount)
+            .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+        let total_supply_before = contract.total_supply();
+
+        let r = f(contract);
+
+        let minted = contract.total_supply().saturating_sub(total_supply_before);
+        contract.record_mint_unchecked(minted);
+
+        r
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::U128};
+
+    use super::MintRateLimit;
+
+    /// External (public) view methods for [`SupplyCap`](super::SupplyCap).
+    #[ext_contract(ext_supply_cap)]
+    pub trait SupplyCapExternal {
+        /// Returns the maximum supply, if there is one.
+        fn ft_max_supply(&self) -> Option<U128>;
+
+        /// Returns the mint rate limit, if there is one.
+        fn ft_mint_rate_limit(&self) -> Option<MintRateLimit>;
+
+        /// Returns the amount of tokens that may be minted right now, or
+        /// `None` if the supply is unlimited.
+        fn ft_remaining_mintable_supply(&self) -> Option<U128>;
+    }
+}
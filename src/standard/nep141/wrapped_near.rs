@@ -0,0 +1,170 @@
+//! Wrapped NEAR: a fungible token minted 1:1 against attached NEAR.
+//!
+//! [`WrappedNear::deposit_near`] mints tokens for attached NEAR, registering
+//! the depositor with NEP-145 storage management first if necessary. The
+//! registration fee (the minimum storage balance) is taken out of the attached
+//! deposit. [`WrappedNear::withdraw_near`] burns tokens and returns a promise
+//! that transfers the same amount of NEAR to the owner.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) Tokens are only minted by [`WrappedNear::deposit_near`] and
+//!     only burned by [`WrappedNear::withdraw_near`]. Otherwise, the total
+//!     supply is no longer backed 1:1 by NEAR held by the contract.
+//! * (UB) A NEP-145 storage accounting hook (e.g.
+//!     [`Nep141StorageAccountingHook`](crate::standard::nep145::hooks::Nep141StorageAccountingHook))
+//!     is installed, so that storage is paid for out of storage balances
+//!     instead of out of the NEAR backing the tokens.
+//! * (ERR) The attached deposit must cover the minimum storage balance for
+//!     accounts that are not yet registered.
+
+use std::borrow::Cow;
+
+use near_sdk::{AccountId, AccountIdRef, NearToken, Promise};
+use thiserror::Error;
+
+use crate::standard::{
+    nep141::{DepositError, Nep141Burn, Nep141Controller, Nep141Mint, WithdrawError},
+    nep145::{error::StorageDepositError, Nep145Controller},
+};
+
+pub use ext::*;
+
+/// Memo of NEP-141 mint events emitted by [`WrappedNear::deposit_near`].
+pub const DEPOSIT_MEMO: &str = "Deposit";
+/// Memo of NEP-141 burn events emitted by [`WrappedNear::withdraw_near`].
+pub const WITHDRAW_MEMO: &str = "Withdraw";
+
+/// The attached deposit does not cover the registration fee.
+#[derive(Debug, Error)]
+#[error("Account {account_id} must attach at least {minimum_deposit} to register")]
+pub struct InsufficientRegistrationDepositError {
+    /// The account that attempted to deposit.
+    pub account_id: AccountId,
+    /// The minimum deposit required to register the account.
+    pub minimum_deposit: NearToken,
+}
+
+/// Errors that may occur when depositing NEAR.
+#[derive(Debug, Error)]
+pub enum DepositNearError {
+    /// The attached deposit does not cover the registration fee.
+    #[error(transparent)]
+    InsufficientRegistrationDeposit(#[from] InsufficientRegistrationDepositError),
+    /// Storage registration failed.
+    #[error(transparent)]
+    StorageDeposit(#[from] StorageDepositError),
+    /// Minting failed.
+    #[error(transparent)]
+    Mint(#[from] DepositError),
+}
+
+/// Wrapped NEAR controller. These functions do not transfer NEAR to the
+/// contract, so they should only be called with amounts that have been
+/// attached to the current function call.
+pub trait WrappedNear {
+    /// Mints tokens for `amount` NEAR deposited by `account_id`. If the
+    /// account is not registered with NEP-145, the minimum storage balance is
+    /// deducted from `amount` and deposited into its storage account first.
+    /// Returns the amount of tokens minted.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not registered and `amount` is less than the
+    ///     minimum storage balance.
+    /// - If storage registration fails.
+    /// - If minting fails.
+    fn deposit_near(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<u128, DepositNearError>;
+
+    /// Burns `amount` tokens owned by `account_id` and returns a promise that
+    /// transfers `amount` NEAR to `account_id`. The promise must not be
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// - If the account does not own enough tokens.
+    #[must_use = "The returned promise transfers the withdrawn NEAR"]
+    fn withdraw_near(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<Promise, WithdrawError>;
+}
+
+impl<T: Nep141Controller + Nep145Controller> WrappedNear for T {
+    fn deposit_near(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<u128, DepositNearError> {
+        let amount = if self.get_storage_balance(account_id).is_ok() {
+            amount
+        } else {
+            let minimum_deposit = self.get_storage_balance_bounds().min;
+            let remaining = amount.checked_sub(minimum_deposit).ok_or_else(|| {
+                InsufficientRegistrationDepositError {
+                    account_id: account_id.to_owned(),
+                    minimum_deposit,
+                }
+            })?;
+
+            self.deposit_to_storage_account(account_id, minimum_deposit)?;
+
+            remaining
+        };
+
+        let amount = amount.as_yoctonear();
+
+        self.mint(&Nep141Mint {
+            amount,
+            receiver_id: account_id.into(),
+            memo: Some(Cow::Borrowed(DEPOSIT_MEMO)),
+        })?;
+
+        Ok(amount)
+    }
+
+    fn withdraw_near(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+    ) -> Result<Promise, WithdrawError> {
+        self.burn(&Nep141Burn {
+            amount,
+            owner_id: account_id.into(),
+            memo: Some(Cow::Borrowed(WITHDRAW_MEMO)),
+            forced: false,
+        })?;
+
+        Ok(Promise::new(account_id.to_owned()).transfer(NearToken::from_yoctonear(amount)))
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::U128, Promise};
+
+    /// External (public) methods for [`WrappedNear`](super::WrappedNear).
+    #[ext_contract(ext_wrapped_near)]
+    pub trait WrappedNearExternal {
+        /// Mints tokens for the attached NEAR to the predecessor, registering
+        /// the predecessor with storage management if necessary. Returns the
+        /// amount of tokens minted.
+        fn near_deposit(&mut self) -> U128;
+
+        /// Burns tokens owned by the predecessor and transfers the same amount
+        /// of NEAR back. Requires exactly 1 yoctoNEAR attached.
+        fn near_withdraw(&mut self, amount: U128) -> Promise;
+    }
+}
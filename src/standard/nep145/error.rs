@@ -64,6 +64,27 @@ pub struct UnregisterWithLockedBalanceError {
     pub locked_balance: NearToken,
 }
 
+/// Occurs when a sponsor pool does not exist.
+#[derive(Debug, Error)]
+#[error("Sponsor pool {0} does not exist")]
+pub struct SponsorPoolNotFoundError(pub AccountId);
+
+/// Occurs when an account attempts to join a sponsor pool that is not open.
+#[derive(Debug, Error)]
+#[error("Sponsor pool {0} is not open")]
+pub struct SponsorPoolNotOpenError(pub AccountId);
+
+/// Occurs when an account is already sponsored by a different sponsor.
+#[derive(Debug, Error)]
+#[error("Account {account_id} is already sponsored by {sponsor_id}")]
+pub struct AlreadySponsoredError {
+    /// The sponsored account.
+    pub account_id: AccountId,
+
+    /// The current sponsor of the account.
+    pub sponsor_id: AccountId,
+}
+
 /// Errors that can occur when locking storage balance.
 #[derive(Debug, Error)]
 pub enum StorageLockError {
@@ -111,6 +132,17 @@ pub enum StorageWithdrawError {
     InsufficientBalance(#[from] InsufficientBalanceError),
 }
 
+/// Errors that can occur when registering an account on behalf of another.
+#[derive(Debug, Error)]
+pub enum StorageRegisterError {
+    /// The payer could not cover the registration.
+    #[error(transparent)]
+    Withdraw(#[from] StorageWithdrawError),
+    /// The registration does not satisfy the balance bounds.
+    #[error(transparent)]
+    Deposit(#[from] StorageDepositError),
+}
+
 /// Errors that can occur when unregistering storage balance.
 #[derive(Debug, Error)]
 pub enum StorageUnregisterError {
@@ -142,3 +174,31 @@ pub enum StorageAccountingError {
     #[error(transparent)]
     StorageUnlock(#[from] StorageUnlockError),
 }
+
+/// Errors that can occur when withdrawing from a sponsor pool.
+#[derive(Debug, Error)]
+pub enum SponsorWithdrawError {
+    /// The pool does not exist.
+    #[error(transparent)]
+    SponsorPoolNotFound(#[from] SponsorPoolNotFoundError),
+    /// The withdrawal exceeds the available balance of the pool.
+    #[error(transparent)]
+    InsufficientBalance(#[from] InsufficientBalanceError),
+}
+
+/// Errors that can occur when assigning a sponsor to an account.
+#[derive(Debug, Error)]
+pub enum SponsorAccountError {
+    /// The pool does not exist.
+    #[error(transparent)]
+    SponsorPoolNotFound(#[from] SponsorPoolNotFoundError),
+    /// The pool is not open to the account.
+    #[error(transparent)]
+    SponsorPoolNotOpen(#[from] SponsorPoolNotOpenError),
+    /// The account is already sponsored by a different sponsor.
+    #[error(transparent)]
+    AlreadySponsored(#[from] AlreadySponsoredError),
+    /// The pool cannot pay for the records of the sponsorship.
+    #[error(transparent)]
+    InsufficientBalance(#[from] InsufficientBalanceError),
+}
@@ -0,0 +1,117 @@
+//! Events describing changes to NEP-145 storage balances.
+
+use near_sdk::{env, AccountId, AccountIdRef, NearToken};
+use near_sdk_contract_tools_macros::event;
+
+/// Upper bound, in bytes, of the logs emitted by a single call to
+/// [`Nep145Controller::storage_accounting`](super::Nep145Controller::storage_accounting):
+/// at most two `lock` or `unlock` events with the longest account IDs and
+/// amounts.
+pub const MAX_STORAGE_ACCOUNTING_LOG_LENGTH: usize = 640;
+
+/// Events emitted by the default implementation of
+/// [`Nep145Controller`](super::Nep145Controller), so that indexers and
+/// wallets can explain changes to storage balances.
+///
+/// `payer_id` is only present when the balance is paid for (or refunded to)
+/// an account other than `account_id`, e.g. a sponsor, or the predecessor
+/// depositing on behalf of another account.
+#[event(
+    standard = "x-storage",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum StorageEvent {
+    /// Emitted when an account is registered.
+    Register {
+        /// The registered account.
+        account_id: AccountId,
+    },
+    /// Emitted when an account is unregistered.
+    Unregister {
+        /// The unregistered account.
+        account_id: AccountId,
+        /// Amount of storage balance released by the unregistration.
+        amount: NearToken,
+        /// Whether the unregistration was forced.
+        force: bool,
+    },
+    /// Emitted when storage balance is deposited to an account.
+    Deposit {
+        /// The account credited with the deposit.
+        account_id: AccountId,
+        /// Amount deposited.
+        amount: NearToken,
+        /// The account that paid for the deposit.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payer_id: Option<AccountId>,
+    },
+    /// Emitted when available storage balance is withdrawn from an account.
+    Withdraw {
+        /// The account debited with the withdrawal.
+        account_id: AccountId,
+        /// Amount withdrawn.
+        amount: NearToken,
+    },
+    /// Emitted when storage balance is locked to pay for storage used by an
+    /// account.
+    Lock {
+        /// The account using the storage.
+        account_id: AccountId,
+        /// Amount locked.
+        amount: NearToken,
+        /// Number of bytes paid for by the locked amount.
+        bytes: u64,
+        /// The account whose balance was locked.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payer_id: Option<AccountId>,
+    },
+    /// Emitted when locked storage balance is released.
+    Unlock {
+        /// The account that released the storage.
+        account_id: AccountId,
+        /// Amount unlocked.
+        amount: NearToken,
+        /// Number of bytes released.
+        bytes: u64,
+        /// The account whose balance was unlocked.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payer_id: Option<AccountId>,
+    },
+    /// Emitted when balance is deposited to the pool of a sponsor.
+    SponsorDeposit {
+        /// The sponsor whose pool was credited.
+        sponsor_id: AccountId,
+        /// Amount deposited, including the storage fee of a new pool.
+        amount: NearToken,
+        /// The account that paid for the deposit.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payer_id: Option<AccountId>,
+    },
+    /// Emitted when available balance is withdrawn from the pool of a
+    /// sponsor.
+    SponsorWithdraw {
+        /// The sponsor whose pool was debited.
+        sponsor_id: AccountId,
+        /// Amount withdrawn.
+        amount: NearToken,
+    },
+}
+
+impl StorageEvent {
+    /// Returns `payer_id` if it differs from `account_id`.
+    pub(crate) fn payer_if_other(
+        account_id: &AccountIdRef,
+        payer_id: &AccountIdRef,
+    ) -> Option<AccountId> {
+        (account_id != payer_id).then(|| payer_id.to_owned())
+    }
+
+    /// Number of bytes of storage that `amount` pays for.
+    pub(crate) fn bytes_for(amount: NearToken) -> u64 {
+        u64::try_from(amount.as_yoctonear() / env::storage_byte_cost().as_yoctonear())
+            .unwrap_or(u64::MAX)
+    }
+}
@@ -1,7 +1,7 @@
 //! External interface for NEP-145.
 #![allow(missing_docs)] // ext_contract doesn't play nice with #![warn(missing_docs)]
 
-use super::{StorageBalance, StorageBalanceBounds};
+use super::{SponsorPolicy, SponsorPool, Sponsorship, StorageBalance, StorageBalanceBounds};
 use near_sdk::{ext_contract, AccountId, NearToken};
 
 /// NEAR uses storage staking which means that a contract account must have
@@ -50,3 +50,50 @@ pub trait Nep145 {
     /// contract. See [`StorageBalanceBounds`] for more details.
     fn storage_balance_bounds(&self) -> StorageBalanceBounds;
 }
+
+/// Sponsored storage: a sponsor deposits into a pool that pays the storage
+/// costs of other accounts when their own balance runs out.
+#[ext_contract(ext_nep145_sponsorship)]
+pub trait Nep145Sponsorship {
+    /// Payable method that deposits the attached NEAR into the pool of the
+    /// predecessor, creating it if it does not exist. The policy and
+    /// per-account cap are updated if provided.
+    ///
+    /// Returns the updated pool.
+    fn storage_sponsor_deposit(
+        &mut self,
+        policy: Option<SponsorPolicy>,
+        account_cap: Option<NearToken>,
+    ) -> SponsorPool;
+
+    /// Withdraw the specified amount (default: all) of available NEAR from
+    /// the pool of the predecessor. Requires exactly 1 yoctoNEAR attached.
+    ///
+    /// Returns the updated pool.
+    fn storage_sponsor_withdraw(&mut self, amount: Option<NearToken>) -> SponsorPool;
+
+    /// Sets the policy and per-account cap of the pool of the predecessor.
+    /// Requires exactly 1 yoctoNEAR attached.
+    ///
+    /// Returns the updated pool.
+    fn storage_sponsor_configure(
+        &mut self,
+        policy: SponsorPolicy,
+        account_cap: Option<NearToken>,
+    ) -> SponsorPool;
+
+    /// Sponsors the given account from the pool of the predecessor.
+    /// Requires exactly 1 yoctoNEAR attached.
+    fn storage_sponsor_add(&mut self, account_id: AccountId);
+
+    /// Joins the open pool of the given sponsor as the predecessor.
+    /// Requires exactly 1 yoctoNEAR attached.
+    fn storage_sponsor_join(&mut self, sponsor_id: AccountId);
+
+    /// Returns the pool of the given sponsor, or `None` if it does not exist.
+    fn storage_sponsor_pool(&self, sponsor_id: AccountId) -> Option<SponsorPool>;
+
+    /// Returns the sponsorship of the given account, or `None` if it is not
+    /// sponsored.
+    fn storage_sponsorship_of(&self, account_id: AccountId) -> Option<Sponsorship>;
+}
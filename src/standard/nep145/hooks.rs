@@ -1,16 +1,24 @@
 //! Hooks to integrate NEP-145 with other components.
 
+use std::{borrow::Cow, cmp::Ordering, marker::PhantomData};
+
 use near_sdk::{env, AccountIdRef};
 
 use crate::{
     hook::Hook,
     standard::{
-        nep141::{Nep141Burn, Nep141Mint, Nep141Transfer},
-        nep171::action::{Nep171Burn, Nep171Mint, Nep171Transfer},
+        nep141::{Nep141Burn, Nep141ControllerInternal, Nep141Mint, Nep141Transfer},
+        nep171::{
+            action::{Nep171Burn, Nep171Mint, Nep171Transfer},
+            Nep171ControllerInternal,
+        },
+        nep178::action::{Nep178Approve, Nep178Revoke, Nep178RevokeAll},
     },
 };
 
-use super::Nep145Controller;
+use super::{
+    Nep145Controller, PANIC_MESSAGE_STORAGE_CREDIT_OVERFLOW, PANIC_MESSAGE_STORAGE_FEE_OVERFLOW,
+};
 
 fn require_registration(contract: &impl Nep145Controller, account_id: &AccountIdRef) {
     contract
@@ -85,3 +93,311 @@ impl<C: Nep145Controller> Hook<C, Nep171Burn<'_>> for Nep171StorageAccountingHoo
         f(contract)
     }
 }
+
+/// NEP-178 support for NEP-145.
+///
+/// The owner of the token pays for the storage of its approvals, and is
+/// refunded when they are revoked. Approvals cleared when a token is
+/// transferred or burned are accounted for by the NEP-171 operation instead.
+pub struct Nep178StorageAccountingHook;
+
+impl<C: Nep145Controller> Hook<C, Nep178Approve<'_>> for Nep178StorageAccountingHook {
+    fn hook<R>(contract: &mut C, action: &Nep178Approve<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        apply_storage_accounting_hook(contract, &action.current_owner_id, f)
+    }
+}
+
+impl<C: Nep145Controller> Hook<C, Nep178Revoke<'_>> for Nep178StorageAccountingHook {
+    fn hook<R>(contract: &mut C, action: &Nep178Revoke<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        apply_storage_accounting_hook(contract, &action.current_owner_id, f)
+    }
+}
+
+impl<C: Nep145Controller> Hook<C, Nep178RevokeAll<'_>> for Nep178StorageAccountingHook {
+    fn hook<R>(contract: &mut C, action: &Nep178RevokeAll<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        apply_storage_accounting_hook(contract, &action.current_owner_id, f)
+    }
+}
+
+/// Number of bytes NEAR charges for every storage record, in addition to the
+/// length of its key and value.
+const STORAGE_RECORD_OVERHEAD: u64 = 40;
+
+fn record_footprint(key: &[u8]) -> u64 {
+    env::storage_read(key).map_or(0, |value| {
+        STORAGE_RECORD_OVERHEAD + key.len() as u64 + value.len() as u64
+    })
+}
+
+fn settle_storage_delta<C: Nep145Controller>(
+    contract: &mut C,
+    account_id: &AccountIdRef,
+    delta: i64,
+) {
+    let byte_cost = env::storage_byte_cost();
+
+    let bytes = u128::from(delta.unsigned_abs());
+
+    let result = match delta.cmp(&0) {
+        Ordering::Greater => {
+            let fee = byte_cost
+                .checked_mul(bytes)
+                .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_FEE_OVERFLOW));
+            contract
+                .lock_storage_with_sponsor(account_id, fee)
+                .map_err(|e| e.to_string())
+        }
+        Ordering::Less => {
+            let credit = byte_cost
+                .checked_mul(bytes)
+                .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_CREDIT_OVERFLOW));
+            contract
+                .unlock_storage_with_sponsor(account_id, credit)
+                .map_err(|e| e.to_string())
+        }
+        Ordering::Equal => Ok(()),
+    };
+
+    result.unwrap_or_else(|e| env::panic_str(&format!("Storage accounting error: {e}")));
+}
+
+/// Describes which accounts pay for the storage touched by an operation.
+///
+/// Each record (the value stored at a key, e.g. a [`Slot`](crate::slot::Slot))
+/// may be attributed to a payer. The change in size of an attributed record
+/// is charged to (or credited to) its payer, and the rest of the storage
+/// delta of the operation is settled with the remainder payer. A payer of
+/// `None` means that the contract absorbs the cost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageAttribution<'a> {
+    /// Attributed records and their payers.
+    pub records: Vec<(Vec<u8>, Option<Cow<'a, AccountIdRef>>)>,
+    /// Payer of the storage delta not covered by `records`.
+    pub remainder: Option<Cow<'a, AccountIdRef>>,
+}
+
+impl<'a> StorageAttribution<'a> {
+    /// Charges the entire storage delta to `remainder`.
+    pub fn new(remainder: Option<impl Into<Cow<'a, AccountIdRef>>>) -> Self {
+        Self {
+            records: vec![],
+            remainder: remainder.map(Into::into),
+        }
+    }
+
+    /// Attributes the record stored at `key` to `payer`.
+    #[must_use]
+    pub fn record(
+        mut self,
+        key: impl Into<Vec<u8>>,
+        payer: Option<impl Into<Cow<'a, AccountIdRef>>>,
+    ) -> Self {
+        self.records.push((key.into(), payer.map(Into::into)));
+        self
+    }
+
+    /// Runs `f`, then settles the storage delta with the payers.
+    ///
+    /// # Panics
+    ///
+    /// - If a payer is not registered.
+    /// - If a payer has insufficient storage balance.
+    pub fn apply<C: Nep145Controller, R>(self, contract: &mut C, f: impl FnOnce(&mut C) -> R) -> R {
+        let payers = self
+            .records
+            .iter()
+            .map(|(_, payer)| payer)
+            .chain(std::iter::once(&self.remainder))
+            .flatten();
+
+        for payer in payers {
+            require_registration(contract, payer);
+        }
+
+        let storage_usage_start = env::storage_usage();
+        let footprints_start = self
+            .records
+            .iter()
+            .map(|(key, _)| record_footprint(key))
+            .collect::<Vec<_>>();
+
+        let r = f(contract);
+
+        #[allow(clippy::cast_possible_wrap)]
+        let mut remainder_delta = env::storage_usage() as i64 - storage_usage_start as i64;
+        let mut deltas: Vec<(&AccountIdRef, i64)> = vec![];
+
+        for ((key, payer), start) in self.records.iter().zip(footprints_start) {
+            #[allow(clippy::cast_possible_wrap)]
+            let delta = record_footprint(key) as i64 - start as i64;
+            remainder_delta -= delta;
+
+            let Some(payer) = payer else {
+                continue;
+            };
+
+            match deltas
+                .iter_mut()
+                .find(|(account_id, _)| *account_id == &**payer)
+            {
+                Some((_, total)) => *total += delta,
+                None => deltas.push((payer, delta)),
+            }
+        }
+
+        for (account_id, delta) in deltas {
+            settle_storage_delta(contract, account_id, delta);
+        }
+
+        if let Some(remainder) = &self.remainder {
+            settle_storage_delta(contract, remainder, remainder_delta);
+        }
+
+        r
+    }
+}
+
+/// Selects a storage attribution for an action.
+pub trait StorageAttributionPolicy<C, A> {
+    /// The attribution of the storage touched by `action`.
+    fn attribution<'a>(contract: &C, action: &'a A) -> StorageAttribution<'a>;
+}
+
+/// Performs storage accounting according to the attribution policy `P`.
+pub struct AttributedStorageAccountingHook<P>(PhantomData<P>);
+
+impl<C: Nep145Controller, A, P: StorageAttributionPolicy<C, A>> Hook<C, A>
+    for AttributedStorageAccountingHook<P>
+{
+    fn hook<R>(contract: &mut C, action: &A, f: impl FnOnce(&mut C) -> R) -> R {
+        P::attribution(contract, action).apply(contract, f)
+    }
+}
+
+/// A party to a transfer that may pay for storage.
+pub trait StorageParty {
+    /// Selects the paying account from the parties of a transfer.
+    fn select<'a>(
+        sender_id: &'a AccountIdRef,
+        receiver_id: &'a AccountIdRef,
+    ) -> Option<&'a AccountIdRef>;
+}
+
+/// The sender of a transfer pays.
+pub struct Sender;
+
+impl StorageParty for Sender {
+    fn select<'a>(sender_id: &'a AccountIdRef, _: &'a AccountIdRef) -> Option<&'a AccountIdRef> {
+        Some(sender_id)
+    }
+}
+
+/// The receiver of a transfer pays.
+pub struct Receiver;
+
+impl StorageParty for Receiver {
+    fn select<'a>(_: &'a AccountIdRef, receiver_id: &'a AccountIdRef) -> Option<&'a AccountIdRef> {
+        Some(receiver_id)
+    }
+}
+
+/// The contract pays.
+pub struct ContractPays;
+
+impl StorageParty for ContractPays {
+    fn select<'a>(_: &'a AccountIdRef, _: &'a AccountIdRef) -> Option<&'a AccountIdRef> {
+        None
+    }
+}
+
+/// NEP-141 storage attribution policy, for use with
+/// [`AttributedStorageAccountingHook`].
+///
+/// On transfer, the balance record of the receiver is charged to `Record`,
+/// and the rest of the storage delta to `Rest`. Mints are attributed the same
+/// way, with the receiver standing in for the sender. Burns are not accounted
+/// for, like [`Nep141StorageAccountingHook`].
+pub struct Nep141StorageAttribution<Record, Rest = Receiver>(PhantomData<(Record, Rest)>);
+
+impl<C: Nep141ControllerInternal, Record: StorageParty, Rest: StorageParty>
+    StorageAttributionPolicy<C, Nep141Mint<'_>> for Nep141StorageAttribution<Record, Rest>
+{
+    fn attribution<'a>(_contract: &C, action: &'a Nep141Mint<'_>) -> StorageAttribution<'a> {
+        let receiver_id = &*action.receiver_id;
+
+        StorageAttribution::new(Rest::select(receiver_id, receiver_id)).record(
+            C::slot_account(receiver_id).key,
+            Record::select(receiver_id, receiver_id),
+        )
+    }
+}
+
+impl<C: Nep141ControllerInternal, Record: StorageParty, Rest: StorageParty>
+    StorageAttributionPolicy<C, Nep141Transfer<'_>> for Nep141StorageAttribution<Record, Rest>
+{
+    fn attribution<'a>(_contract: &C, action: &'a Nep141Transfer<'_>) -> StorageAttribution<'a> {
+        let (sender_id, receiver_id) = (&*action.sender_id, &*action.receiver_id);
+
+        StorageAttribution::new(Rest::select(sender_id, receiver_id)).record(
+            C::slot_account(receiver_id).key,
+            Record::select(sender_id, receiver_id),
+        )
+    }
+}
+
+impl<C: Nep141ControllerInternal, Record, Rest> StorageAttributionPolicy<C, Nep141Burn<'_>>
+    for Nep141StorageAttribution<Record, Rest>
+{
+    fn attribution<'a>(_contract: &C, _action: &'a Nep141Burn<'_>) -> StorageAttribution<'a> {
+        StorageAttribution::new(None::<&AccountIdRef>)
+    }
+}
+
+/// NEP-171 storage attribution policy, for use with
+/// [`AttributedStorageAccountingHook`].
+///
+/// On transfer, the ownership record of the token is charged to `Record`,
+/// and the rest of the storage delta (e.g. enumeration records of the
+/// receiver) to `Rest`. Mints are attributed the same way, with the receiver
+/// standing in for the sender. Burns are not accounted for, like
+/// [`Nep171StorageAccountingHook`].
+pub struct Nep171StorageAttribution<Record, Rest = Receiver>(PhantomData<(Record, Rest)>);
+
+impl<C: Nep171ControllerInternal, Record: StorageParty, Rest: StorageParty>
+    StorageAttributionPolicy<C, Nep171Mint<'_>> for Nep171StorageAttribution<Record, Rest>
+{
+    fn attribution<'a>(_contract: &C, action: &'a Nep171Mint<'_>) -> StorageAttribution<'a> {
+        let receiver_id = &*action.receiver_id;
+
+        action.token_ids.iter().fold(
+            StorageAttribution::new(Rest::select(receiver_id, receiver_id)),
+            |attribution, token_id| {
+                attribution.record(
+                    C::slot_token_owner(token_id).key,
+                    Record::select(receiver_id, receiver_id),
+                )
+            },
+        )
+    }
+}
+
+impl<C: Nep171ControllerInternal, Record: StorageParty, Rest: StorageParty>
+    StorageAttributionPolicy<C, Nep171Transfer<'_>> for Nep171StorageAttribution<Record, Rest>
+{
+    fn attribution<'a>(_contract: &C, action: &'a Nep171Transfer<'_>) -> StorageAttribution<'a> {
+        let (sender_id, receiver_id) = (&*action.sender_id, &*action.receiver_id);
+
+        StorageAttribution::new(Rest::select(sender_id, receiver_id)).record(
+            C::slot_token_owner(&action.token_id).key,
+            Record::select(sender_id, receiver_id),
+        )
+    }
+}
+
+impl<C: Nep171ControllerInternal, Record, Rest> StorageAttributionPolicy<C, Nep171Burn<'_>>
+    for Nep171StorageAttribution<Record, Rest>
+{
+    fn attribution<'a>(_contract: &C, _action: &'a Nep171Burn<'_>) -> StorageAttribution<'a> {
+        StorageAttribution::new(None::<&AccountIdRef>)
+    }
+}
@@ -1,14 +1,46 @@
 //! NEP-145 Storage Management
 //! <https://github.com/near/NEPs/blob/master/neps/nep-0145.md>
+//!
+//! # Sponsorship
+//!
+//! A sponsor (another account, or the contract itself) may deposit into a
+//! [`SponsorPool`] to pay for the storage of other accounts. A sponsored
+//! account has at most one sponsor, assigned either by the sponsor or, if the
+//! pool is [`SponsorPolicy::Open`], by the account itself. When a sponsored
+//! account runs out of available balance during
+//! [`Nep145Controller::storage_accounting`], the shortfall is drawn from the
+//! pool, up to the per-account cap of the pool. Released storage is credited
+//! back to the sponsor first, then to the account.
+//!
+//! The storage used by the pool itself is paid from the first deposit, and
+//! the storage used by the records of a sponsored account (its sponsorship,
+//! and its registration, if it was not registered) is paid from the pool and
+//! released back to the pool when the account unregisters.
+//!
+//! # Events
+//!
+//! The default implementation of [`Nep145Controller`] emits
+//! [`StorageEvent`]s (standard `x-storage`) whenever an account is
+//! registered or unregistered, or its storage balance is deposited,
+//! withdrawn, locked, or unlocked, and whenever balance is deposited to or
+//! withdrawn from a sponsor pool.
 
 use std::{borrow::Cow, cmp::Ordering};
 
-use near_sdk::{borsh::BorshSerialize, env, near, AccountIdRef, BorshStorageKey, NearToken};
+use near_sdk::{
+    borsh::BorshSerialize, env, near, require, AccountId, AccountIdRef, BorshStorageKey, NearToken,
+    Promise,
+};
 
-use crate::{hook::Hook, slot::Slot, DefaultStorageKey};
+use crate::{
+    hook::Hook, slot::Slot, standard::nep297::Event, utils::apply_storage_fee_and_refund,
+    DefaultStorageKey,
+};
 
 pub mod error;
 use error::*;
+pub mod event;
+use event::*;
 mod ext;
 pub use ext::*;
 pub mod hooks;
@@ -74,11 +106,64 @@ impl Default for StorageBalanceBounds {
     }
 }
 
+/// Which accounts a sponsor pool may pay for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub enum SponsorPolicy {
+    /// Only accounts designated by the sponsor.
+    #[default]
+    Designated,
+    /// Any account that joins the pool.
+    Open,
+}
+
+/// Storage balance deposited by a sponsor to pay for the storage of other
+/// accounts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct SponsorPool {
+    /// Balance not currently used by sponsored accounts.
+    pub available: NearToken,
+    /// Balance currently used by sponsored accounts.
+    pub locked: NearToken,
+    /// Which accounts the pool may pay for.
+    pub policy: SponsorPolicy,
+    /// Maximum amount of balance each sponsored account may use, if any.
+    pub account_cap: Option<NearToken>,
+}
+
+impl Default for SponsorPool {
+    fn default() -> Self {
+        Self {
+            available: NearToken::from_yoctonear(0),
+            locked: NearToken::from_yoctonear(0),
+            policy: SponsorPolicy::default(),
+            account_cap: None,
+        }
+    }
+}
+
+/// The sponsorship of an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct Sponsorship {
+    /// The sponsor paying for the account.
+    pub sponsor_id: AccountId,
+    /// Balance of the sponsor currently used by the account.
+    pub locked: NearToken,
+    /// Balance of the sponsor used by the records of the sponsorship itself,
+    /// released when the account unregisters.
+    pub registration: NearToken,
+}
+
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
 enum StorageKey<'a> {
     BalanceBounds,
     Account(&'a AccountIdRef),
+    SponsorPool(&'a AccountIdRef),
+    Sponsorship(&'a AccountIdRef),
+    ForceUnregisterBeneficiary,
 }
 
 /// Describes a force unregister action.
@@ -115,6 +200,76 @@ pub trait Nep145ControllerInternal {
     fn slot_account(account_id: &AccountIdRef) -> Slot<StorageBalance> {
         Slot::new(StorageKey::Account(account_id))
     }
+
+    /// Storage slot for the pool of a sponsor.
+    #[must_use]
+    fn slot_sponsor_pool(sponsor_id: &AccountIdRef) -> Slot<SponsorPool> {
+        Self::root().field(StorageKey::SponsorPool(sponsor_id))
+    }
+
+    /// Storage slot for the sponsorship of an account.
+    #[must_use]
+    fn slot_sponsorship(account_id: &AccountIdRef) -> Slot<Sponsorship> {
+        Self::root().field(StorageKey::Sponsorship(account_id))
+    }
+
+    /// Storage slot for the account that receives the assets of forcibly
+    /// unregistered accounts.
+    #[must_use]
+    fn slot_force_unregister_beneficiary() -> Slot<AccountId> {
+        Self::root().field(StorageKey::ForceUnregisterBeneficiary)
+    }
+}
+
+/// Returns all balance used by a removed sponsorship to the pool of its
+/// sponsor.
+fn release_to_sponsor_pool<C: Nep145ControllerInternal + ?Sized>(
+    account_id: &AccountIdRef,
+    sponsorship: &Sponsorship,
+) {
+    let amount = sponsorship
+        .locked
+        .checked_add(sponsorship.registration)
+        .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_CREDIT_OVERFLOW));
+
+    let mut pool_slot = C::slot_sponsor_pool(&sponsorship.sponsor_id);
+    if let Some(mut pool) = pool_slot.read() {
+        pool.locked = pool.locked.saturating_sub(amount);
+        pool.available = pool
+            .available
+            .checked_add(amount)
+            .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_CREDIT_OVERFLOW));
+        pool_slot.write(&pool);
+    }
+
+    if !amount.is_zero() {
+        StorageEvent::Unlock {
+            account_id: account_id.to_owned(),
+            amount,
+            bytes: StorageEvent::bytes_for(amount),
+            payer_id: Some(sponsorship.sponsor_id.clone()),
+        }
+        .emit();
+    }
+}
+
+/// Emits a deposit to the pool of a sponsor, attributed to the predecessor.
+fn emit_sponsor_deposit(sponsor_id: &AccountIdRef, amount: NearToken) {
+    StorageEvent::SponsorDeposit {
+        sponsor_id: sponsor_id.to_owned(),
+        amount,
+        payer_id: StorageEvent::payer_if_other(sponsor_id, &env::predecessor_account_id()),
+    }
+    .emit();
+}
+
+/// Storage fee for the bytes written since `storage_usage_start`.
+fn storage_fee_since(storage_usage_start: u64) -> NearToken {
+    let storage_consumed = env::storage_usage().saturating_sub(storage_usage_start);
+
+    env::storage_byte_cost()
+        .checked_mul(u128::from(storage_consumed))
+        .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_FEE_OVERFLOW))
 }
 
 /// NEP-145 Storage Management controller interface. These functions are not directly
@@ -213,8 +368,164 @@ pub trait Nep145Controller {
     /// Sets the storage balance bounds for the contract.
     fn set_storage_balance_bounds(&mut self, bounds: &StorageBalanceBounds);
 
+    /// Returns the account that receives the assets of forcibly unregistered
+    /// accounts, if any. Used by transfer-on-force-unregister hooks.
+    fn get_force_unregister_beneficiary(&self) -> Option<AccountId>;
+
+    /// Sets (or clears, with `None`) the account that receives the assets of
+    /// forcibly unregistered accounts.
+    fn set_force_unregister_beneficiary(&mut self, beneficiary_id: Option<&AccountIdRef>);
+
+    /// Registers an account with the minimum storage balance, unless it is
+    /// already registered. The registration is paid from `deposit` first, and
+    /// then from the available storage balance of `payer_id`.
+    ///
+    /// Returns the amount of `deposit` used.
+    ///
+    /// # Errors
+    ///
+    /// - If `deposit` is insufficient and the payer is not registered.
+    /// - If `deposit` and the balance of the payer are insufficient.
+    fn register_storage_account_paid_by(
+        &mut self,
+        account_id: &AccountIdRef,
+        payer_id: &AccountIdRef,
+        deposit: NearToken,
+    ) -> Result<NearToken, StorageRegisterError> {
+        if self.get_storage_balance(account_id).is_ok() {
+            return Ok(NearToken::from_yoctonear(0));
+        }
+
+        let min = self.get_storage_balance_bounds().min;
+        let from_deposit = min.min(deposit);
+        let from_payer = min.saturating_sub(from_deposit);
+
+        if !from_payer.is_zero() {
+            self.withdraw_from_storage_account(payer_id, from_payer)?;
+        }
+
+        self.deposit_to_storage_account(account_id, min)?;
+
+        Ok(from_deposit)
+    }
+
+    /// Returns the pool of a sponsor, if it exists.
+    fn get_sponsor_pool(&self, sponsor_id: &AccountIdRef) -> Option<SponsorPool>;
+
+    /// Returns the sponsorship of an account, if it is sponsored.
+    fn get_sponsorship(&self, account_id: &AccountIdRef) -> Option<Sponsorship>;
+
+    /// Deposits into the pool of a sponsor, creating it with the default
+    /// policy if it does not exist. The storage used by a new pool is paid
+    /// from the deposit.
+    ///
+    /// # Errors
+    ///
+    /// - If the pool does not exist and the deposit does not cover its
+    ///   storage.
+    fn deposit_to_sponsor_pool(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<SponsorPool, InsufficientBalanceError>;
+
+    /// Withdraws available balance from the pool of a sponsor.
+    ///
+    /// # Errors
+    ///
+    /// - If the pool does not exist.
+    /// - If the pool has insufficient available balance.
+    fn withdraw_from_sponsor_pool(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<SponsorPool, SponsorWithdrawError>;
+
+    /// Sets the policy and per-account cap of the pool of a sponsor.
+    ///
+    /// # Errors
+    ///
+    /// - If the pool does not exist.
+    fn set_sponsor_policy(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        policy: SponsorPolicy,
+        account_cap: Option<NearToken>,
+    ) -> Result<SponsorPool, SponsorPoolNotFoundError>;
+
+    /// Assigns a sponsor to an account, regardless of the policy of the pool.
+    /// Registers the account with an empty balance if it is not registered.
+    /// The storage used by the new records is paid from the pool.
+    ///
+    /// # Errors
+    ///
+    /// - If the pool does not exist.
+    /// - If the account is already sponsored by a different sponsor.
+    /// - If the pool has insufficient available balance to pay for the new
+    ///   records.
+    fn sponsor_account(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        account_id: &AccountIdRef,
+    ) -> Result<(), SponsorAccountError>;
+
+    /// Assigns a sponsor to an account at the request of the account. The
+    /// pool must be [`SponsorPolicy::Open`].
+    ///
+    /// # Errors
+    ///
+    /// - If the pool does not exist or is not open.
+    /// - If the account is already sponsored by a different sponsor.
+    /// - If the pool has insufficient available balance to pay for the new
+    ///   records.
+    fn join_sponsor_pool(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        account_id: &AccountIdRef,
+    ) -> Result<(), SponsorAccountError> {
+        let pool = self
+            .get_sponsor_pool(sponsor_id)
+            .ok_or_else(|| SponsorPoolNotFoundError(sponsor_id.to_owned()))?;
+
+        if pool.policy != SponsorPolicy::Open {
+            return Err(SponsorPoolNotOpenError(sponsor_id.to_owned()).into());
+        }
+
+        self.sponsor_account(sponsor_id, account_id)
+    }
+
+    /// Locks storage balance for an account like
+    /// [`lock_storage`](Nep145Controller::lock_storage), drawing any shortfall
+    /// from the sponsor of the account.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not registered.
+    /// - If the account and its sponsor have insufficient balance.
+    fn lock_storage_with_sponsor(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<(), StorageLockError>;
+
+    /// Unlocks storage balance for an account like
+    /// [`unlock_storage`](Nep145Controller::unlock_storage), crediting the
+    /// sponsor of the account first.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not registered.
+    /// - If more balance is unlocked than was locked.
+    fn unlock_storage_with_sponsor(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<(), StorageUnlockError>;
+
     /// Convenience method for performing storage accounting, to be used after
     /// storage writes that are to be debited from the account's balance.
+    /// Draws from the sponsor of the account, if any, when the balance of the
+    /// account is insufficient.
     ///
     /// # Errors
     ///
@@ -234,7 +545,7 @@ pub trait Nep145Controller {
                     .checked_mul(u128::from(storage_consumed))
                     .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_FEE_OVERFLOW));
 
-                Nep145Controller::lock_storage(self, account_id, storage_fee)?;
+                Nep145Controller::lock_storage_with_sponsor(self, account_id, storage_fee)?;
             }
             Ordering::Less => {
                 let storage_released = storage_usage_start - storage_usage_end;
@@ -242,7 +553,7 @@ pub trait Nep145Controller {
                     .checked_mul(u128::from(storage_released))
                     .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_CREDIT_OVERFLOW));
 
-                Nep145Controller::unlock_storage(self, account_id, storage_credit)?;
+                Nep145Controller::unlock_storage_with_sponsor(self, account_id, storage_credit)?;
             }
         };
 
@@ -284,6 +595,14 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
 
         account_slot.write(&balance);
 
+        StorageEvent::Lock {
+            account_id: account_id.to_owned(),
+            amount,
+            bytes: StorageEvent::bytes_for(amount),
+            payer_id: None,
+        }
+        .emit();
+
         Ok(balance)
     }
 
@@ -313,6 +632,14 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
 
         account_slot.write(&balance);
 
+        StorageEvent::Unlock {
+            account_id: account_id.to_owned(),
+            amount,
+            bytes: StorageEvent::bytes_for(amount),
+            payer_id: None,
+        }
+        .emit();
+
         Ok(balance)
     }
 
@@ -323,7 +650,9 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
     ) -> Result<StorageBalance, StorageDepositError> {
         let mut account_slot = Self::slot_account(account_id);
 
-        let mut balance = account_slot.read().unwrap_or_default();
+        let existing = account_slot.read();
+        let registering = existing.is_none();
+        let mut balance = existing.unwrap_or_default();
 
         balance.total = {
             let new_total = balance
@@ -361,6 +690,20 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
 
         account_slot.write(&balance);
 
+        if registering {
+            StorageEvent::Register {
+                account_id: account_id.to_owned(),
+            }
+            .emit();
+        }
+
+        StorageEvent::Deposit {
+            account_id: account_id.to_owned(),
+            amount,
+            payer_id: StorageEvent::payer_if_other(account_id, &env::predecessor_account_id()),
+        }
+        .emit();
+
         Ok(balance)
     }
 
@@ -400,6 +743,12 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
 
         account_slot.write(&balance);
 
+        StorageEvent::Withdraw {
+            account_id: account_id.to_owned(),
+            amount,
+        }
+        .emit();
+
         Ok(balance)
     }
 
@@ -425,8 +774,30 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
             _ => {}
         }
 
+        let mut sponsorship_slot = Self::slot_sponsorship(account_id);
+
+        if let Some(sponsorship) = sponsorship_slot.read() {
+            if !sponsorship.locked.is_zero() {
+                return Err(UnregisterWithLockedBalanceError {
+                    account_id: account_id.to_owned(),
+                    locked_balance: sponsorship.locked,
+                }
+                .into());
+            }
+
+            sponsorship_slot.remove();
+            release_to_sponsor_pool::<Self>(account_id, &sponsorship);
+        }
+
         account_slot.remove();
 
+        StorageEvent::Unregister {
+            account_id: account_id.to_owned(),
+            amount: balance.total,
+            force: false,
+        }
+        .emit();
+
         Ok(balance.total)
     }
 
@@ -447,8 +818,19 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
 
         Self::ForceUnregisterHook::hook(self, &action, |_| {
             account_slot.remove();
+
+            if let Some(sponsorship) = Self::slot_sponsorship(account_id).take() {
+                release_to_sponsor_pool::<Self>(account_id, &sponsorship);
+            }
         });
 
+        StorageEvent::Unregister {
+            account_id: account_id.to_owned(),
+            amount: action.balance.available,
+            force: true,
+        }
+        .emit();
+
         Ok(action.balance.available)
     }
 
@@ -459,4 +841,366 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
     fn set_storage_balance_bounds(&mut self, bounds: &StorageBalanceBounds) {
         Self::slot_balance_bounds().write(bounds);
     }
+
+    fn get_force_unregister_beneficiary(&self) -> Option<AccountId> {
+        Self::slot_force_unregister_beneficiary().read()
+    }
+
+    fn set_force_unregister_beneficiary(&mut self, beneficiary_id: Option<&AccountIdRef>) {
+        let mut slot = Self::slot_force_unregister_beneficiary();
+        if let Some(beneficiary_id) = beneficiary_id {
+            slot.write_deref(beneficiary_id);
+        } else {
+            slot.remove();
+        }
+    }
+
+    fn get_sponsor_pool(&self, sponsor_id: &AccountIdRef) -> Option<SponsorPool> {
+        Self::slot_sponsor_pool(sponsor_id).read()
+    }
+
+    fn get_sponsorship(&self, account_id: &AccountIdRef) -> Option<Sponsorship> {
+        Self::slot_sponsorship(account_id).read()
+    }
+
+    fn deposit_to_sponsor_pool(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<SponsorPool, InsufficientBalanceError> {
+        let mut pool_slot = Self::slot_sponsor_pool(sponsor_id);
+
+        let Some(mut pool) = pool_slot.read() else {
+            let storage_usage_start = env::storage_usage();
+
+            pool_slot.write(&SponsorPool {
+                available: amount,
+                ..SponsorPool::default()
+            });
+
+            let storage_fee = storage_fee_since(storage_usage_start);
+
+            let Some(available) = amount
+                .checked_sub(storage_fee)
+                .filter(|available| !available.is_zero())
+            else {
+                pool_slot.remove();
+
+                return Err(InsufficientBalanceError {
+                    account_id: sponsor_id.to_owned(),
+                    available: amount,
+                    attempted_to_use: storage_fee,
+                });
+            };
+
+            let pool = SponsorPool {
+                available,
+                ..SponsorPool::default()
+            };
+
+            pool_slot.write(&pool);
+            emit_sponsor_deposit(sponsor_id, amount);
+
+            return Ok(pool);
+        };
+
+        pool.available = pool
+            .available
+            .checked_add(amount)
+            .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_TOTAL_OVERFLOW));
+
+        pool_slot.write(&pool);
+        emit_sponsor_deposit(sponsor_id, amount);
+
+        Ok(pool)
+    }
+
+    fn withdraw_from_sponsor_pool(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<SponsorPool, SponsorWithdrawError> {
+        let mut pool_slot = Self::slot_sponsor_pool(sponsor_id);
+        let mut pool = pool_slot
+            .read()
+            .ok_or_else(|| SponsorPoolNotFoundError(sponsor_id.to_owned()))?;
+
+        pool.available =
+            pool.available
+                .checked_sub(amount)
+                .ok_or_else(|| InsufficientBalanceError {
+                    account_id: sponsor_id.to_owned(),
+                    available: pool.available,
+                    attempted_to_use: amount,
+                })?;
+
+        pool_slot.write(&pool);
+
+        StorageEvent::SponsorWithdraw {
+            sponsor_id: sponsor_id.to_owned(),
+            amount,
+        }
+        .emit();
+
+        Ok(pool)
+    }
+
+    fn set_sponsor_policy(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        policy: SponsorPolicy,
+        account_cap: Option<NearToken>,
+    ) -> Result<SponsorPool, SponsorPoolNotFoundError> {
+        let mut pool_slot = Self::slot_sponsor_pool(sponsor_id);
+        let mut pool = pool_slot
+            .read()
+            .ok_or_else(|| SponsorPoolNotFoundError(sponsor_id.to_owned()))?;
+
+        pool.policy = policy;
+        pool.account_cap = account_cap;
+
+        pool_slot.write(&pool);
+
+        Ok(pool)
+    }
+
+    fn sponsor_account(
+        &mut self,
+        sponsor_id: &AccountIdRef,
+        account_id: &AccountIdRef,
+    ) -> Result<(), SponsorAccountError> {
+        let mut pool_slot = Self::slot_sponsor_pool(sponsor_id);
+        let Some(mut pool) = pool_slot.read() else {
+            return Err(SponsorPoolNotFoundError(sponsor_id.to_owned()).into());
+        };
+
+        let mut sponsorship_slot = Self::slot_sponsorship(account_id);
+
+        match sponsorship_slot.read() {
+            Some(sponsorship) if sponsorship.sponsor_id == sponsor_id => return Ok(()),
+            Some(sponsorship) => {
+                return Err(AlreadySponsoredError {
+                    account_id: account_id.to_owned(),
+                    sponsor_id: sponsorship.sponsor_id,
+                }
+                .into())
+            }
+            None => {}
+        }
+
+        let storage_usage_start = env::storage_usage();
+
+        let mut account_slot = Self::slot_account(account_id);
+        let registering = !account_slot.exists();
+        if registering {
+            account_slot.write(&StorageBalance::default());
+        }
+
+        let mut sponsorship = Sponsorship {
+            sponsor_id: sponsor_id.to_owned(),
+            locked: NearToken::from_yoctonear(0),
+            registration: NearToken::from_yoctonear(0),
+        };
+
+        sponsorship_slot.write(&sponsorship);
+
+        let storage_fee = storage_fee_since(storage_usage_start);
+
+        let Some(available) = pool.available.checked_sub(storage_fee) else {
+            sponsorship_slot.remove();
+            if registering {
+                account_slot.remove();
+            }
+
+            return Err(InsufficientBalanceError {
+                account_id: sponsor_id.to_owned(),
+                available: pool.available,
+                attempted_to_use: storage_fee,
+            }
+            .into());
+        };
+
+        pool.available = available;
+        pool.locked = pool
+            .locked
+            .checked_add(storage_fee)
+            .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_FEE_OVERFLOW));
+        sponsorship.registration = storage_fee;
+
+        pool_slot.write(&pool);
+        sponsorship_slot.write(&sponsorship);
+
+        if registering {
+            StorageEvent::Register {
+                account_id: account_id.to_owned(),
+            }
+            .emit();
+        }
+
+        StorageEvent::Lock {
+            account_id: account_id.to_owned(),
+            amount: storage_fee,
+            bytes: StorageEvent::bytes_for(storage_fee),
+            payer_id: Some(sponsor_id.to_owned()),
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    fn lock_storage_with_sponsor(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<(), StorageLockError> {
+        let mut account_slot = Self::slot_account(account_id);
+        let mut balance = account_slot
+            .read()
+            .ok_or(AccountNotRegisteredError(account_id.to_owned()))?;
+
+        let Some(shortfall) = amount
+            .checked_sub(balance.available)
+            .filter(|shortfall| !shortfall.is_zero())
+        else {
+            Nep145Controller::lock_storage(self, account_id, amount)?;
+            return Ok(());
+        };
+
+        let insufficient = |sponsor_available: NearToken| InsufficientBalanceError {
+            account_id: account_id.to_owned(),
+            available: balance.available.saturating_add(sponsor_available),
+            attempted_to_use: amount,
+        };
+
+        let mut sponsorship_slot = Self::slot_sponsorship(account_id);
+        let Some(mut sponsorship) = sponsorship_slot.read() else {
+            return Err(insufficient(NearToken::from_yoctonear(0)).into());
+        };
+
+        let mut pool_slot = Self::slot_sponsor_pool(&sponsorship.sponsor_id);
+        let Some(mut pool) = pool_slot.read() else {
+            return Err(insufficient(NearToken::from_yoctonear(0)).into());
+        };
+
+        let sponsor_available = pool.account_cap.map_or(pool.available, |cap| {
+            pool.available.min(cap.saturating_sub(sponsorship.locked))
+        });
+
+        if shortfall > sponsor_available {
+            return Err(insufficient(sponsor_available).into());
+        }
+
+        pool.available = pool.available.saturating_sub(shortfall);
+        pool.locked = pool
+            .locked
+            .checked_add(shortfall)
+            .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_FEE_OVERFLOW));
+        sponsorship.locked = sponsorship
+            .locked
+            .checked_add(shortfall)
+            .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_FEE_OVERFLOW));
+
+        let from_account = balance.available;
+        balance.available = NearToken::from_yoctonear(0);
+
+        pool_slot.write(&pool);
+        sponsorship_slot.write(&sponsorship);
+        account_slot.write(&balance);
+
+        if !from_account.is_zero() {
+            StorageEvent::Lock {
+                account_id: account_id.to_owned(),
+                amount: from_account,
+                bytes: StorageEvent::bytes_for(from_account),
+                payer_id: None,
+            }
+            .emit();
+        }
+
+        StorageEvent::Lock {
+            account_id: account_id.to_owned(),
+            amount: shortfall,
+            bytes: StorageEvent::bytes_for(shortfall),
+            payer_id: Some(sponsorship.sponsor_id),
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    fn unlock_storage_with_sponsor(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: NearToken,
+    ) -> Result<(), StorageUnlockError> {
+        let mut remainder = amount;
+
+        let mut sponsorship_slot = Self::slot_sponsorship(account_id);
+        if let Some(mut sponsorship) = sponsorship_slot
+            .read()
+            .filter(|sponsorship| !sponsorship.locked.is_zero())
+        {
+            let credit = sponsorship.locked.min(amount);
+            let mut pool_slot = Self::slot_sponsor_pool(&sponsorship.sponsor_id);
+
+            if let Some(mut pool) = pool_slot.read() {
+                pool.locked = pool.locked.saturating_sub(credit);
+                pool.available = pool
+                    .available
+                    .checked_add(credit)
+                    .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_CREDIT_OVERFLOW));
+                pool_slot.write(&pool);
+            }
+
+            sponsorship.locked = sponsorship.locked.saturating_sub(credit);
+            sponsorship_slot.write(&sponsorship);
+            remainder = remainder.saturating_sub(credit);
+
+            StorageEvent::Unlock {
+                account_id: account_id.to_owned(),
+                amount: credit,
+                bytes: StorageEvent::bytes_for(credit),
+                payer_id: Some(sponsorship.sponsor_id),
+            }
+            .emit();
+        }
+
+        if !remainder.is_zero() {
+            Nep145Controller::unlock_storage(self, account_id, remainder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Registers the receiver of a transfer if it is not registered, paid from
+/// the attached deposit (less one yocto) and then from the storage balance
+/// of the sender. Leftover deposit is refunded to the predecessor, like
+/// [`apply_storage_fee_and_refund`].
+///
+/// Used by external transfer functions of tokens with automatic receiver
+/// registration enabled.
+///
+/// # Panics
+///
+/// - If no deposit is attached.
+/// - If the registration cannot be paid for.
+#[must_use]
+pub fn register_receiver_with_deposit<C: Nep145Controller>(
+    contract: &mut C,
+    sender_id: &AccountIdRef,
+    receiver_id: &AccountIdRef,
+) -> Option<Promise> {
+    require!(
+        env::attached_deposit() >= NearToken::from_yoctonear(1),
+        "Requires attached deposit of at least 1 yoctoNEAR",
+    );
+
+    let deposit = env::attached_deposit().saturating_sub(NearToken::from_yoctonear(1));
+
+    let used = contract
+        .register_storage_account_paid_by(receiver_id, sender_id, deposit)
+        .unwrap_or_else(|e| env::panic_str(&format!("Receiver registration error: {e}")));
+
+    apply_storage_fee_and_refund(env::storage_usage(), used.as_yoctonear() + 1)
 }
@@ -0,0 +1,189 @@
+//! Reusable [`CheckExternalTransfer`] implementations for restricting
+//! transfers of non-fungible tokens, e.g. credentials.
+//!
+//! Restricting checkers ([`Soulbound`], [`TransferableUntilLock`], and
+//! [`Pausable`]) do not check whether the sender is authorized to transfer
+//! the token, so they should be composed with an authorizing checker, such as
+//! [`DefaultCheckExternalTransfer`](super::DefaultCheckExternalTransfer) or
+//! [`TokenApprovals`](crate::standard::nep178::TokenApprovals), using a
+//! tuple:
+//!
+//! ```
+//! use near_sdk::{near, PanicOnDefault};
+//! use near_sdk_contract_tools::{
+//!     nft::{nep171::DefaultCheckExternalTransfer, *},
+//!     standard::nep171::check::*,
+//!     Pause,
+//! };
+//!
+//! #[derive(Nep171, Pause, PanicOnDefault)]
+//! #[nep171(check_external_transfer = "(DefaultCheckExternalTransfer, (TransferableUntilLock, Pausable))")]
+//! #[near(contract_state)]
+//! struct Contract {}
+//!
+//! impl Nep171TransferLock for Contract {
+//!     fn transfer_lock_timestamp(&self, _token_id: &TokenId) -> Option<u64> {
+//!         Some(1_700_000_000_000_000_000)
+//!     }
+//! }
+//! ```
+//!
+//! [`TransferableByIssuer`] authorizes transfers on its own.
+
+use near_sdk::{env, AccountId, AccountIdRef};
+
+pub use crate::pause::hooks::Pausable;
+use crate::pause::Pause;
+
+use super::{
+    action::Nep171Transfer,
+    error::{
+        ContractPausedError, Nep171TransferError, SenderNotIssuerError, TokenDoesNotExistError,
+        TokenIsSoulboundError, TokenReceiverIsCurrentOwnerError, TokenTransferLockedError,
+    },
+    CheckExternalTransfer, Nep171Controller, TokenId,
+};
+
+fn token_owner(
+    contract: &impl Nep171Controller,
+    token_id: &TokenId,
+) -> Result<AccountId, TokenDoesNotExistError> {
+    contract
+        .token_owner(token_id)
+        .ok_or_else(|| TokenDoesNotExistError {
+            token_id: token_id.clone(),
+        })
+}
+
+/// Rejects all external transfers. Tokens can still be minted and burned.
+pub struct Soulbound;
+
+impl<C: Nep171Controller> CheckExternalTransfer<C> for Soulbound {
+    fn check_external_transfer(
+        contract: &C,
+        transfer: &Nep171Transfer,
+    ) -> Result<AccountId, Nep171TransferError> {
+        token_owner(contract, &transfer.token_id)?;
+
+        Err(TokenIsSoulboundError {
+            token_id: transfer.token_id.clone(),
+        }
+        .into())
+    }
+}
+
+/// Determines which accounts may transfer tokens checked by
+/// [`TransferableByIssuer`].
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::{near, AccountIdRef, BorshStorageKey, PanicOnDefault};
+/// use near_sdk_contract_tools::{nft::*, rbac::Rbac, standard::nep171::check::*, Rbac};
+///
+/// #[derive(BorshStorageKey)]
+/// #[near]
+/// enum Role {
+///     Issuer,
+/// }
+///
+/// #[derive(Nep171, Rbac, PanicOnDefault)]
+/// #[nep171(check_external_transfer = "TransferableByIssuer")]
+/// #[rbac(roles = "Role")]
+/// #[near(contract_state)]
+/// struct Credentials {}
+///
+/// impl Nep171Issuer for Credentials {
+///     fn is_issuer(&self, account_id: &AccountIdRef) -> bool {
+///         Self::has_role(&account_id.to_owned(), &Role::Issuer)
+///     }
+/// }
+/// ```
+pub trait Nep171Issuer {
+    /// Returns `true` if the account may transfer any token.
+    fn is_issuer(&self, account_id: &AccountIdRef) -> bool;
+}
+
+/// Only allows transfers by issuers, as determined by [`Nep171Issuer`].
+/// Issuers may transfer any token, regardless of who owns it. Owners who
+/// are not issuers cannot transfer their tokens.
+pub struct TransferableByIssuer;
+
+impl<C: Nep171Controller + Nep171Issuer> CheckExternalTransfer<C> for TransferableByIssuer {
+    fn check_external_transfer(
+        contract: &C,
+        transfer: &Nep171Transfer,
+    ) -> Result<AccountId, Nep171TransferError> {
+        let owner_id = token_owner(contract, &transfer.token_id)?;
+
+        if !contract.is_issuer(&transfer.sender_id) {
+            return Err(SenderNotIssuerError {
+                sender_id: transfer.sender_id.clone().into(),
+                token_id: transfer.token_id.clone(),
+            }
+            .into());
+        }
+
+        if transfer.receiver_id.as_ref() == owner_id {
+            return Err(TokenReceiverIsCurrentOwnerError {
+                owner_id,
+                token_id: transfer.token_id.clone(),
+            }
+            .into());
+        }
+
+        Ok(owner_id)
+    }
+}
+
+/// Determines when tokens checked by [`TransferableUntilLock`] become
+/// non-transferable.
+pub trait Nep171TransferLock {
+    /// Returns the block timestamp (in nanoseconds) from which the token can
+    /// no longer be transferred, or `None` if the token is never locked.
+    fn transfer_lock_timestamp(&self, token_id: &TokenId) -> Option<u64>;
+}
+
+/// Rejects transfers of a token at or after its lock timestamp, as
+/// determined by [`Nep171TransferLock`].
+pub struct TransferableUntilLock;
+
+impl<C: Nep171Controller + Nep171TransferLock> CheckExternalTransfer<C> for TransferableUntilLock {
+    fn check_external_transfer(
+        contract: &C,
+        transfer: &Nep171Transfer,
+    ) -> Result<AccountId, Nep171TransferError> {
+        let owner_id = token_owner(contract, &transfer.token_id)?;
+
+        if let Some(locked_at) = contract.transfer_lock_timestamp(&transfer.token_id) {
+            if env::block_timestamp() >= locked_at {
+                return Err(TokenTransferLockedError {
+                    token_id: transfer.token_id.clone(),
+                    locked_at,
+                }
+                .into());
+            }
+        }
+
+        Ok(owner_id)
+    }
+}
+
+/// Rejects all transfers while the contract is paused.
+impl<C: Nep171Controller + Pause> CheckExternalTransfer<C> for Pausable {
+    fn check_external_transfer(
+        contract: &C,
+        transfer: &Nep171Transfer,
+    ) -> Result<AccountId, Nep171TransferError> {
+        let owner_id = token_owner(contract, &transfer.token_id)?;
+
+        if C::is_paused() {
+            return Err(ContractPausedError {
+                token_id: transfer.token_id.clone(),
+            }
+            .into());
+        }
+
+        Ok(owner_id)
+    }
+}
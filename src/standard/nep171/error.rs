@@ -43,6 +43,51 @@ pub enum Nep171TransferError {
     /// The token could not be transferred because it is no longer owned by the expected owner.
     #[error(transparent)]
     TokenNotOwnedByExpectedOwner(#[from] TokenNotOwnedByExpectedOwnerError),
+    /// The token could not be transferred because it is non-transferable (soulbound).
+    #[error(transparent)]
+    TokenIsSoulbound(#[from] TokenIsSoulboundError),
+    /// The token could not be transferred because only issuers may transfer it.
+    #[error(transparent)]
+    SenderNotIssuer(#[from] SenderNotIssuerError),
+    /// The token could not be transferred because its transfer lock has passed.
+    #[error(transparent)]
+    TokenTransferLocked(#[from] TokenTransferLockedError),
+    /// The token could not be transferred because the contract is paused.
+    #[error(transparent)]
+    ContractPaused(#[from] ContractPausedError),
+}
+
+/// Potential errors encountered when performing a batch of token transfers.
+#[derive(Error, Clone, Debug)]
+pub enum Nep171BatchTransferError {
+    /// One of the transfers is invalid.
+    #[error(transparent)]
+    Transfer(#[from] Nep171TransferError),
+    /// The same token appears more than once in the batch.
+    #[error(transparent)]
+    DuplicateTokenId(#[from] DuplicateTokenIdError),
+    /// The logs of the batch would exceed the log budget of the receipt.
+    #[error(transparent)]
+    LogLengthExceeded(#[from] BatchLogLengthExceededError),
+}
+
+/// Occurs when a batch transfers the same token more than once.
+#[derive(Error, Clone, Debug)]
+#[error("Token `{token_id}` appears more than once in the batch")]
+pub struct DuplicateTokenIdError {
+    /// The duplicated token ID.
+    pub token_id: TokenId,
+}
+
+/// Occurs when the logs emitted by a batch of transfers would exceed the
+/// total log length allowed for a single receipt.
+#[derive(Error, Clone, Debug)]
+#[error("Batch would emit {length} bytes of logs, exceeding the limit of {limit} bytes")]
+pub struct BatchLogLengthExceededError {
+    /// Estimated length of the logs emitted by the batch, in bytes.
+    pub length: usize,
+    /// Maximum total length of logs, in bytes.
+    pub limit: usize,
 }
 
 /// Occurs when trying to create a token ID that already exists.
@@ -102,3 +147,42 @@ pub struct TokenReceiverIsCurrentOwnerError {
     /// The ID of the token in question.
     pub token_id: TokenId,
 }
+
+/// Occurs when attempting to transfer a non-transferable (soulbound) token.
+#[derive(Error, Clone, Debug)]
+#[error("Token `{token_id}` is non-transferable")]
+pub struct TokenIsSoulboundError {
+    /// The ID of the token in question.
+    pub token_id: TokenId,
+}
+
+/// Occurs when an account that is not an issuer attempts to transfer a token
+/// that may only be transferred by issuers.
+#[derive(Error, Clone, Debug)]
+#[error("Sender `{sender_id}` is not an issuer and cannot transfer token `{token_id}`")]
+pub struct SenderNotIssuerError {
+    /// The sender of the transfer.
+    pub sender_id: AccountId,
+    /// The ID of the token in question.
+    pub token_id: TokenId,
+}
+
+/// Occurs when attempting to transfer a token after its transfer lock
+/// timestamp.
+#[derive(Error, Clone, Debug)]
+#[error("Token `{token_id}` has been non-transferable since {locked_at} (nanoseconds)")]
+pub struct TokenTransferLockedError {
+    /// The ID of the token in question.
+    pub token_id: TokenId,
+    /// Block timestamp (in nanoseconds) after which the token became
+    /// non-transferable.
+    pub locked_at: u64,
+}
+
+/// Occurs when attempting to transfer a token while the contract is paused.
+#[derive(Error, Clone, Debug)]
+#[error("Token `{token_id}` cannot be transferred while the contract is paused")]
+pub struct ContractPausedError {
+    /// The ID of the token in question.
+    pub token_id: TokenId,
+}
@@ -4,10 +4,41 @@ use std::borrow::Cow;
 
 use near_sdk::{
     serde::{Deserialize, Serialize},
-    AccountIdRef,
+    serde_json, AccountIdRef,
 };
 use near_sdk_contract_tools_macros::event;
 
+use crate::standard::nep297::{Event, EventLog, ToEventLog};
+
+/// Maximum total length, in bytes, of all logs emitted by a single receipt.
+/// This is the default `max_total_log_length` of the NEAR runtime; a receipt
+/// that logs more fails.
+pub const MAX_TOTAL_LOG_LENGTH: usize = 16_384;
+
+/// Versions of the NEP-171 event standard that a contract may advertise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Nep171EventVersion {
+    /// Mint, transfer, and burn events.
+    V1_0_0,
+    /// Adds metadata update events.
+    V1_1_0,
+    /// Latest version.
+    #[default]
+    V1_2_0,
+}
+
+impl Nep171EventVersion {
+    /// The version string, e.g. `"1.1.0"`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::V1_0_0 => "1.0.0",
+            Self::V1_1_0 => "1.1.0",
+            Self::V1_2_0 => "1.2.0",
+        }
+    }
+}
+
 /// NEP-171 standard events.
 #[event(
     crate = "crate",
@@ -29,6 +60,142 @@ pub enum Nep171Event<'a> {
     ContractMetadataUpdate(Vec<NftContractMetadataUpdateLog<'a>>),
 }
 
+impl<'a> Nep171Event<'a> {
+    /// Returns `true` if the event is part of the given version of the
+    /// standard.
+    #[must_use]
+    pub fn is_supported_by(&self, version: Nep171EventVersion) -> bool {
+        match self {
+            Self::NftMint(_) | Self::NftTransfer(_) | Self::NftBurn(_) => true,
+            Self::NftMetadataUpdate(_) | Self::ContractMetadataUpdate(_) => {
+                version >= Nep171EventVersion::V1_1_0
+            }
+        }
+    }
+
+    /// Emits the event, advertising the given version of the standard. Events
+    /// that are not part of that version are not emitted.
+    pub fn emit_versioned(&self, version: Nep171EventVersion) {
+        if !self.is_supported_by(version) {
+            return;
+        }
+
+        VersionedEvent {
+            event: self,
+            version,
+        }
+        .emit();
+    }
+
+    /// The log line emitted by [`Nep171Event::emit_versioned`].
+    #[must_use]
+    pub fn to_versioned_log_string(&self, version: Nep171EventVersion) -> String {
+        VersionedEvent {
+            event: self,
+            version,
+        }
+        .to_event_string()
+    }
+
+    /// Packs transfer logs into as few `nft_transfer` events as possible,
+    /// such that the log line of each event is at most `max_length` bytes.
+    /// Logs are split between events by token ID if necessary. Order is
+    /// preserved.
+    #[must_use]
+    pub fn split_transfers(
+        logs: Vec<NftTransferLog<'a>>,
+        version: Nep171EventVersion,
+        max_length: usize,
+    ) -> Vec<Self> {
+        fn json_length<T: Serialize>(value: &T) -> usize {
+            serde_json::to_string(value).map_or(0, |s| s.len())
+        }
+
+        // length of the log line of an event with no transfer logs, i.e.
+        // `data: []`
+        let empty_length = Self::NftTransfer(vec![])
+            .to_versioned_log_string(version)
+            .len();
+
+        let mut events = vec![];
+        let mut current: Vec<NftTransferLog<'a>> = vec![];
+        let mut length = empty_length;
+
+        for NftTransferLog {
+            authorized_id,
+            old_owner_id,
+            new_owner_id,
+            token_ids,
+            memo,
+        } in logs
+        {
+            for token_id in token_ids {
+                // adding a string to a non-empty array adds a comma and the
+                // quoted string
+                let token_id_length = json_length(&token_id) + 1;
+
+                if current.last().is_some_and(|last| {
+                    last.old_owner_id == old_owner_id
+                        && last.new_owner_id == new_owner_id
+                        && last.authorized_id == authorized_id
+                        && last.memo == memo
+                }) && length + token_id_length <= max_length
+                {
+                    if let Some(last) = current.last_mut() {
+                        last.token_ids.push(token_id);
+                    }
+                    length += token_id_length;
+                    continue;
+                }
+
+                let log = NftTransferLog {
+                    authorized_id: authorized_id.clone(),
+                    old_owner_id: old_owner_id.clone(),
+                    new_owner_id: new_owner_id.clone(),
+                    token_ids: vec![token_id],
+                    memo: memo.clone(),
+                };
+                let log_length = json_length(&log);
+
+                // adding an object to a non-empty array also adds a comma
+                if !current.is_empty() && length + log_length + 1 > max_length {
+                    events.push(Self::NftTransfer(std::mem::take(&mut current)));
+                    length = empty_length;
+                }
+
+                if !current.is_empty() {
+                    length += 1;
+                }
+                length += log_length;
+                current.push(log);
+            }
+        }
+
+        if !current.is_empty() {
+            events.push(Self::NftTransfer(current));
+        }
+
+        events
+    }
+}
+
+/// An event that advertises a specific version of the standard.
+struct VersionedEvent<'e, 'a> {
+    event: &'e Nep171Event<'a>,
+    version: Nep171EventVersion,
+}
+
+impl<'e, 'a> ToEventLog for VersionedEvent<'e, 'a> {
+    type Data = Nep171Event<'a>;
+
+    fn to_event_log(&self) -> EventLog<&Self::Data> {
+        EventLog {
+            version: self.version.as_str().into(),
+            ..self.event.to_event_log()
+        }
+    }
+}
+
 /// Tokens minted to a single owner.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(crate = "near_sdk::serde")]
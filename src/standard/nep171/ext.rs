@@ -32,6 +32,21 @@ pub trait Nep171 {
     fn nft_token(&self, token_id: TokenId) -> Option<super::Token>;
 }
 
+/// Batch transfers. Not part of NEP-171.
+#[ext_contract(ext_nep171_batch_transfer)]
+pub trait Nep171BatchTransfer {
+    /// Transfer many tokens to the same receiver in a single call. If
+    /// provided, `approval_ids` must have the same length as `token_ids`.
+    /// Either all tokens are transferred, or none are.
+    fn nft_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        approval_ids: Option<Vec<Option<u32>>>,
+        memo: Option<String>,
+    );
+}
+
 /// Original token contract follow-up to [`Nep171::nft_transfer_call`].
 #[ext_contract(ext_nep171_resolver)]
 pub trait Nep171Resolver {
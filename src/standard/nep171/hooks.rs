@@ -1,11 +1,20 @@
 //! Hooks to integrate NEP-171 with other components.
 
+use std::borrow::Cow;
+
 use crate::{
     hook::Hook,
-    standard::{nep145::Nep145ForceUnregister, nep181::Nep181Controller},
+    standard::{
+        nep145::{Nep145Controller, Nep145ForceUnregister},
+        nep181::Nep181Controller,
+    },
 };
 
-use super::{action::Nep171Burn, Nep171Controller};
+use super::{
+    action::{Nep171Burn, Nep171Transfer},
+    event::{Nep171Event, NftTransferLog, MAX_TOTAL_LOG_LENGTH},
+    Nep171Controller, Nep171TransferAuthorization,
+};
 
 /// Hook that burns all NEP-171 tokens held by an account when the account
 /// performs an NEP-145 force unregister.
@@ -37,3 +46,71 @@ where
         f(contract)
     }
 }
+
+/// Hook that transfers all NEP-171 tokens held by an account to the
+/// [force unregister beneficiary](Nep145Controller::get_force_unregister_beneficiary)
+/// when the account performs an NEP-145 force unregister. Runs the regular
+/// transfer hooks, so approvals and enumeration are updated as for any other
+/// transfer, and emits transfer events.
+///
+/// Transfers are not subject to
+/// [`Nep171Controller::CheckExternalTransfer`].
+///
+/// # Panics
+///
+/// - If no beneficiary is set.
+pub struct TransferNep171OnForceUnregisterHook;
+
+impl<C> Hook<C, Nep145ForceUnregister<'_>> for TransferNep171OnForceUnregisterHook
+where
+    C: Nep171Controller + Nep181Controller + Nep145Controller,
+{
+    fn hook<R>(
+        contract: &mut C,
+        action: &Nep145ForceUnregister<'_>,
+        f: impl FnOnce(&mut C) -> R,
+    ) -> R {
+        let beneficiary_id = contract
+            .get_force_unregister_beneficiary()
+            .unwrap_or_else(|| {
+                near_sdk::env::panic_str("No beneficiary set for forced unregistration")
+            });
+
+        let token_ids =
+            contract.with_tokens_for_owner(&action.account_id, |t| t.iter().collect::<Vec<_>>());
+
+        if !token_ids.is_empty() {
+            for token_id in &token_ids {
+                let transfer = Nep171Transfer::new(
+                    token_id.clone(),
+                    action.account_id.clone(),
+                    &*beneficiary_id,
+                    Nep171TransferAuthorization::Owner,
+                )
+                .memo("storage forced unregistration");
+
+                C::TransferHook::hook(contract, &transfer, |contract| {
+                    contract.transfer_unchecked(
+                        std::array::from_ref(&transfer.token_id),
+                        &transfer.receiver_id,
+                    );
+                });
+            }
+
+            let logs = vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: action.account_id.clone(),
+                new_owner_id: Cow::Borrowed(&*beneficiary_id),
+                token_ids: token_ids.into_iter().map(Into::into).collect(),
+                memo: Some("storage forced unregistration".into()),
+            }];
+
+            for event in Nep171Event::split_transfers(logs, C::EVENT_VERSION, MAX_TOTAL_LOG_LENGTH)
+            {
+                event.emit_versioned(C::EVENT_VERSION);
+            }
+        }
+
+        f(contract)
+    }
+}
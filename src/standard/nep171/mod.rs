@@ -46,11 +46,13 @@ use near_sdk::{
     AccountId, AccountIdRef, BorshStorageKey, Gas, NearSchema,
 };
 
-use crate::{hook::Hook, slot::Slot, standard::nep297::Event, DefaultStorageKey};
+use crate::{hook::Hook, slot::Slot, DefaultStorageKey};
 
 pub mod action;
 use action::*;
 
+pub mod check;
+
 pub mod error;
 use error::*;
 pub mod event;
@@ -102,6 +104,14 @@ pub trait Nep171ControllerInternal {
     where
         Self: Sized;
 
+    /// Version of the NEP-171 event standard advertised by emitted events.
+    const EVENT_VERSION: Nep171EventVersion = Nep171EventVersion::V1_2_0;
+
+    /// Upper bound, in bytes, of the logs emitted by the transfer hook for a
+    /// single token, e.g. `x-storage` events. Reserved from the log budget of
+    /// each transfer in a batch.
+    const TRANSFER_HOOK_LOG_LENGTH: usize = 0;
+
     /// Root storage slot.
     #[must_use]
     fn root() -> Slot<()> {
@@ -140,6 +150,14 @@ pub trait Nep171Controller {
     where
         Self: Sized;
 
+    /// Version of the NEP-171 event standard advertised by emitted events.
+    const EVENT_VERSION: Nep171EventVersion = Nep171EventVersion::V1_2_0;
+
+    /// Upper bound, in bytes, of the logs emitted by the transfer hook for a
+    /// single token, e.g. `x-storage` events. Reserved from the log budget of
+    /// each transfer in a batch.
+    const TRANSFER_HOOK_LOG_LENGTH: usize = 0;
+
     /// Transfer a token from `sender_id` to `receiver_id`, as for an external
     /// call to `nft_transfer`. Checks that the transfer is valid using
     /// [`CheckExternalTransfer::check_external_transfer`] before performing
@@ -155,6 +173,28 @@ pub trait Nep171Controller {
     where
         Self: Sized;
 
+    /// Transfer a batch of tokens, as for an external call to
+    /// `nft_batch_transfer`. Every transfer is checked using
+    /// [`CheckExternalTransfer::check_external_transfer`] before any token is
+    /// transferred. Runs the transfer hook once per token, and emits
+    /// `nft_transfer` events grouped by previous owner and receiver.
+    ///
+    /// The events, plus [`Nep171Controller::TRANSFER_HOOK_LOG_LENGTH`] bytes
+    /// per token, must fit in [`MAX_TOTAL_LOG_LENGTH`]; this is checked
+    /// before any token is transferred.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the transfers would fail [`Nep171Controller::external_transfer`].
+    /// - If a token appears more than once in the batch.
+    /// - If the batch would emit more logs than a receipt allows.
+    fn external_batch_transfer(
+        &mut self,
+        transfers: &[Nep171Transfer],
+    ) -> Result<(), Nep171BatchTransferError>
+    where
+        Self: Sized;
+
     /// Performs a token transfer without running [`CheckExternalTransfer::check_external_transfer`].
     /// Does not emit events or run hooks.
     ///
@@ -225,6 +265,21 @@ pub trait CheckExternalTransfer<C> {
     ) -> Result<AccountId, Nep171TransferError>;
 }
 
+/// Composes two checkers: the transfer must pass both, in order. Returns the
+/// owner reported by the second checker. Tuples may be nested to compose more
+/// than two checkers, e.g. `(DefaultCheckExternalTransfer, (Soulbound, Pausable))`.
+impl<C, T: CheckExternalTransfer<C>, U: CheckExternalTransfer<C>> CheckExternalTransfer<C>
+    for (T, U)
+{
+    fn check_external_transfer(
+        contract: &C,
+        transfer: &Nep171Transfer,
+    ) -> Result<AccountId, Nep171TransferError> {
+        T::check_external_transfer(contract, transfer)?;
+        U::check_external_transfer(contract, transfer)
+    }
+}
+
 /// Default external transfer checker. Only allows transfers by the owner of a
 /// token. Does not support approval IDs.
 pub struct DefaultCheckExternalTransfer;
@@ -283,6 +338,10 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
     type CheckExternalTransfer = <Self as Nep171ControllerInternal>::CheckExternalTransfer;
     type LoadTokenMetadata = <Self as Nep171ControllerInternal>::LoadTokenMetadata;
 
+    const EVENT_VERSION: Nep171EventVersion = <Self as Nep171ControllerInternal>::EVENT_VERSION;
+    const TRANSFER_HOOK_LOG_LENGTH: usize =
+        <Self as Nep171ControllerInternal>::TRANSFER_HOOK_LOG_LENGTH;
+
     fn external_transfer(&mut self, transfer: &Nep171Transfer) -> Result<(), Nep171TransferError> {
         match Self::CheckExternalTransfer::check_external_transfer(self, transfer) {
             Ok(current_owner_id) => {
@@ -299,7 +358,7 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
                         token_ids: vec![transfer.token_id.clone().into()],
                         memo: transfer.memo.clone(),
                     }])
-                    .emit();
+                    .emit_versioned(Self::EVENT_VERSION);
                 });
 
                 Ok(())
@@ -308,6 +367,84 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
         }
     }
 
+    fn external_batch_transfer(
+        &mut self,
+        transfers: &[Nep171Transfer],
+    ) -> Result<(), Nep171BatchTransferError> {
+        let mut seen = std::collections::HashSet::with_capacity(transfers.len());
+        let mut current_owner_ids = Vec::with_capacity(transfers.len());
+
+        for transfer in transfers {
+            if !seen.insert(&transfer.token_id) {
+                return Err(DuplicateTokenIdError {
+                    token_id: transfer.token_id.clone(),
+                }
+                .into());
+            }
+
+            current_owner_ids.push(Self::CheckExternalTransfer::check_external_transfer(
+                self, transfer,
+            )?);
+        }
+
+        let mut logs: Vec<NftTransferLog> = vec![];
+
+        for (transfer, current_owner_id) in transfers.iter().zip(current_owner_ids) {
+            let group = logs.iter_mut().find(|log| {
+                *log.old_owner_id == *current_owner_id
+                    && log.new_owner_id == transfer.receiver_id
+                    && log.memo == transfer.memo
+            });
+
+            if let Some(log) = group {
+                log.token_ids.push(transfer.token_id.clone().into());
+            } else {
+                logs.push(NftTransferLog {
+                    authorized_id: None,
+                    old_owner_id: current_owner_id.into(),
+                    new_owner_id: transfer.receiver_id.clone(),
+                    token_ids: vec![transfer.token_id.clone().into()],
+                    memo: transfer.memo.clone(),
+                });
+            }
+        }
+
+        let events = Nep171Event::split_transfers(logs, Self::EVENT_VERSION, MAX_TOTAL_LOG_LENGTH);
+
+        let length = events
+            .iter()
+            .map(|event| event.to_versioned_log_string(Self::EVENT_VERSION).len())
+            .sum::<usize>()
+            .saturating_add(
+                transfers
+                    .len()
+                    .saturating_mul(Self::TRANSFER_HOOK_LOG_LENGTH),
+            );
+
+        if length > MAX_TOTAL_LOG_LENGTH {
+            return Err(BatchLogLengthExceededError {
+                length,
+                limit: MAX_TOTAL_LOG_LENGTH,
+            }
+            .into());
+        }
+
+        for transfer in transfers {
+            Self::TransferHook::hook(self, transfer, |contract| {
+                contract.transfer_unchecked(
+                    std::array::from_ref(&transfer.token_id),
+                    &transfer.receiver_id,
+                );
+            });
+        }
+
+        for event in events {
+            event.emit_versioned(Self::EVENT_VERSION);
+        }
+
+        Ok(())
+    }
+
     fn transfer_unchecked(&mut self, token_ids: &[TokenId], receiver_id: &AccountIdRef) {
         for token_id in token_ids {
             let mut slot = Self::slot_token_owner(token_id);
@@ -345,7 +482,7 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
                 owner_id: action.receiver_id.clone(),
                 memo: action.memo.clone(),
             }])
-            .emit();
+            .emit_versioned(Self::EVENT_VERSION);
 
             Ok(())
         })
@@ -383,7 +520,7 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
                 authorized_id: None,
                 memo: action.memo.clone(),
             }])
-            .emit();
+            .emit_versioned(Self::EVENT_VERSION);
 
             Ok(())
         })
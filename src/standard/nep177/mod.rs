@@ -10,20 +10,22 @@ use thiserror::Error;
 
 use crate::{
     slot::Slot,
-    standard::{
-        nep171::{
-            action::{Nep171Burn, Nep171Mint},
-            error::{Nep171BurnError, Nep171MintError, TokenDoesNotExistError},
-            event::{Nep171Event, NftContractMetadataUpdateLog, NftMetadataUpdateLog},
-            LoadTokenMetadata, Nep171Controller, TokenId,
+    standard::nep171::{
+        action::{Nep171Burn, Nep171Mint},
+        error::{
+            Nep171BurnError, Nep171MintError, TokenAlreadyExistsError, TokenDoesNotExistError,
         },
-        nep297::Event,
+        event::{Nep171Event, NftContractMetadataUpdateLog, NftMetadataUpdateLog},
+        LoadTokenMetadata, Nep171Controller, TokenId,
     },
     DefaultStorageKey,
 };
 
 pub use ext::*;
 
+pub mod series;
+pub mod voucher;
+
 const CONTRACT_METADATA_NOT_INITIALIZED_ERROR: &str = "Contract metadata not initialized";
 
 /// Non-fungible token contract metadata.
@@ -246,7 +248,8 @@ pub trait Nep177ControllerInternal {
 
 /// Functions for managing non-fungible tokens with attached metadata, NEP-177.
 pub trait Nep177Controller {
-    /// Mint a new token with metadata.
+    /// Mint a new token with metadata. The metadata is written before the
+    /// token is minted, so mint hooks can read it.
     ///
     /// # Errors
     ///
@@ -327,7 +330,7 @@ impl<T: Nep177ControllerInternal + Nep171Controller> Nep177Controller for T {
     fn set_contract_metadata(&mut self, metadata: &ContractMetadata) {
         Self::slot_contract_metadata().set(Some(metadata));
         Nep171Event::ContractMetadataUpdate(vec![NftContractMetadataUpdateLog { memo: None }])
-            .emit();
+            .emit_versioned(<Self as Nep171Controller>::EVENT_VERSION);
     }
 
     fn mint_with_metadata(
@@ -336,8 +339,27 @@ impl<T: Nep177ControllerInternal + Nep171Controller> Nep177Controller for T {
         owner_id: &AccountIdRef,
         metadata: &TokenMetadata,
     ) -> Result<(), Nep171MintError> {
-        self.mint(&Nep171Mint::new(vec![token_id.clone()], owner_id))?;
-        self.set_token_metadata_unchecked(token_id, Some(metadata));
+        if self.token_owner(token_id).is_some() {
+            return Err(TokenAlreadyExistsError {
+                token_id: token_id.clone(),
+            }
+            .into());
+        }
+
+        let mut slot = <Self as Nep177ControllerInternal>::slot_token_metadata(token_id);
+        slot.write(metadata);
+
+        if let Err(e) = self.mint(&Nep171Mint::new(vec![token_id.clone()], owner_id)) {
+            slot.remove();
+            return Err(e);
+        }
+
+        Nep171Event::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+            token_ids: vec![token_id.into()],
+            memo: None,
+        }])
+        .emit_versioned(<Self as Nep171Controller>::EVENT_VERSION);
+
         Ok(())
     }
 
@@ -361,7 +383,7 @@ impl<T: Nep177ControllerInternal + Nep171Controller> Nep177Controller for T {
             token_ids: vec![token_id.into()],
             memo: None,
         }])
-        .emit();
+        .emit_versioned(<Self as Nep171Controller>::EVENT_VERSION);
     }
 
     fn token_metadata(&self, token_id: &TokenId) -> Option<TokenMetadata> {
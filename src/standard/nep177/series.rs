@@ -0,0 +1,329 @@
+//! NFT series (editions): many tokens minted from the same metadata template.
+//!
+//! A [`Series`] describes a template [`TokenMetadata`], an optional maximum
+//! number of copies, an optional price, and the creator who receives the
+//! proceeds. Tokens minted from a series receive IDs of the form
+//! `"{series_id}:{edition}"`, where editions are numbered starting at 1.
+//!
+//! Tokens are minted with [`Nep177Controller::mint_with_metadata`], so all
+//! NEP-171 hooks apply. The metadata of each token is the series template
+//! with `copies` set to the maximum supply of the series and `issued_at` set
+//! to the current block timestamp.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The series root storage slot is not used or modified. The default
+//!     key is `~$177s`.
+//! * (UB) Token IDs of the form `"{series_id}:{edition}"` are not minted
+//!     outside of this component.
+//! * (ERR) No more than `max_supply` tokens are minted from a series.
+
+use near_sdk::{
+    borsh::BorshSerialize, env, json_types::U64, near, AccountId, AccountIdRef, BorshStorageKey,
+    NearToken,
+};
+use near_sdk_contract_tools_macros::event;
+use thiserror::Error;
+
+use crate::{
+    slot::Slot,
+    standard::{
+        nep171::{error::Nep171MintError, TokenId},
+        nep177::{Nep177Controller, TokenMetadata},
+        nep297::Event,
+    },
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+/// Series identifier.
+pub type SeriesId = u32;
+
+/// A series of tokens sharing the same metadata template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct Series {
+    /// The series ID.
+    pub series_id: SeriesId,
+    /// The account that created the series and receives the proceeds.
+    pub creator_id: AccountId,
+    /// Metadata template for tokens minted from this series.
+    pub metadata: TokenMetadata,
+    /// Maximum number of tokens that can be minted from this series, if any.
+    pub max_supply: Option<U64>,
+    /// Price of minting a token from this series, if any.
+    pub price: Option<NearToken>,
+    /// Number of tokens minted from this series so far.
+    pub minted: U64,
+}
+
+impl Series {
+    /// Returns the token ID of an edition of this series.
+    #[must_use]
+    pub fn token_id(&self, edition: u64) -> TokenId {
+        format!("{}:{edition}", self.series_id)
+    }
+
+    /// Returns `true` if no more tokens can be minted from this series.
+    #[must_use]
+    pub fn is_sold_out(&self) -> bool {
+        self.max_supply
+            .is_some_and(|max_supply| self.minted.0 >= max_supply.0)
+    }
+}
+
+/// Events emitted by the series component.
+#[event(
+    standard = "x-nft-series",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum NftSeriesEvent {
+    /// Emitted when a series is created.
+    SeriesCreate {
+        /// The new series ID.
+        series_id: SeriesId,
+        /// The creator of the series.
+        creator_id: AccountId,
+        /// Maximum number of tokens that can be minted from the series.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_supply: Option<U64>,
+    },
+    /// Emitted when a token is minted from a series.
+    SeriesMint {
+        /// The series ID.
+        series_id: SeriesId,
+        /// Edition number of the minted token.
+        edition: U64,
+        /// The minted token ID.
+        token_id: TokenId,
+        /// The owner of the minted token.
+        owner_id: AccountId,
+    },
+}
+
+/// The series does not exist.
+#[derive(Error, Clone, Debug)]
+#[error("Series {series_id} does not exist")]
+pub struct SeriesDoesNotExistError {
+    /// The series ID.
+    pub series_id: SeriesId,
+}
+
+/// All tokens of the series have been minted.
+#[derive(Error, Clone, Debug)]
+#[error("Series {series_id} is sold out ({max_supply} minted)")]
+pub struct SeriesSoldOutError {
+    /// The series ID.
+    pub series_id: SeriesId,
+    /// The maximum supply of the series.
+    pub max_supply: u64,
+}
+
+/// Errors that may occur when minting from a series.
+#[derive(Error, Clone, Debug)]
+pub enum MintFromSeriesError {
+    /// The series does not exist.
+    #[error(transparent)]
+    SeriesDoesNotExist(#[from] SeriesDoesNotExistError),
+    /// All tokens of the series have been minted.
+    #[error(transparent)]
+    SeriesSoldOut(#[from] SeriesSoldOutError),
+    /// The token could not be minted.
+    #[error(transparent)]
+    Mint(#[from] Nep171MintError),
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey {
+    SeriesCount,
+    Series(SeriesId),
+}
+
+/// Internal functions for [`NftSeries`].
+pub trait NftSeriesInternal {
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Nep177Series)
+    }
+
+    /// Storage slot for the number of series created.
+    #[must_use]
+    fn slot_series_count() -> Slot<SeriesId> {
+        Self::root().field(StorageKey::SeriesCount)
+    }
+
+    /// Storage slot for a series.
+    #[must_use]
+    fn slot_series(series_id: SeriesId) -> Slot<Series> {
+        Self::root().field(StorageKey::Series(series_id))
+    }
+}
+
+/// Functions for managing NFT series.
+pub trait NftSeries {
+    /// Returns the number of series created. Series IDs are assigned
+    /// sequentially, starting at 0.
+    fn series_count(&self) -> SeriesId;
+
+    /// Returns a series, if it exists.
+    fn series(&self, series_id: SeriesId) -> Option<Series>;
+
+    /// Creates a new series and emits a [`NftSeriesEvent::SeriesCreate`]
+    /// event. Returns the new series.
+    fn create_series(
+        &mut self,
+        creator_id: &AccountIdRef,
+        metadata: &TokenMetadata,
+        max_supply: Option<u64>,
+        price: Option<NearToken>,
+    ) -> Series;
+
+    /// Mints the next edition of a series to `owner_id`, and emits a
+    /// [`NftSeriesEvent::SeriesMint`] event. Does not collect payment.
+    /// Returns the minted token ID.
+    ///
+    /// # Errors
+    ///
+    /// - If the series does not exist.
+    /// - If the series is sold out.
+    /// - If the token could not be minted.
+    fn mint_from_series(
+        &mut self,
+        series_id: SeriesId,
+        owner_id: &AccountIdRef,
+    ) -> Result<TokenId, MintFromSeriesError>;
+}
+
+impl<T: NftSeriesInternal + Nep177Controller> NftSeries for T {
+    fn series_count(&self) -> SeriesId {
+        Self::slot_series_count().read().unwrap_or(0)
+    }
+
+    fn series(&self, series_id: SeriesId) -> Option<Series> {
+        Self::slot_series(series_id).read()
+    }
+
+    fn create_series(
+        &mut self,
+        creator_id: &AccountIdRef,
+        metadata: &TokenMetadata,
+        max_supply: Option<u64>,
+        price: Option<NearToken>,
+    ) -> Series {
+        let series_id = self.series_count();
+
+        let series = Series {
+            series_id,
+            creator_id: creator_id.to_owned(),
+            metadata: metadata.clone(),
+            max_supply: max_supply.map(Into::into),
+            price,
+            minted: U64(0),
+        };
+
+        Self::slot_series(series_id).write(&series);
+        Self::slot_series_count().write(
+            &series_id
+                .checked_add(1)
+                .unwrap_or_else(|| env::panic_str("Series count overflow")),
+        );
+
+        NftSeriesEvent::SeriesCreate {
+            series_id,
+            creator_id: series.creator_id.clone(),
+            max_supply: series.max_supply,
+        }
+        .emit();
+
+        series
+    }
+
+    fn mint_from_series(
+        &mut self,
+        series_id: SeriesId,
+        owner_id: &AccountIdRef,
+    ) -> Result<TokenId, MintFromSeriesError> {
+        let mut series = self
+            .series(series_id)
+            .ok_or(SeriesDoesNotExistError { series_id })?;
+
+        if let Some(U64(max_supply)) = series.max_supply {
+            if series.minted.0 >= max_supply {
+                return Err(SeriesSoldOutError {
+                    series_id,
+                    max_supply,
+                }
+                .into());
+            }
+        }
+
+        let edition = series.minted.0 + 1;
+        let token_id = series.token_id(edition);
+
+        let metadata = TokenMetadata {
+            copies: series.max_supply,
+            issued_at: Some(U64(env::block_timestamp_ms())),
+            ..series.metadata.clone()
+        };
+
+        self.mint_with_metadata(&token_id, owner_id, &metadata)?;
+
+        series.minted = U64(edition);
+        Self::slot_series(series_id).write(&series);
+
+        NftSeriesEvent::SeriesMint {
+            series_id,
+            edition: U64(edition),
+            token_id: token_id.clone(),
+            owner_id: owner_id.to_owned(),
+        }
+        .emit();
+
+        Ok(token_id)
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, AccountId};
+
+    use super::{Series, SeriesId};
+    use crate::standard::nep171::TokenId;
+
+    /// External (public) methods for [`NftSeries`](super::NftSeries).
+    #[ext_contract(ext_nft_series)]
+    pub trait NftSeriesExternal {
+        /// Mints the next edition of a series to `receiver_id` (default: the
+        /// predecessor). The attached deposit must cover the price of the
+        /// series, which is paid to the series creator, plus the storage fee
+        /// of the minted token. Any excess is refunded.
+        fn nft_mint_from_series(
+            &mut self,
+            series_id: SeriesId,
+            receiver_id: Option<AccountId>,
+        ) -> TokenId;
+
+        /// Returns a series, if it exists.
+        fn nft_get_series(&self, series_id: SeriesId) -> Option<Series>;
+
+        /// Returns the number of series.
+        fn nft_series_count(&self) -> SeriesId;
+
+        /// Returns series in order of creation, starting at `from_index`
+        /// (default: 0), up to `limit` (default: all) series.
+        fn nft_series(&self, from_index: Option<SeriesId>, limit: Option<SeriesId>) -> Vec<Series>;
+    }
+}
@@ -0,0 +1,484 @@
+//! Lazy minting: tokens minted on redemption of creator-signed vouchers.
+//!
+//! A creator signs a [`MintVoucher`] off chain with an ed25519 key that has
+//! been registered for them on the contract. Anyone holding the voucher, the
+//! metadata it commits to, and the signature may redeem it before it expires,
+//! paying the voucher price. The token is minted with
+//! [`Nep177Controller::mint_with_metadata`], so all NEP-171 hooks apply.
+//!
+//! The signed message is [`MintVoucher::signing_message`]: the Borsh
+//! serialization of the contract account ID followed by the voucher, so a
+//! voucher cannot be redeemed on another contract. The voucher commits to its
+//! metadata by the SHA-256 hash of the Borsh-serialized [`TokenMetadata`]
+//! (see [`hash_metadata`]).
+//!
+//! The price is split by a [`VoucherPayout`] implementation. The default,
+//! [`CreatorPayout`], pays the full price to the creator; implement
+//! [`VoucherPayout`] to deduct royalties. Payouts may not add up to more than
+//! the price, and any remainder is paid to the creator.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The voucher root storage slot is not used or modified. The default
+//!     key is `~$177v`.
+//! * (ERR) Vouchers are signed by the registered key of their creator.
+//! * (ERR) Vouchers are redeemed at most once, and only before they expire.
+//! * (ERR) The redeemed metadata matches the hash in the voucher.
+//! * (ERR) Payouts do not add up to more than the price of the voucher.
+
+use near_sdk::{
+    borsh::{self, BorshSerialize},
+    env,
+    json_types::{Base64VecU8, U64},
+    near, AccountId, AccountIdRef, BorshStorageKey, CurveType, NearToken, PublicKey,
+};
+use near_sdk_contract_tools_macros::event;
+use thiserror::Error;
+
+use crate::{
+    slot::Slot,
+    standard::{
+        nep171::{error::Nep171MintError, TokenId},
+        nep177::{Nep177Controller, TokenMetadata},
+        nep297::Event,
+    },
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+/// A creator's authorization to mint a token for a price.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct MintVoucher {
+    /// ID of the token to mint.
+    pub token_id: TokenId,
+    /// The creator who signed the voucher and receives the proceeds.
+    pub creator_id: AccountId,
+    /// SHA-256 hash of the Borsh-serialized token metadata.
+    pub metadata_hash: Base64VecU8,
+    /// Price of redeeming the voucher.
+    pub price: NearToken,
+    /// When the voucher expires, in milliseconds since the Unix epoch.
+    pub expires_at: U64,
+}
+
+impl MintVoucher {
+    /// Returns the message that the creator signs for redemption on the
+    /// contract `contract_id`.
+    #[must_use]
+    pub fn signing_message(&self, contract_id: &AccountIdRef) -> Vec<u8> {
+        borsh::to_vec(&(contract_id, self))
+            .unwrap_or_else(|_| env::panic_str("Failed to serialize voucher"))
+    }
+}
+
+/// Returns the SHA-256 hash of the Borsh-serialized token metadata, as
+/// committed to by [`MintVoucher::metadata_hash`].
+#[must_use]
+pub fn hash_metadata(metadata: &TokenMetadata) -> Vec<u8> {
+    env::sha256(
+        &borsh::to_vec(metadata)
+            .unwrap_or_else(|_| env::panic_str("Failed to serialize token metadata")),
+    )
+}
+
+/// Splits the price of a redeemed voucher between recipients.
+pub trait VoucherPayout<C> {
+    /// Returns the recipients of the price of the voucher and their shares.
+    /// The shares must not add up to more than the price. Any remainder is
+    /// paid to the creator.
+    fn payout(contract: &C, voucher: &MintVoucher) -> Vec<(AccountId, NearToken)>;
+}
+
+/// Pays the full price of a voucher to its creator.
+pub struct CreatorPayout;
+
+impl<C> VoucherPayout<C> for CreatorPayout {
+    fn payout(_contract: &C, voucher: &MintVoucher) -> Vec<(AccountId, NearToken)> {
+        vec![(voucher.creator_id.clone(), voucher.price)]
+    }
+}
+
+/// Events emitted by the voucher component.
+#[event(
+    standard = "x-nft-voucher",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum NftVoucherEvent {
+    /// Emitted when the key of a creator is set or removed.
+    CreatorKeyUpdate {
+        /// The creator.
+        creator_id: AccountId,
+        /// The new key, if any.
+        public_key: Option<PublicKey>,
+    },
+    /// Emitted when a voucher is redeemed.
+    VoucherRedeem {
+        /// The minted token ID.
+        token_id: TokenId,
+        /// The creator of the voucher.
+        creator_id: AccountId,
+        /// The owner of the minted token.
+        owner_id: AccountId,
+        /// The price paid.
+        price: NearToken,
+    },
+}
+
+/// Only ed25519 keys can be registered.
+#[derive(Error, Clone, Debug)]
+#[error("Creator keys must be ed25519 keys")]
+pub struct UnsupportedKeyTypeError;
+
+/// The creator of the voucher has no registered key.
+#[derive(Error, Clone, Debug)]
+#[error("Creator {creator_id} has no registered key")]
+pub struct CreatorKeyNotRegisteredError {
+    /// The creator.
+    pub creator_id: AccountId,
+}
+
+/// The signature does not match the voucher and the creator's key.
+#[derive(Error, Clone, Debug)]
+#[error("Invalid signature for voucher of token {token_id}")]
+pub struct InvalidVoucherSignatureError {
+    /// The token ID of the voucher.
+    pub token_id: TokenId,
+}
+
+/// The voucher has expired.
+#[derive(Error, Clone, Debug)]
+#[error("Voucher of token {token_id} expired at {expires_at}")]
+pub struct VoucherExpiredError {
+    /// The token ID of the voucher.
+    pub token_id: TokenId,
+    /// When the voucher expired.
+    pub expires_at: u64,
+}
+
+/// The voucher has already been redeemed.
+#[derive(Error, Clone, Debug)]
+#[error("Voucher of token {token_id} has already been redeemed")]
+pub struct VoucherConsumedError {
+    /// The token ID of the voucher.
+    pub token_id: TokenId,
+}
+
+/// The metadata does not match the hash in the voucher.
+#[derive(Error, Clone, Debug)]
+#[error("Metadata does not match the voucher of token {token_id}")]
+pub struct MetadataHashMismatchError {
+    /// The token ID of the voucher.
+    pub token_id: TokenId,
+}
+
+/// Errors that may occur when verifying a voucher.
+#[derive(Error, Clone, Debug)]
+pub enum VerifyVoucherError {
+    /// The creator of the voucher has no registered key.
+    #[error(transparent)]
+    CreatorKeyNotRegistered(#[from] CreatorKeyNotRegisteredError),
+    /// The signature is invalid.
+    #[error(transparent)]
+    InvalidSignature(#[from] InvalidVoucherSignatureError),
+    /// The voucher has expired.
+    #[error(transparent)]
+    Expired(#[from] VoucherExpiredError),
+    /// The voucher has already been redeemed.
+    #[error(transparent)]
+    Consumed(#[from] VoucherConsumedError),
+    /// The metadata does not match the voucher.
+    #[error(transparent)]
+    MetadataHashMismatch(#[from] MetadataHashMismatchError),
+}
+
+/// The payouts of a voucher add up to more than its price.
+#[derive(Error, Clone, Debug)]
+#[error("Payouts for voucher of token {token_id} exceed its price {price}")]
+pub struct ExcessivePayoutError {
+    /// The token ID of the voucher.
+    pub token_id: TokenId,
+    /// The price of the voucher.
+    pub price: NearToken,
+}
+
+/// Errors that may occur when redeeming a voucher.
+#[derive(Error, Clone, Debug)]
+pub enum RedeemVoucherError {
+    /// The voucher is not valid.
+    #[error(transparent)]
+    Verify(#[from] VerifyVoucherError),
+    /// The payouts exceed the price of the voucher.
+    #[error(transparent)]
+    ExcessivePayout(#[from] ExcessivePayoutError),
+    /// The token could not be minted.
+    #[error(transparent)]
+    Mint(#[from] Nep171MintError),
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey<'a> {
+    CreatorKey(&'a AccountIdRef),
+    Consumed([u8; 32]),
+}
+
+/// Internal functions for [`NftVoucher`].
+pub trait NftVoucherInternal {
+    /// Splits the price of redeemed vouchers.
+    type Payout: VoucherPayout<Self>
+    where
+        Self: Sized;
+
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Nep177Voucher)
+    }
+
+    /// Storage slot for the registered key of a creator.
+    #[must_use]
+    fn slot_creator_key(creator_id: &AccountIdRef) -> Slot<PublicKey> {
+        Self::root().field(StorageKey::CreatorKey(creator_id))
+    }
+
+    /// Storage slot marking a voucher as consumed, by the hash of its
+    /// signing message.
+    #[must_use]
+    fn slot_consumed(voucher_hash: [u8; 32]) -> Slot<()> {
+        Self::root().field(StorageKey::Consumed(voucher_hash))
+    }
+}
+
+/// Functions for lazy minting with creator-signed vouchers.
+pub trait NftVoucher {
+    /// Returns the registered key of a creator, if any.
+    fn creator_key(&self, creator_id: &AccountIdRef) -> Option<PublicKey>;
+
+    /// Registers (or removes, if `None`) the key of a creator and emits a
+    /// [`NftVoucherEvent::CreatorKeyUpdate`] event.
+    ///
+    /// # Errors
+    ///
+    /// - If the key is not an ed25519 key.
+    fn set_creator_key(
+        &mut self,
+        creator_id: &AccountIdRef,
+        public_key: Option<&PublicKey>,
+    ) -> Result<(), UnsupportedKeyTypeError>;
+
+    /// Returns `true` if the voucher has been redeemed.
+    fn is_voucher_consumed(&self, voucher: &MintVoucher) -> bool;
+
+    /// Checks that a voucher can be redeemed with the given metadata and
+    /// signature.
+    ///
+    /// # Errors
+    ///
+    /// - If the creator has no registered key.
+    /// - If the signature is invalid.
+    /// - If the voucher has expired.
+    /// - If the voucher has already been redeemed.
+    /// - If the metadata does not match the voucher.
+    fn verify_voucher(
+        &self,
+        voucher: &MintVoucher,
+        metadata: &TokenMetadata,
+        signature: &[u8],
+    ) -> Result<(), VerifyVoucherError>;
+
+    /// Redeems a voucher, minting its token to `owner_id`, and emits a
+    /// [`NftVoucherEvent::VoucherRedeem`] event. Does not collect payment.
+    /// Returns the recipients of the price and their shares, which add up to
+    /// the price.
+    ///
+    /// # Errors
+    ///
+    /// - If the voucher cannot be verified.
+    /// - If the payouts add up to more than the price.
+    /// - If the token could not be minted.
+    fn redeem_voucher(
+        &mut self,
+        voucher: &MintVoucher,
+        metadata: &TokenMetadata,
+        signature: &[u8],
+        owner_id: &AccountIdRef,
+    ) -> Result<Vec<(AccountId, NearToken)>, RedeemVoucherError>;
+}
+
+fn voucher_hash(voucher: &MintVoucher) -> [u8; 32] {
+    env::sha256_array(&voucher.signing_message(&env::current_account_id()))
+}
+
+impl<T: NftVoucherInternal + Nep177Controller> NftVoucher for T {
+    fn creator_key(&self, creator_id: &AccountIdRef) -> Option<PublicKey> {
+        Self::slot_creator_key(creator_id).read()
+    }
+
+    fn set_creator_key(
+        &mut self,
+        creator_id: &AccountIdRef,
+        public_key: Option<&PublicKey>,
+    ) -> Result<(), UnsupportedKeyTypeError> {
+        if public_key.is_some_and(|k| k.curve_type() != CurveType::ED25519) {
+            return Err(UnsupportedKeyTypeError);
+        }
+
+        Self::slot_creator_key(creator_id).set(public_key);
+
+        NftVoucherEvent::CreatorKeyUpdate {
+            creator_id: creator_id.to_owned(),
+            public_key: public_key.cloned(),
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    fn is_voucher_consumed(&self, voucher: &MintVoucher) -> bool {
+        Self::slot_consumed(voucher_hash(voucher)).exists()
+    }
+
+    fn verify_voucher(
+        &self,
+        voucher: &MintVoucher,
+        metadata: &TokenMetadata,
+        signature: &[u8],
+    ) -> Result<(), VerifyVoucherError> {
+        let public_key =
+            self.creator_key(&voucher.creator_id)
+                .ok_or_else(|| CreatorKeyNotRegisteredError {
+                    creator_id: voucher.creator_id.clone(),
+                })?;
+
+        let invalid_signature = || InvalidVoucherSignatureError {
+            token_id: voucher.token_id.clone(),
+        };
+
+        // The first byte of the key is the curve type.
+        let public_key: &[u8; 32] = public_key.as_bytes()[1..]
+            .try_into()
+            .map_err(|_| invalid_signature())?;
+        let signature: &[u8; 64] = signature.try_into().map_err(|_| invalid_signature())?;
+        let message = voucher.signing_message(&env::current_account_id());
+
+        if !env::ed25519_verify(signature, &message, public_key) {
+            return Err(invalid_signature().into());
+        }
+
+        if voucher.expires_at.0 <= env::block_timestamp_ms() {
+            return Err(VoucherExpiredError {
+                token_id: voucher.token_id.clone(),
+                expires_at: voucher.expires_at.0,
+            }
+            .into());
+        }
+
+        if Self::slot_consumed(env::sha256_array(&message)).exists() {
+            return Err(VoucherConsumedError {
+                token_id: voucher.token_id.clone(),
+            }
+            .into());
+        }
+
+        if hash_metadata(metadata) != voucher.metadata_hash.0 {
+            return Err(MetadataHashMismatchError {
+                token_id: voucher.token_id.clone(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn redeem_voucher(
+        &mut self,
+        voucher: &MintVoucher,
+        metadata: &TokenMetadata,
+        signature: &[u8],
+        owner_id: &AccountIdRef,
+    ) -> Result<Vec<(AccountId, NearToken)>, RedeemVoucherError> {
+        self.verify_voucher(voucher, metadata, signature)?;
+
+        let mut payouts = T::Payout::payout(self, voucher);
+
+        let remainder = payouts
+            .iter()
+            .try_fold(voucher.price, |remainder, (_, amount)| {
+                remainder.checked_sub(*amount)
+            })
+            .ok_or_else(|| ExcessivePayoutError {
+                token_id: voucher.token_id.clone(),
+                price: voucher.price,
+            })?;
+
+        if !remainder.is_zero() {
+            payouts.push((voucher.creator_id.clone(), remainder));
+        }
+
+        self.mint_with_metadata(&voucher.token_id, owner_id, metadata)?;
+
+        Self::slot_consumed(voucher_hash(voucher)).write(&());
+
+        NftVoucherEvent::VoucherRedeem {
+            token_id: voucher.token_id.clone(),
+            creator_id: voucher.creator_id.clone(),
+            owner_id: owner_id.to_owned(),
+            price: voucher.price,
+        }
+        .emit();
+
+        Ok(payouts)
+    }
+}
+
+/// Authorization for registering creator keys through the external
+/// interface.
+pub trait NftVoucherAuthority {
+    /// Panics if the predecessor is not allowed to register creator keys.
+    fn require_voucher_authority(&self);
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::Base64VecU8, AccountId, PublicKey};
+
+    use super::MintVoucher;
+    use crate::standard::{nep171::TokenId, nep177::TokenMetadata};
+
+    /// External (public) methods for [`NftVoucher`](super::NftVoucher).
+    #[ext_contract(ext_nft_voucher)]
+    pub trait NftVoucherExternal {
+        /// Redeems a voucher, minting its token to `receiver_id` (default:
+        /// the predecessor). The attached deposit must cover the price of the
+        /// voucher, which is paid out to the creator. Any excess is refunded.
+        fn nft_redeem_voucher(
+            &mut self,
+            voucher: MintVoucher,
+            metadata: TokenMetadata,
+            signature: Base64VecU8,
+            receiver_id: Option<AccountId>,
+        ) -> TokenId;
+
+        /// Registers (or removes, if `None`) the ed25519 key of a creator.
+        /// Requires exactly 1 yoctoNEAR attached.
+        fn nft_set_creator_key(&mut self, creator_id: AccountId, public_key: Option<PublicKey>);
+
+        /// Returns the registered key of a creator, if any.
+        fn nft_creator_key(&self, creator_id: AccountId) -> Option<PublicKey>;
+
+        /// Returns `true` if the voucher has been redeemed.
+        fn nft_is_voucher_consumed(&self, voucher: MintVoucher) -> bool;
+    }
+}
@@ -16,6 +16,9 @@ pub struct Nep178Approve<'a> {
     /// Account ID of the target account. This account will be able to
     /// transfer the token.
     pub account_id: Cow<'a, AccountIdRef>,
+    /// When the approval expires, in milliseconds since the Unix epoch. If
+    /// `None`, the approval does not expire.
+    pub expires_at: Option<u64>,
 }
 
 /// NEP-178 revoke action.
@@ -1,6 +1,6 @@
 //! NEP-178 errors.
 
-use super::{TokenId, MAX_APPROVALS};
+use super::TokenId;
 use near_sdk::AccountId;
 use thiserror::Error;
 
@@ -26,13 +26,22 @@ pub struct AccountAlreadyApprovedError {
 
 /// The token has too many approvals.
 #[derive(Error, Debug)]
-#[error(
-    "Too many approvals for token {token_id}, maximum is {}.",
-    MAX_APPROVALS
-)]
+#[error("Too many approvals for token {token_id}, maximum is {max_approvals}.")]
 pub struct TooManyApprovalsError {
     /// The token ID.
     pub token_id: TokenId,
+    /// The maximum number of approvals per token.
+    pub max_approvals: u64,
+}
+
+/// The expiration time of an approval is not in the future.
+#[derive(Error, Debug)]
+#[error("Approval for token {token_id} must expire in the future, got {expires_at}.")]
+pub struct ApprovalExpiryInPastError {
+    /// The token ID.
+    pub token_id: TokenId,
+    /// The requested expiration time.
+    pub expires_at: u64,
 }
 
 /// Errors that can occur when managing non-fungible token approvals.
@@ -47,6 +56,9 @@ pub enum Nep178ApproveError {
     /// The token has too many approvals.
     #[error(transparent)]
     TooManyApprovals(#[from] TooManyApprovalsError),
+    /// The expiration time is not in the future.
+    #[error(transparent)]
+    ApprovalExpiryInPast(#[from] ApprovalExpiryInPastError),
 }
 
 /// The account is not approved for the token.
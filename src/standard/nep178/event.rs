@@ -0,0 +1,48 @@
+//! Events emitted when approvals change.
+
+use near_sdk::{json_types::U64, AccountId};
+use near_sdk_contract_tools_macros::event;
+
+use super::{ApprovalId, TokenId};
+
+/// Approval change events. NEP-178 does not specify events, so these use a
+/// non-standard name.
+#[event(
+    standard = "x-nft-approval",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum Nep178Event {
+    /// Emitted when an account is approved for a token.
+    Approve {
+        /// The token ID.
+        token_id: TokenId,
+        /// The owner of the token.
+        owner_id: AccountId,
+        /// The approved account.
+        account_id: AccountId,
+        /// The approval ID.
+        approval_id: ApprovalId,
+        /// When the approval expires, in milliseconds since the Unix epoch.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expires_at: Option<U64>,
+    },
+    /// Emitted when the approval of an account for a token is revoked.
+    Revoke {
+        /// The token ID.
+        token_id: TokenId,
+        /// The owner of the token.
+        owner_id: AccountId,
+        /// The account that is no longer approved.
+        account_id: AccountId,
+    },
+    /// Emitted when all approvals for a token are revoked.
+    RevokeAll {
+        /// The token ID.
+        token_id: TokenId,
+        /// The owner of the token.
+        owner_id: AccountId,
+    },
+}
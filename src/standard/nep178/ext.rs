@@ -28,6 +28,20 @@ pub trait Nep178 {
     ) -> bool;
 }
 
+/// Extension to the NEP-178 external interface for approvals that expire.
+#[near_sdk::ext_contract(ext_nep178_expiry)]
+pub trait Nep178Expiry {
+    /// Same as `nft_approve`, but the approval expires at `expires_at`, in
+    /// milliseconds since the Unix epoch.
+    fn nft_approve_with_expiry(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expires_at: U64,
+        msg: Option<String>,
+    ) -> PromiseOrValue<()>;
+}
+
 /// NEP-178 receiver interface.
 ///
 /// Respond to notification that contract has been granted approval for a token.
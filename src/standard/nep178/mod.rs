@@ -1,11 +1,19 @@
 //! NEP-178 non-fungible token approval management implementation.
 //!
 //! Reference: <https://github.com/near/NEPs/blob/master/neps/nep-0178.md>
+//!
+//! Approvals may optionally expire. The standard `nft_approve` creates
+//! approvals without expiry; the [`Nep178Expiry::nft_approve_with_expiry`]
+//! extension sets an expiration time. Expired approvals are ignored by
+//! [`Nep178Controller::get_approval_id_for`] and
+//! [`Nep178Controller::get_approvals_for`] (and therefore by external
+//! transfers and `nft_is_approved`), and are pruned the next time an approval
+//! is created for the same token.
 use std::{borrow::Cow, collections::HashMap, error::Error};
 
 use near_sdk::{
-    borsh::BorshSerialize, collections::UnorderedMap, near, AccountId, AccountIdRef,
-    BorshStorageKey,
+    borsh::BorshSerialize, collections::UnorderedMap, env, json_types::U64, near, AccountId,
+    AccountIdRef, BorshStorageKey,
 };
 
 use crate::{
@@ -17,6 +25,7 @@ use crate::{
         CheckExternalTransfer, DefaultCheckExternalTransfer, LoadTokenMetadata, Nep171Controller,
         Nep171TransferAuthorization, TokenId,
     },
+    standard::nep297::Event,
     DefaultStorageKey,
 };
 
@@ -24,13 +33,15 @@ pub mod action;
 use action::*;
 pub mod error;
 use error::*;
+pub mod event;
+use event::*;
 // separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
 mod ext;
 pub use ext::*;
 
 /// Type for approval IDs.
 pub type ApprovalId = u32;
-/// Maximum number of approvals per token.
+/// Default maximum number of approvals per token.
 pub const MAX_APPROVALS: u64 = 32;
 
 /// NFT token approvals. Hooks are implemented on this struct.
@@ -111,6 +122,7 @@ impl<C: Nep171Controller + Nep178Controller> CheckExternalTransfer<C> for TokenA
 enum StorageKey<'a> {
     TokenApprovals(&'a TokenId),
     TokenApprovalsUnorderedMap(&'a TokenId),
+    ApprovalExpiry(&'a TokenId, &'a AccountIdRef),
 }
 
 /// Internal functions for [`Nep178Controller`].
@@ -128,6 +140,9 @@ pub trait Nep178ControllerInternal {
     where
         Self: Sized;
 
+    /// Maximum number of unexpired approvals per token.
+    const MAX_APPROVALS: u64 = MAX_APPROVALS;
+
     /// Storage root.
     #[must_use]
     fn root() -> Slot<()> {
@@ -147,6 +162,12 @@ pub trait Nep178ControllerInternal {
     ) -> Slot<UnorderedMap<AccountId, ApprovalId>> {
         Self::root().field(StorageKey::TokenApprovalsUnorderedMap(token_id))
     }
+
+    /// Storage slot for the expiration time of an approval.
+    #[must_use]
+    fn slot_approval_expiry(token_id: &TokenId, account_id: &AccountIdRef) -> Slot<u64> {
+        Self::root().field(StorageKey::ApprovalExpiry(token_id, account_id))
+    }
 }
 
 /// Functions for managing token approvals, NEP-178.
@@ -164,6 +185,9 @@ pub trait Nep178Controller {
     where
         Self: Sized;
 
+    /// Maximum number of unexpired approvals per token.
+    const MAX_APPROVALS: u64;
+
     /// Approve a token for transfer by a delegated account.
     ///
     /// # Errors
@@ -171,6 +195,7 @@ pub trait Nep178Controller {
     /// - If the acting account is not authorized to create approvals for the token.
     /// - If the target account is already approved for the token.
     /// - If the token exceeds the maximum number of approvals.
+    /// - If the expiration time is not in the future.
     fn approve(&mut self, action: &Nep178Approve<'_>) -> Result<ApprovalId, Nep178ApproveError>;
 
     /// Approve a token without checking if the account is already approved or
@@ -199,14 +224,20 @@ pub trait Nep178Controller {
     /// Revoke all approvals for a token without checking current owner.
     fn revoke_all_unchecked(&mut self, token_id: &TokenId);
 
-    /// Get the approval ID for an account, if it is approved for a token.
+    /// Get the approval ID for an account, if it has an unexpired approval
+    /// for a token.
     fn get_approval_id_for(
         &self,
         token_id: &TokenId,
         account_id: &AccountIdRef,
     ) -> Option<ApprovalId>;
 
-    /// Get the approvals for a token.
+    /// Get the expiration time of an approval, in milliseconds since the Unix
+    /// epoch. Returns `None` if the approval does not expire or does not
+    /// exist.
+    fn get_approval_expiry(&self, token_id: &TokenId, account_id: &AccountIdRef) -> Option<u64>;
+
+    /// Get the unexpired approvals for a token.
     fn get_approvals_for(&self, token_id: &TokenId) -> HashMap<AccountId, ApprovalId>;
 }
 
@@ -215,6 +246,8 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
     type RevokeHook = T::RevokeHook;
     type RevokeAllHook = T::RevokeAllHook;
 
+    const MAX_APPROVALS: u64 = <T as Nep178ControllerInternal>::MAX_APPROVALS;
+
     fn approve_unchecked(&mut self, token_id: &TokenId, account_id: &AccountIdRef) -> ApprovalId {
         let mut slot = Self::slot_token_approvals(token_id);
         let mut approvals = slot.read().unwrap_or_else(|| TokenApprovals {
@@ -225,6 +258,7 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         approvals.accounts.insert(&account_id.into(), &approval_id);
         approvals.next_approval_id += 1; // overflow unrealistic
         slot.write(&approvals);
+        Self::slot_approval_expiry(token_id, account_id).remove();
 
         approval_id
     }
@@ -245,17 +279,40 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
             accounts: UnorderedMap::new(Self::slot_token_approvals_unordered_map(&action.token_id)),
         });
 
-        if approvals.accounts.len() >= MAX_APPROVALS {
+        let now = env::block_timestamp_ms();
+
+        if let Some(expires_at) = action.expires_at {
+            if expires_at <= now {
+                return Err(ApprovalExpiryInPastError {
+                    token_id: action.token_id.clone(),
+                    expires_at,
+                }
+                .into());
+            }
+        }
+
+        let expired = approvals
+            .accounts
+            .keys()
+            .filter(|account_id| {
+                Self::slot_approval_expiry(&action.token_id, account_id)
+                    .read()
+                    .is_some_and(|expires_at| expires_at <= now)
+            })
+            .collect::<Vec<_>>();
+
+        let max_approvals = <Self as Nep178Controller>::MAX_APPROVALS;
+        if approvals.accounts.len() - expired.len() as u64 >= max_approvals {
             return Err(TooManyApprovalsError {
                 token_id: action.token_id.clone(),
+                max_approvals,
             }
             .into());
         }
 
         let approval_id = approvals.next_approval_id;
-        if approvals
-            .accounts
-            .get(&AccountId::from(action.account_id.as_ref()))
+        if self
+            .get_approval_id_for(&action.token_id, &action.account_id)
             .is_some()
         {
             return Err(AccountAlreadyApprovedError {
@@ -266,12 +323,29 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         }
 
         Self::ApproveHook::hook(self, action, |_| {
+            for account_id in &expired {
+                approvals.accounts.remove(account_id);
+                Self::slot_approval_expiry(&action.token_id, account_id).remove();
+            }
+
             approvals
                 .accounts
                 .insert(&action.account_id.clone().into(), &approval_id);
             approvals.next_approval_id += 1; // overflow unrealistic
             slot.write(&approvals);
 
+            Self::slot_approval_expiry(&action.token_id, &action.account_id)
+                .set(action.expires_at.as_ref());
+
+            Nep178Event::Approve {
+                token_id: action.token_id.clone(),
+                owner_id: action.current_owner_id.clone().into(),
+                account_id: action.account_id.clone().into(),
+                approval_id,
+                expires_at: action.expires_at.map(U64),
+            }
+            .emit();
+
             Ok(approval_id)
         })
     }
@@ -286,6 +360,7 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
 
         if old.is_some() {
             slot.write(&approvals);
+            Self::slot_approval_expiry(token_id, account_id).remove();
         }
     }
 
@@ -322,6 +397,14 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
                 .accounts
                 .remove(&AccountId::from(action.account_id.as_ref()));
             slot.write(&approvals);
+            Self::slot_approval_expiry(&action.token_id, &action.account_id).remove();
+
+            Nep178Event::Revoke {
+                token_id: action.token_id.clone(),
+                owner_id: action.current_owner_id.clone().into(),
+                account_id: action.account_id.clone().into(),
+            }
+            .emit();
 
             Ok(())
         })
@@ -340,6 +423,12 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         Self::RevokeAllHook::hook(self, action, |contract| {
             contract.revoke_all_unchecked(&action.token_id);
 
+            Nep178Event::RevokeAll {
+                token_id: action.token_id.clone(),
+                owner_id: action.current_owner_id.clone().into(),
+            }
+            .emit();
+
             Ok(())
         })
     }
@@ -351,6 +440,9 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         };
 
         if !approvals.accounts.is_empty() {
+            for account_id in approvals.accounts.keys() {
+                Self::slot_approval_expiry(token_id, &account_id).remove();
+            }
             approvals.accounts.clear();
             slot.write(&approvals);
         }
@@ -364,7 +456,14 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         let slot = Self::slot_token_approvals(token_id);
         let approvals = slot.read()?;
 
-        approvals.accounts.get(&account_id.into())
+        approvals
+            .accounts
+            .get(&account_id.into())
+            .filter(|_| !is_expired::<Self>(token_id, account_id))
+    }
+
+    fn get_approval_expiry(&self, token_id: &TokenId, account_id: &AccountIdRef) -> Option<u64> {
+        Self::slot_approval_expiry(token_id, account_id).read()
     }
 
     fn get_approvals_for(&self, token_id: &TokenId) -> HashMap<AccountId, ApprovalId> {
@@ -373,6 +472,16 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
             return HashMap::default();
         };
 
-        approvals.accounts.into_iter().collect()
+        approvals
+            .accounts
+            .into_iter()
+            .filter(|(account_id, _)| !is_expired::<Self>(token_id, account_id))
+            .collect()
     }
 }
+
+fn is_expired<C: Nep178ControllerInternal>(token_id: &TokenId, account_id: &AccountIdRef) -> bool {
+    C::slot_approval_expiry(token_id, account_id)
+        .read()
+        .is_some_and(|expires_at| expires_at <= env::block_timestamp_ms())
+}
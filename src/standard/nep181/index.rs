@@ -0,0 +1,270 @@
+//! Secondary indexes for NEP-181 enumeration.
+//!
+//! A secondary index lists tokens by user-defined keys, e.g. "tokens in series
+//! X", "tokens with trait Y", or "tokens sorted by mint time". The keys of a
+//! token are extracted by a [`TokenIndex`] implementation, and the index is
+//! kept up to date by the [`TokenIndexing`] hook when tokens are minted,
+//! transferred, and burned.
+//!
+//! Keys are ordered as strings, so numeric keys should be zero-padded (e.g.
+//! `format!("{timestamp:020}")`) for tokens to be listed in numeric order.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The index root storage slot is not used or modified. The default
+//!     key is `~$181i`.
+//! * (UB) Index names are unique within a contract.
+//! * (UB) [`TokenIndexing`] is installed as a NEP-171 mint, transfer, and burn
+//!     hook for every index. If the keys of a token change in any other way
+//!     (e.g. when its metadata is updated),
+//!     [`Nep181Index::reindex_token`] must be called.
+
+use std::{marker::PhantomData, ops::Bound};
+
+use near_sdk::{borsh::BorshSerialize, collections::TreeMap, BorshStorageKey};
+
+use crate::{
+    hook::Hook,
+    slot::Slot,
+    standard::nep171::{
+        action::{Nep171Burn, Nep171Mint, Nep171Transfer},
+        TokenId,
+    },
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+/// Extracts the keys of a token in a secondary index.
+pub trait TokenIndex<C> {
+    /// Name of the index. Must be unique within a contract.
+    const NAME: &'static str;
+
+    /// Returns the keys of a token in this index. A token may have any number
+    /// of keys. Called after the token is minted or transferred.
+    fn keys(contract: &C, token_id: &TokenId) -> Vec<String>;
+}
+
+/// Hook that maintains the secondary index `I`. Compose multiple indexes as
+/// a tuple, e.g. `(TokenIndexing<BySeries>, TokenIndexing<ByMintTime>)`.
+pub struct TokenIndexing<I>(PhantomData<I>);
+
+impl<C: Nep181Index, I: TokenIndex<C>> Hook<C, Nep171Mint<'_>> for TokenIndexing<I> {
+    fn hook<R>(contract: &mut C, args: &Nep171Mint<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        let r = f(contract);
+        for token_id in &args.token_ids {
+            contract.reindex_token::<I>(token_id);
+        }
+        r
+    }
+}
+
+impl<C: Nep181Index, I: TokenIndex<C>> Hook<C, Nep171Transfer<'_>> for TokenIndexing<I> {
+    fn hook<R>(contract: &mut C, args: &Nep171Transfer<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        let r = f(contract);
+        contract.reindex_token::<I>(&args.token_id);
+        r
+    }
+}
+
+impl<C: Nep181Index, I: TokenIndex<C>> Hook<C, Nep171Burn<'_>> for TokenIndexing<I> {
+    fn hook<R>(contract: &mut C, args: &Nep171Burn<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        let r = f(contract);
+        for token_id in &args.token_ids {
+            contract.unindex_token(I::NAME, token_id);
+        }
+        r
+    }
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey<'a> {
+    Index(&'a str),
+    KeySupply(&'a str, &'a str),
+    TokenKeys(&'a str, &'a TokenId),
+}
+
+/// Internal functions for [`Nep181Index`].
+pub trait Nep181IndexInternal {
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Nep181Index)
+    }
+
+    /// Storage slot for the entries of an index, ordered by key.
+    #[must_use]
+    fn slot_index(index: &str) -> Slot<TreeMap<(String, TokenId), ()>> {
+        Self::root().field(StorageKey::Index(index))
+    }
+
+    /// Storage slot for the number of tokens with a key in an index.
+    #[must_use]
+    fn slot_key_supply(index: &str, key: &str) -> Slot<u64> {
+        Self::root().field(StorageKey::KeySupply(index, key))
+    }
+
+    /// Storage slot for the keys of a token in an index.
+    #[must_use]
+    fn slot_token_keys(index: &str, token_id: &TokenId) -> Slot<Vec<String>> {
+        Self::root().field(StorageKey::TokenKeys(index, token_id))
+    }
+}
+
+/// Functions for managing secondary indexes of non-fungible tokens.
+pub trait Nep181Index {
+    /// Recomputes the keys of a token in the index `I`.
+    fn reindex_token<I: TokenIndex<Self>>(&mut self, token_id: &TokenId)
+    where
+        Self: Sized;
+
+    /// Sets the keys of a token in an index, replacing any previous keys.
+    fn index_token(&mut self, index: &str, token_id: &TokenId, keys: &[String]);
+
+    /// Removes a token from an index.
+    fn unindex_token(&mut self, index: &str, token_id: &TokenId) {
+        self.index_token(index, token_id, &[]);
+    }
+
+    /// Returns the keys of a token in an index.
+    fn token_keys(&self, index: &str, token_id: &TokenId) -> Vec<String>;
+
+    /// Returns the number of tokens with `key` in an index, or the number of
+    /// entries in the index if `key` is `None`.
+    fn index_supply(&self, index: &str, key: Option<&str>) -> u64;
+
+    /// Returns tokens with `key` in an index, or all entries of the index
+    /// ordered by key if `key` is `None`. A token with multiple keys is listed
+    /// once per key in the latter case.
+    fn index_tokens(
+        &self,
+        index: &str,
+        key: Option<&str>,
+        from_index: usize,
+        limit: Option<usize>,
+    ) -> Vec<TokenId>;
+}
+
+impl<T: Nep181IndexInternal> Nep181Index for T {
+    fn reindex_token<I: TokenIndex<Self>>(&mut self, token_id: &TokenId) {
+        let keys = I::keys(self, token_id);
+        self.index_token(I::NAME, token_id, &keys);
+    }
+
+    fn index_token(&mut self, index: &str, token_id: &TokenId, keys: &[String]) {
+        let mut token_keys_slot = Self::slot_token_keys(index, token_id);
+        let old_keys = token_keys_slot.read().unwrap_or_default();
+
+        if old_keys == keys {
+            return;
+        }
+
+        let mut index_slot = Self::slot_index(index);
+        let mut entries = index_slot
+            .read()
+            .unwrap_or_else(|| TreeMap::new(index_slot.key.clone()));
+
+        for key in &old_keys {
+            if entries.remove(&(key.clone(), token_id.clone())).is_some() {
+                let mut supply_slot = Self::slot_key_supply(index, key);
+                let supply = supply_slot.read().unwrap_or(0).saturating_sub(1);
+                supply_slot.set(Some(&supply).filter(|s| **s > 0));
+            }
+        }
+
+        for key in keys {
+            if entries
+                .insert(&(key.clone(), token_id.clone()), &())
+                .is_none()
+            {
+                let mut supply_slot = Self::slot_key_supply(index, key);
+                let supply = supply_slot.read().unwrap_or(0) + 1;
+                supply_slot.write(&supply);
+            }
+        }
+
+        index_slot.write(&entries);
+        token_keys_slot.set(Some(&keys.to_vec()).filter(|k| !k.is_empty()));
+    }
+
+    fn token_keys(&self, index: &str, token_id: &TokenId) -> Vec<String> {
+        Self::slot_token_keys(index, token_id)
+            .read()
+            .unwrap_or_default()
+    }
+
+    fn index_supply(&self, index: &str, key: Option<&str>) -> u64 {
+        match key {
+            Some(key) => Self::slot_key_supply(index, key).read().unwrap_or(0),
+            None => Self::slot_index(index)
+                .read()
+                .map_or(0, |entries| entries.len()),
+        }
+    }
+
+    fn index_tokens(
+        &self,
+        index: &str,
+        key: Option<&str>,
+        from_index: usize,
+        limit: Option<usize>,
+    ) -> Vec<TokenId> {
+        let Some(entries) = Self::slot_index(index).read() else {
+            return vec![];
+        };
+
+        let limit = limit.unwrap_or(usize::MAX);
+
+        match key {
+            Some(key) => entries
+                .range((
+                    Bound::Included((key.to_string(), String::new())),
+                    Bound::Unbounded,
+                ))
+                .take_while(|((k, _), ())| k == key)
+                .skip(from_index)
+                .take(limit)
+                .map(|((_, token_id), ())| token_id)
+                .collect(),
+            None => entries
+                .iter()
+                .skip(from_index)
+                .take(limit)
+                .map(|((_, token_id), ())| token_id)
+                .collect(),
+        }
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::U128};
+
+    use crate::standard::nep171::Token;
+
+    /// External (public) methods for [`Nep181Index`](super::Nep181Index).
+    #[ext_contract(ext_nep181_index)]
+    pub trait Nep181IndexExternal {
+        /// Returns the number of tokens with `key` in `index`, or the number
+        /// of entries in `index` if `key` is omitted.
+        fn nft_supply_by_index(&self, index: String, key: Option<String>) -> U128;
+
+        /// Returns tokens with `key` in `index`, or all entries of `index`
+        /// ordered by key if `key` is omitted.
+        fn nft_tokens_by_index(
+            &self,
+            index: String,
+            key: Option<String>,
+            from_index: Option<U128>,
+            limit: Option<u32>,
+        ) -> Vec<Token>;
+    }
+}
@@ -11,6 +11,8 @@ use crate::{hook::Hook, slot::Slot, standard::nep171::*, DefaultStorageKey};
 
 pub use ext::*;
 
+pub mod index;
+
 /// Extension hook for [`Nep171Controller`].
 pub struct TokenEnumeration;
 
@@ -0,0 +1,90 @@
+//! NEP-393 actions.
+//!
+//! Used when calling various functions on [`Nep393Controller`]. Also used when
+//! implementing [`Hook`]s for the NEP-393 component.
+
+use std::borrow::Cow;
+
+use super::*;
+
+/// NEP-393 mint action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near]
+pub struct Nep393Mint<'a> {
+    /// Account ID of the receiver.
+    pub owner_id: Cow<'a, AccountIdRef>,
+    /// Metadata of the tokens to mint, one token per entry.
+    pub metadata: Vec<TokenMetadata>,
+}
+
+impl<'a> Nep393Mint<'a> {
+    /// Create a new mint action.
+    pub fn new(owner_id: impl Into<Cow<'a, AccountIdRef>>, metadata: Vec<TokenMetadata>) -> Self {
+        Self {
+            owner_id: owner_id.into(),
+            metadata,
+        }
+    }
+}
+
+/// NEP-393 revoke action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near]
+pub struct Nep393Revoke {
+    /// Token IDs to revoke.
+    pub token_ids: Vec<TokenId>,
+    /// Whether to burn (remove) the tokens, or only expire them.
+    pub burn: bool,
+}
+
+impl Nep393Revoke {
+    /// Create a new revoke action.
+    #[must_use]
+    pub fn new(token_ids: Vec<TokenId>, burn: bool) -> Self {
+        Self { token_ids, burn }
+    }
+}
+
+/// NEP-393 renew action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near]
+pub struct Nep393Renew {
+    /// Token IDs to renew.
+    pub token_ids: Vec<TokenId>,
+    /// New expiration time, in milliseconds since the Unix epoch.
+    pub expires_at: u64,
+}
+
+impl Nep393Renew {
+    /// Create a new renew action.
+    #[must_use]
+    pub fn new(token_ids: Vec<TokenId>, expires_at: u64) -> Self {
+        Self {
+            token_ids,
+            expires_at,
+        }
+    }
+}
+
+/// NEP-393 recover action. Moves all tokens of one account to another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near]
+pub struct Nep393Recover<'a> {
+    /// Account ID of the current (lost) owner.
+    pub old_owner_id: Cow<'a, AccountIdRef>,
+    /// Account ID of the new owner.
+    pub new_owner_id: Cow<'a, AccountIdRef>,
+}
+
+impl<'a> Nep393Recover<'a> {
+    /// Create a new recover action.
+    pub fn new(
+        old_owner_id: impl Into<Cow<'a, AccountIdRef>>,
+        new_owner_id: impl Into<Cow<'a, AccountIdRef>>,
+    ) -> Self {
+        Self {
+            old_owner_id: old_owner_id.into(),
+            new_owner_id: new_owner_id.into(),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Potential errors produced by NEP-393 token operations.
+
+use near_sdk::AccountId;
+use thiserror::Error;
+
+use super::{ClassId, TokenId};
+
+/// Potential errors encountered when minting tokens.
+#[derive(Error, Clone, Debug)]
+pub enum Nep393MintError {
+    /// The class ID is not valid.
+    #[error(transparent)]
+    InvalidClass(#[from] InvalidClassError),
+    /// The owner already holds a token of the class.
+    #[error(transparent)]
+    ClassAlreadyOwned(#[from] ClassAlreadyOwnedError),
+}
+
+/// Potential errors encountered when revoking tokens.
+#[derive(Error, Clone, Debug)]
+pub enum Nep393RevokeError {
+    /// The token does not exist.
+    #[error(transparent)]
+    TokenDoesNotExist(#[from] TokenDoesNotExistError),
+}
+
+/// Potential errors encountered when renewing tokens.
+#[derive(Error, Clone, Debug)]
+pub enum Nep393RenewError {
+    /// The token does not exist.
+    #[error(transparent)]
+    TokenDoesNotExist(#[from] TokenDoesNotExistError),
+}
+
+/// Potential errors encountered when recovering tokens.
+#[derive(Error, Clone, Debug)]
+pub enum Nep393RecoverError {
+    /// The old and new owners are the same account.
+    #[error(transparent)]
+    RecoverToSelf(#[from] RecoverToSelfError),
+    /// The new owner already holds a token of a class held by the old owner.
+    #[error(transparent)]
+    ClassAlreadyOwned(#[from] ClassAlreadyOwnedError),
+}
+
+/// Occurs when using a class ID of zero, which is reserved.
+#[derive(Error, Clone, Debug)]
+#[error("Class ID must be positive, got {class}")]
+pub struct InvalidClassError {
+    /// The invalid class ID.
+    pub class: ClassId,
+}
+
+/// Occurs when an account would hold more than one token of the same class.
+#[derive(Error, Clone, Debug)]
+#[error("Account {owner_id} already owns a token of class {class}")]
+pub struct ClassAlreadyOwnedError {
+    /// The account that owns a token of the class.
+    pub owner_id: AccountId,
+    /// The class ID.
+    pub class: ClassId,
+}
+
+/// Occurs when a token is referenced that does not exist.
+#[derive(Error, Clone, Debug)]
+#[error("Token {token_id} does not exist")]
+pub struct TokenDoesNotExistError {
+    /// The token ID.
+    pub token_id: TokenId,
+}
+
+/// Occurs when trying to recover tokens to the account that already owns them.
+#[derive(Error, Clone, Debug)]
+#[error("Cannot recover tokens of {account_id} to itself")]
+pub struct RecoverToSelfError {
+    /// The account ID.
+    pub account_id: AccountId,
+}
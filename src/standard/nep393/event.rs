@@ -0,0 +1,56 @@
+//! NEP-393 event log metadata & associated structures.
+
+use near_sdk::AccountId;
+use near_sdk_contract_tools_macros::event;
+
+use super::TokenId;
+
+/// NEP-393 standard events.
+#[event(
+    crate = "crate",
+    macros = "near_sdk_contract_tools_macros",
+    standard = "nep393",
+    version = "1.0.0"
+)]
+#[derive(Debug, Clone)]
+pub enum Nep393Event {
+    /// Emitted when tokens are minted.
+    Mint {
+        /// The issuer of the tokens.
+        issuer: AccountId,
+        /// Minted token IDs, grouped by owner.
+        tokens: Vec<(AccountId, Vec<TokenId>)>,
+    },
+    /// Emitted when the tokens of an account are recovered to a new account.
+    Recover {
+        /// The issuer of the tokens.
+        issuer: AccountId,
+        /// The previous owner of the tokens.
+        old_owner: AccountId,
+        /// The new owner of the tokens.
+        new_owner: AccountId,
+        /// Recovered token IDs.
+        tokens: Vec<TokenId>,
+    },
+    /// Emitted when the expiration time of tokens is updated.
+    Renew {
+        /// The issuer of the tokens.
+        issuer: AccountId,
+        /// Renewed token IDs.
+        tokens: Vec<TokenId>,
+    },
+    /// Emitted when tokens are revoked (expired) without being burned.
+    Revoke {
+        /// The issuer of the tokens.
+        issuer: AccountId,
+        /// Revoked token IDs.
+        tokens: Vec<TokenId>,
+    },
+    /// Emitted when tokens are burned.
+    Burn {
+        /// The issuer of the tokens.
+        issuer: AccountId,
+        /// Burned token IDs.
+        tokens: Vec<TokenId>,
+    },
+}
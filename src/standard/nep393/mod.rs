@@ -0,0 +1,663 @@
+//! NEP-393 soulbound token (SBT) issuer implementation.
+//!
+//! Reference: <https://github.com/near/NEPs/blob/master/neps/nep-0393.md>
+//!
+//! The contract acts as both the issuer and the registry of its tokens, so
+//! the `issuer` parameter of registry queries must be the contract's own
+//! account ID.
+//!
+//! Soulbound tokens cannot be transferred by their owners. Each owner may hold
+//! at most one token of each class. The issuer may revoke (expire or burn),
+//! renew, and recover tokens, where recovery moves all tokens of a lost
+//! account to a new account.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The NEP-393 root storage slot is not used or modified. The default
+//!     key is `~$393`.
+//! * (ERR) Class IDs are positive.
+//! * (ERR) An account holds at most one token of each class.
+//!
+//! Banning accounts and soul transfers are not implemented.
+
+use std::collections::HashSet;
+
+use near_sdk::{
+    borsh::BorshSerialize, collections::UnorderedSet, env, json_types::Base64VecU8, near,
+    AccountId, AccountIdRef, BorshStorageKey,
+};
+
+use crate::{hook::Hook, slot::Slot, standard::nep297::Event, DefaultStorageKey};
+
+pub mod action;
+use action::*;
+pub mod error;
+use error::*;
+pub mod event;
+use event::*;
+
+pub use ext::*;
+
+/// Maximum number of token IDs examined by a single `sbt_tokens` call.
+/// Burned tokens, and expired tokens unless requested, are skipped but still
+/// count towards this bound, so a call may return fewer than `limit` tokens
+/// before the last token.
+pub const MAX_SBT_TOKENS_SCANNED: u64 = 500;
+
+/// Token IDs. Assigned sequentially, starting at 1.
+pub type TokenId = u64;
+
+/// Token class IDs. Must be positive.
+pub type ClassId = u64;
+
+/// Metadata of a soulbound token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct TokenMetadata {
+    /// Class of the token.
+    pub class: ClassId,
+    /// When the token was issued, in milliseconds since the Unix epoch.
+    /// Defaults to the time of minting.
+    pub issued_at: Option<u64>,
+    /// When the token expires, in milliseconds since the Unix epoch.
+    pub expires_at: Option<u64>,
+    /// URL to an off-chain JSON file with more info.
+    pub reference: Option<String>,
+    /// Base64-encoded sha256 hash of the JSON file contained in the reference
+    /// field. Required if `reference` is included.
+    pub reference_hash: Option<Base64VecU8>,
+}
+
+impl TokenMetadata {
+    /// Create new metadata of a token of the given class.
+    #[must_use]
+    pub fn new(class: ClassId) -> Self {
+        Self {
+            class,
+            issued_at: None,
+            expires_at: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+
+    /// Set the issue time, in milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn issued_at(mut self, issued_at: u64) -> Self {
+        self.issued_at = Some(issued_at);
+        self
+    }
+
+    /// Set the expiration time, in milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn expires_at(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Set the reference and reference hash.
+    #[must_use]
+    pub fn reference(mut self, reference: impl Into<String>, reference_hash: Vec<u8>) -> Self {
+        self.reference = Some(reference.into());
+        self.reference_hash = Some(reference_hash.into());
+        self
+    }
+
+    /// Returns `true` if the token has expired at the given time, in
+    /// milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= now_ms)
+    }
+}
+
+/// A soulbound token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct Token {
+    /// Token ID.
+    pub token: TokenId,
+    /// Current owner of the token.
+    pub owner: AccountId,
+    /// Token metadata.
+    pub metadata: TokenMetadata,
+}
+
+/// A soulbound token, as seen from its owner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct OwnedToken {
+    /// Token ID.
+    pub token: TokenId,
+    /// Token metadata.
+    pub metadata: TokenMetadata,
+}
+
+impl From<Token> for OwnedToken {
+    fn from(token: Token) -> Self {
+        Self {
+            token: token.token,
+            metadata: token.metadata,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey<'a> {
+    LastTokenId,
+    Supply,
+    Token(TokenId),
+    ClassSupply(ClassId),
+    OwnerClass(&'a AccountIdRef, ClassId),
+    OwnerTokens(&'a AccountIdRef),
+}
+
+/// Internal (storage location) methods for implementors of
+/// [`Nep393Controller`].
+pub trait Nep393ControllerInternal {
+    /// Hook for mint operations.
+    type MintHook: for<'a> Hook<Self, Nep393Mint<'a>>
+    where
+        Self: Sized;
+    /// Hook for revoke operations.
+    type RevokeHook: Hook<Self, Nep393Revoke>
+    where
+        Self: Sized;
+    /// Hook for renew operations.
+    type RenewHook: Hook<Self, Nep393Renew>
+    where
+        Self: Sized;
+    /// Hook for recover operations.
+    type RecoverHook: for<'a> Hook<Self, Nep393Recover<'a>>
+    where
+        Self: Sized;
+
+    /// Root storage slot.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::Nep393)
+    }
+
+    /// Storage slot for the most recently minted token ID.
+    #[must_use]
+    fn slot_last_token_id() -> Slot<TokenId> {
+        Self::root().field(StorageKey::LastTokenId)
+    }
+
+    /// Storage slot for the number of tokens in existence.
+    #[must_use]
+    fn slot_supply() -> Slot<u64> {
+        Self::root().field(StorageKey::Supply)
+    }
+
+    /// Storage slot for a token.
+    #[must_use]
+    fn slot_token(token_id: TokenId) -> Slot<Token> {
+        Self::root().field(StorageKey::Token(token_id))
+    }
+
+    /// Storage slot for the number of tokens of a class in existence.
+    #[must_use]
+    fn slot_class_supply(class: ClassId) -> Slot<u64> {
+        Self::root().field(StorageKey::ClassSupply(class))
+    }
+
+    /// Storage slot for the token of a class held by an account.
+    #[must_use]
+    fn slot_owner_class(owner_id: &AccountIdRef, class: ClassId) -> Slot<TokenId> {
+        Self::root().field(StorageKey::OwnerClass(owner_id, class))
+    }
+
+    /// Storage slot for the tokens held by an account.
+    #[must_use]
+    fn slot_owner_tokens(owner_id: &AccountIdRef) -> Slot<UnorderedSet<TokenId>> {
+        Self::root().field(StorageKey::OwnerTokens(owner_id))
+    }
+}
+
+/// Non-public controller interface for NEP-393 implementations.
+pub trait Nep393Controller {
+    /// Hook for mint operations.
+    type MintHook: for<'a> Hook<Self, Nep393Mint<'a>>
+    where
+        Self: Sized;
+    /// Hook for revoke operations.
+    type RevokeHook: Hook<Self, Nep393Revoke>
+    where
+        Self: Sized;
+    /// Hook for renew operations.
+    type RenewHook: Hook<Self, Nep393Renew>
+    where
+        Self: Sized;
+    /// Hook for recover operations.
+    type RecoverHook: for<'a> Hook<Self, Nep393Recover<'a>>
+    where
+        Self: Sized;
+
+    /// Returns a token, if it exists.
+    fn token(&self, token_id: TokenId) -> Option<Token>;
+
+    /// Returns the token of a class held by an account, if any.
+    fn token_by_owner_class(&self, owner_id: &AccountIdRef, class: ClassId) -> Option<Token>;
+
+    /// Returns all tokens held by an account, ordered by class.
+    fn tokens_for_owner(&self, owner_id: &AccountIdRef) -> Vec<Token>;
+
+    /// Returns the most recently minted token ID, or 0 if no tokens have
+    /// been minted.
+    fn last_token_id(&self) -> TokenId;
+
+    /// Returns the number of tokens in existence, including expired tokens.
+    fn supply(&self) -> u64;
+
+    /// Returns the number of tokens of a class in existence, including
+    /// expired tokens.
+    fn supply_by_class(&self, class: ClassId) -> u64;
+
+    /// Returns the number of tokens held by an account, including expired
+    /// tokens.
+    fn supply_by_owner(&self, owner_id: &AccountIdRef) -> u64;
+
+    /// Mints one token per metadata entry to the owner. Tokens without an
+    /// issue time are issued at the current block time. Emits events and
+    /// runs relevant hooks. Returns the new token IDs.
+    ///
+    /// # Errors
+    ///
+    /// - If a class ID is zero.
+    /// - If the owner would hold more than one token of a class.
+    fn mint(&mut self, action: &Nep393Mint<'_>) -> Result<Vec<TokenId>, Nep393MintError>;
+
+    /// Revokes tokens. Burned tokens are removed; otherwise, unexpired tokens
+    /// expire at the current block time. Emits events and runs relevant
+    /// hooks.
+    ///
+    /// # Errors
+    ///
+    /// - If a token does not exist.
+    fn revoke(&mut self, action: &Nep393Revoke) -> Result<(), Nep393RevokeError>;
+
+    /// Sets the expiration time of tokens. Emits events and runs relevant
+    /// hooks.
+    ///
+    /// # Errors
+    ///
+    /// - If a token does not exist.
+    fn renew(&mut self, action: &Nep393Renew) -> Result<(), Nep393RenewError>;
+
+    /// Moves all tokens held by one account to another. Emits events and runs
+    /// relevant hooks. Returns the recovered token IDs.
+    ///
+    /// # Errors
+    ///
+    /// - If the old and new owners are the same account.
+    /// - If the new owner already holds a token of a class held by the old
+    ///     owner.
+    fn recover(&mut self, action: &Nep393Recover<'_>) -> Result<Vec<TokenId>, Nep393RecoverError>;
+}
+
+fn insert_token<C: Nep393ControllerInternal>(token: &Token) {
+    C::slot_token(token.token).write(token);
+    C::slot_owner_class(&token.owner, token.metadata.class).write(&token.token);
+
+    let mut owner_tokens_slot = C::slot_owner_tokens(&token.owner);
+    let mut owner_tokens = owner_tokens_slot
+        .read()
+        .unwrap_or_else(|| UnorderedSet::new(owner_tokens_slot.key.clone()));
+    owner_tokens.insert(&token.token);
+    owner_tokens_slot.write(&owner_tokens);
+
+    let mut class_supply = C::slot_class_supply(token.metadata.class);
+    class_supply.write(&(class_supply.read().unwrap_or(0) + 1));
+
+    let mut supply = C::slot_supply();
+    supply.write(&(supply.read().unwrap_or(0) + 1));
+}
+
+fn remove_token<C: Nep393ControllerInternal>(token: &Token) {
+    if !C::slot_token(token.token).remove() {
+        return;
+    }
+
+    C::slot_owner_class(&token.owner, token.metadata.class).remove();
+
+    let mut owner_tokens_slot = C::slot_owner_tokens(&token.owner);
+    if let Some(mut owner_tokens) = owner_tokens_slot.read() {
+        owner_tokens.remove(&token.token);
+        if owner_tokens.is_empty() {
+            owner_tokens_slot.remove();
+        } else {
+            owner_tokens_slot.write(&owner_tokens);
+        }
+    }
+
+    let mut class_supply = C::slot_class_supply(token.metadata.class);
+    match class_supply.read().unwrap_or(0) {
+        0 | 1 => class_supply.remove(),
+        n => class_supply.write(&(n - 1)),
+    };
+
+    let mut supply = C::slot_supply();
+    supply.write(&supply.read().unwrap_or(0).saturating_sub(1));
+}
+
+fn load_tokens<C: Nep393Controller>(
+    contract: &C,
+    token_ids: &[TokenId],
+) -> Result<Vec<Token>, TokenDoesNotExistError> {
+    token_ids
+        .iter()
+        .map(|&token_id| {
+            contract
+                .token(token_id)
+                .ok_or(TokenDoesNotExistError { token_id })
+        })
+        .collect()
+}
+
+impl<T: Nep393ControllerInternal> Nep393Controller for T {
+    type MintHook = <Self as Nep393ControllerInternal>::MintHook;
+    type RevokeHook = <Self as Nep393ControllerInternal>::RevokeHook;
+    type RenewHook = <Self as Nep393ControllerInternal>::RenewHook;
+    type RecoverHook = <Self as Nep393ControllerInternal>::RecoverHook;
+
+    fn token(&self, token_id: TokenId) -> Option<Token> {
+        Self::slot_token(token_id).read()
+    }
+
+    fn token_by_owner_class(&self, owner_id: &AccountIdRef, class: ClassId) -> Option<Token> {
+        Self::slot_owner_class(owner_id, class)
+            .read()
+            .and_then(|token_id| self.token(token_id))
+    }
+
+    fn tokens_for_owner(&self, owner_id: &AccountIdRef) -> Vec<Token> {
+        let mut tokens = Self::slot_owner_tokens(owner_id)
+            .read()
+            .map(|owner_tokens| {
+                owner_tokens
+                    .iter()
+                    .filter_map(|token_id| self.token(token_id))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        tokens.sort_by_key(|token| token.metadata.class);
+
+        tokens
+    }
+
+    fn last_token_id(&self) -> TokenId {
+        Self::slot_last_token_id().read().unwrap_or(0)
+    }
+
+    fn supply(&self) -> u64 {
+        Self::slot_supply().read().unwrap_or(0)
+    }
+
+    fn supply_by_class(&self, class: ClassId) -> u64 {
+        Self::slot_class_supply(class).read().unwrap_or(0)
+    }
+
+    fn supply_by_owner(&self, owner_id: &AccountIdRef) -> u64 {
+        Self::slot_owner_tokens(owner_id)
+            .read()
+            .map_or(0, |owner_tokens| owner_tokens.len())
+    }
+
+    fn mint(&mut self, action: &Nep393Mint<'_>) -> Result<Vec<TokenId>, Nep393MintError> {
+        if action.metadata.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut classes = HashSet::new();
+        for metadata in &action.metadata {
+            let class = metadata.class;
+
+            if class == 0 {
+                return Err(InvalidClassError { class }.into());
+            }
+
+            if !classes.insert(class) || Self::slot_owner_class(&action.owner_id, class).exists() {
+                return Err(ClassAlreadyOwnedError {
+                    owner_id: action.owner_id.clone().into(),
+                    class,
+                }
+                .into());
+            }
+        }
+
+        Self::MintHook::hook(self, action, |contract| {
+            let now = env::block_timestamp_ms();
+            let mut token_id = contract.last_token_id();
+            let mut token_ids = Vec::with_capacity(action.metadata.len());
+
+            for metadata in &action.metadata {
+                token_id = token_id
+                    .checked_add(1)
+                    .unwrap_or_else(|| env::panic_str("Token ID overflow"));
+
+                insert_token::<Self>(&Token {
+                    token: token_id,
+                    owner: action.owner_id.clone().into(),
+                    metadata: TokenMetadata {
+                        issued_at: metadata.issued_at.or(Some(now)),
+                        ..metadata.clone()
+                    },
+                });
+
+                token_ids.push(token_id);
+            }
+
+            Self::slot_last_token_id().write(&token_id);
+
+            Nep393Event::Mint {
+                issuer: env::current_account_id(),
+                tokens: vec![(action.owner_id.clone().into(), token_ids.clone())],
+            }
+            .emit();
+
+            Ok(token_ids)
+        })
+    }
+
+    fn revoke(&mut self, action: &Nep393Revoke) -> Result<(), Nep393RevokeError> {
+        if action.token_ids.is_empty() {
+            return Ok(());
+        }
+
+        let tokens = load_tokens(self, &action.token_ids)?;
+
+        Self::RevokeHook::hook(self, action, |_| {
+            let issuer = env::current_account_id();
+
+            if action.burn {
+                for token in &tokens {
+                    remove_token::<Self>(token);
+                }
+
+                Nep393Event::Burn {
+                    issuer,
+                    tokens: action.token_ids.clone(),
+                }
+                .emit();
+            } else {
+                let now = env::block_timestamp_ms();
+
+                for mut token in tokens {
+                    if !token.metadata.is_expired(now) {
+                        token.metadata.expires_at = Some(now);
+                        Self::slot_token(token.token).write(&token);
+                    }
+                }
+
+                Nep393Event::Revoke {
+                    issuer,
+                    tokens: action.token_ids.clone(),
+                }
+                .emit();
+            }
+
+            Ok(())
+        })
+    }
+
+    fn renew(&mut self, action: &Nep393Renew) -> Result<(), Nep393RenewError> {
+        if action.token_ids.is_empty() {
+            return Ok(());
+        }
+
+        let tokens = load_tokens(self, &action.token_ids)?;
+
+        Self::RenewHook::hook(self, action, |_| {
+            for mut token in tokens {
+                token.metadata.expires_at = Some(action.expires_at);
+                Self::slot_token(token.token).write(&token);
+            }
+
+            Nep393Event::Renew {
+                issuer: env::current_account_id(),
+                tokens: action.token_ids.clone(),
+            }
+            .emit();
+
+            Ok(())
+        })
+    }
+
+    fn recover(&mut self, action: &Nep393Recover<'_>) -> Result<Vec<TokenId>, Nep393RecoverError> {
+        if action.old_owner_id == action.new_owner_id {
+            return Err(RecoverToSelfError {
+                account_id: action.old_owner_id.clone().into(),
+            }
+            .into());
+        }
+
+        let tokens = self.tokens_for_owner(&action.old_owner_id);
+
+        for token in &tokens {
+            let class = token.metadata.class;
+            if Self::slot_owner_class(&action.new_owner_id, class).exists() {
+                return Err(ClassAlreadyOwnedError {
+                    owner_id: action.new_owner_id.clone().into(),
+                    class,
+                }
+                .into());
+            }
+        }
+
+        Self::RecoverHook::hook(self, action, |_| {
+            let token_ids = tokens.iter().map(|token| token.token).collect::<Vec<_>>();
+
+            for mut token in tokens {
+                remove_token::<Self>(&token);
+                token.owner = action.new_owner_id.clone().into();
+                insert_token::<Self>(&token);
+            }
+
+            Nep393Event::Recover {
+                issuer: env::current_account_id(),
+                old_owner: action.old_owner_id.clone().into(),
+                new_owner: action.new_owner_id.clone().into(),
+                tokens: token_ids.clone(),
+            }
+            .emit();
+
+            Ok(token_ids)
+        })
+    }
+}
+
+/// Authorization of the issuer for the external NEP-393 interface.
+pub trait Nep393Authority {
+    /// Panics if the predecessor is not allowed to mint, revoke, renew, or
+    /// recover tokens.
+    fn require_issuer(&self);
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, AccountId};
+
+    use super::{ClassId, OwnedToken, Token, TokenId, TokenMetadata};
+
+    /// Interface of contracts that implement NEP-393 as both issuer and
+    /// registry. Queries return nothing for issuers other than the contract
+    /// itself.
+    #[ext_contract(ext_nep393)]
+    pub trait Nep393 {
+        /// Mints tokens to their owners. The attached deposit must cover the
+        /// storage cost; any excess is refunded. Returns the new token IDs.
+        fn sbt_mint(&mut self, token_spec: Vec<(AccountId, Vec<TokenMetadata>)>) -> Vec<TokenId>;
+
+        /// Moves all tokens of `from` to `to`. The attached deposit must cover
+        /// the storage cost; any excess is refunded. Returns the number of
+        /// recovered tokens and whether the recovery completed. Recovery is
+        /// never partial: all tokens are moved in a single call, so the second
+        /// value is always `true`. An account holds at most one token of each
+        /// class, so the work is bounded by the number of classes.
+        fn sbt_recover(&mut self, from: AccountId, to: AccountId) -> (u32, bool);
+
+        /// Sets the expiration time of tokens, in milliseconds since the Unix
+        /// epoch. Requires a deposit of exactly 1 yoctoNEAR.
+        fn sbt_renew(&mut self, tokens: Vec<TokenId>, expires_at: u64);
+
+        /// Revokes tokens. If `burn` is `true`, the tokens are removed;
+        /// otherwise, they expire immediately. Requires a deposit of exactly 1
+        /// yoctoNEAR.
+        fn sbt_revoke(&mut self, tokens: Vec<TokenId>, burn: bool);
+
+        /// Returns a token, if it exists.
+        fn sbt(&self, issuer: AccountId, token: TokenId) -> Option<Token>;
+
+        /// Returns the number of tokens in existence.
+        fn sbt_supply(&self, issuer: AccountId) -> u64;
+
+        /// Returns the number of tokens held by an account, optionally only
+        /// of the given class.
+        fn sbt_supply_by_owner(
+            &self,
+            account: AccountId,
+            issuer: AccountId,
+            class: Option<ClassId>,
+        ) -> u64;
+
+        /// Returns the number of tokens of a class in existence.
+        fn sbt_supply_by_class(&self, issuer: AccountId, class: ClassId) -> u64;
+
+        /// Returns tokens ordered by ID, starting at `from_token` (default:
+        /// 1), up to `limit` (default: all) tokens. Expired tokens are only
+        /// included if `with_expired` is `true`. At most
+        /// [`MAX_SBT_TOKENS_SCANNED`](super::MAX_SBT_TOKENS_SCANNED) token IDs
+        /// are examined per call.
+        fn sbt_tokens(
+            &self,
+            issuer: AccountId,
+            from_token: Option<TokenId>,
+            limit: Option<u32>,
+            with_expired: bool,
+        ) -> Vec<Token>;
+
+        /// Returns the tokens held by an account, grouped by issuer and
+        /// ordered by class, starting at `from_class` (default: 0), up to
+        /// `limit` (default: all) tokens. Expired tokens are only included if
+        /// `with_expired` is `true`.
+        fn sbt_tokens_by_owner(
+            &self,
+            account: AccountId,
+            issuer: Option<AccountId>,
+            from_class: Option<ClassId>,
+            limit: Option<u32>,
+            with_expired: bool,
+        ) -> Vec<(AccountId, Vec<OwnedToken>)>;
+    }
+}
@@ -0,0 +1,347 @@
+//! NFT rentals: a time-limited "user" role separate from the owner, in the
+//! style of ERC-4907.
+//!
+//! The owner of a token may assign a user until an expiration time. While the
+//! rental is active, the owner cannot replace or remove the user, but may
+//! extend the rental of the current user. Rentals are cleared when the token
+//! is transferred or burned.
+//!
+//! [`TokenRental`] integrates with NEP-171: it implements transfer and burn
+//! hooks that clear the rental, and it loads the active rental into
+//! [`Token::extensions_metadata`](crate::standard::nep171::Token) under the
+//! `"rental"` key.
+//!
+//! # Safety
+//!
+//! The default implementation assumes or enforces the following invariants.
+//! Violating assumed invariants may corrupt contract state and show unexpected
+//! behavior (UB). Enforced invariants throw an error (ERR) but contract
+//! state remains intact.
+//!
+//! * (UB) The rental root storage slot is not used or modified. The default
+//!     key is `~$171r`.
+//! * (UB) [`TokenRental`] is installed as a NEP-171 transfer and burn hook.
+//!     Otherwise, users of a token are kept after it changes hands.
+//! * (ERR) Only the owner of a token may assign its user.
+//! * (ERR) The user of an active rental cannot be replaced, and the rental
+//!     cannot be shortened.
+
+use std::{borrow::Cow, error::Error};
+
+use near_sdk::{
+    borsh::BorshSerialize, env, json_types::U64, near, AccountId, AccountIdRef, BorshStorageKey,
+};
+use near_sdk_contract_tools_macros::event;
+use thiserror::Error;
+
+use crate::{
+    hook::Hook,
+    slot::Slot,
+    standard::{
+        nep171::{
+            action::{Nep171Burn, Nep171Mint, Nep171Transfer},
+            error::{TokenDoesNotExistError, TokenNotOwnedByExpectedOwnerError},
+            LoadTokenMetadata, Nep171Controller, TokenId,
+        },
+        nep297::Event,
+    },
+    DefaultStorageKey,
+};
+
+pub use ext::*;
+
+/// The user of a token and the expiration time of the rental. Hooks are
+/// implemented on this struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct TokenRental {
+    /// The account that may use the token.
+    pub user_id: AccountId,
+    /// When the rental expires, in milliseconds since the Unix epoch.
+    pub expires_at: U64,
+}
+
+impl TokenRental {
+    /// Returns `true` if the rental has not expired at the given time, in
+    /// milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.expires_at.0 > now_ms
+    }
+}
+
+impl<C: NftRental> LoadTokenMetadata<C> for TokenRental {
+    fn load(
+        contract: &C,
+        token_id: &TokenId,
+        metadata: &mut std::collections::HashMap<String, near_sdk::serde_json::Value>,
+    ) -> Result<(), Box<dyn Error>> {
+        metadata.insert(
+            "rental".to_string(),
+            near_sdk::serde_json::to_value(contract.rental_of(token_id))?,
+        );
+        Ok(())
+    }
+}
+
+impl<C: NftRental> Hook<C, Nep171Mint<'_>> for TokenRental {}
+
+impl<C: NftRental> Hook<C, Nep171Transfer<'_>> for TokenRental {
+    fn hook<R>(contract: &mut C, args: &Nep171Transfer<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        let r = f(contract);
+        contract.clear_user(&args.token_id);
+        r
+    }
+}
+
+impl<C: NftRental> Hook<C, Nep171Burn<'_>> for TokenRental {
+    fn hook<R>(contract: &mut C, args: &Nep171Burn<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        let r = f(contract);
+        for token_id in &args.token_ids {
+            contract.clear_user(token_id);
+        }
+        r
+    }
+}
+
+/// Events emitted by the rental component.
+#[event(
+    standard = "x-nft-rental",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub enum NftRentalEvent {
+    /// Emitted when the user of a token is assigned or cleared.
+    UpdateUser {
+        /// The token ID.
+        token_id: TokenId,
+        /// The new user, if any.
+        user_id: Option<AccountId>,
+        /// When the rental expires, if there is a user.
+        expires_at: Option<U64>,
+    },
+}
+
+/// Set user action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near]
+pub struct NftSetUser<'a> {
+    /// The token ID.
+    pub token_id: TokenId,
+    /// Account ID of the current owner of the token.
+    pub current_owner_id: Cow<'a, AccountIdRef>,
+    /// Account ID of the new user.
+    pub user_id: Cow<'a, AccountIdRef>,
+    /// When the rental expires, in milliseconds since the Unix epoch.
+    pub expires_at: u64,
+}
+
+/// The token is rented out, and the rental cannot be changed as requested.
+#[derive(Error, Clone, Debug)]
+#[error("Token {token_id} is rented to {user_id} until {expires_at}")]
+pub struct RentalActiveError {
+    /// The token ID.
+    pub token_id: TokenId,
+    /// The current user.
+    pub user_id: AccountId,
+    /// When the current rental expires.
+    pub expires_at: u64,
+}
+
+/// The expiration time of a rental is not in the future.
+#[derive(Error, Clone, Debug)]
+#[error("Rental of token {token_id} must expire in the future, got {expires_at}")]
+pub struct RentalExpiryInPastError {
+    /// The token ID.
+    pub token_id: TokenId,
+    /// The requested expiration time.
+    pub expires_at: u64,
+}
+
+/// Errors that may occur when assigning the user of a token.
+#[derive(Error, Clone, Debug)]
+pub enum NftSetUserError {
+    /// The token does not exist.
+    #[error(transparent)]
+    TokenDoesNotExist(#[from] TokenDoesNotExistError),
+    /// The token is not owned by the expected owner.
+    #[error(transparent)]
+    TokenNotOwnedByExpectedOwner(#[from] TokenNotOwnedByExpectedOwnerError),
+    /// The token is rented out.
+    #[error(transparent)]
+    RentalActive(#[from] RentalActiveError),
+    /// The expiration time is not in the future.
+    #[error(transparent)]
+    RentalExpiryInPast(#[from] RentalExpiryInPastError),
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey<'a> {
+    Rental(&'a str),
+}
+
+/// Internal functions for [`NftRental`].
+pub trait NftRentalInternal {
+    /// Hook for set user operations.
+    type SetUserHook: for<'a> Hook<Self, NftSetUser<'a>>
+    where
+        Self: Sized;
+
+    /// Storage root.
+    #[must_use]
+    fn root() -> Slot<()> {
+        Slot::root(DefaultStorageKey::NftRental)
+    }
+
+    /// Storage slot for the rental of a token.
+    #[must_use]
+    fn slot_rental(token_id: &TokenId) -> Slot<TokenRental> {
+        Self::root().field(StorageKey::Rental(token_id))
+    }
+}
+
+/// Functions for managing NFT rentals.
+pub trait NftRental {
+    /// Hook for set user operations.
+    type SetUserHook: for<'a> Hook<Self, NftSetUser<'a>>
+    where
+        Self: Sized;
+
+    /// Returns the active rental of a token, if any.
+    fn rental_of(&self, token_id: &TokenId) -> Option<TokenRental>;
+
+    /// Returns the user of a token, if it is rented out.
+    fn user_of(&self, token_id: &TokenId) -> Option<AccountId> {
+        self.rental_of(token_id).map(|rental| rental.user_id)
+    }
+
+    /// Assigns the user of a token. Emits events and runs relevant hooks.
+    ///
+    /// # Errors
+    ///
+    /// - If the token does not exist.
+    /// - If the token is not owned by the expected owner.
+    /// - If the token is rented to a different user, or the new expiration
+    ///     time is earlier than the current one.
+    /// - If the expiration time is not in the future.
+    fn set_user(&mut self, action: &NftSetUser<'_>) -> Result<(), NftSetUserError>;
+
+    /// Assigns the user of a token without any checks. Does not emit events
+    /// or run hooks.
+    fn set_user_unchecked(&mut self, token_id: &TokenId, rental: &TokenRental);
+
+    /// Removes the rental of a token, regardless of whether it has expired.
+    /// Emits an event if the rental was active. Returns the removed rental.
+    fn clear_user(&mut self, token_id: &TokenId) -> Option<TokenRental>;
+}
+
+impl<T: NftRentalInternal + Nep171Controller> NftRental for T {
+    type SetUserHook = <Self as NftRentalInternal>::SetUserHook;
+
+    fn rental_of(&self, token_id: &TokenId) -> Option<TokenRental> {
+        Self::slot_rental(token_id)
+            .read()
+            .filter(|rental| rental.is_active(env::block_timestamp_ms()))
+    }
+
+    fn set_user(&mut self, action: &NftSetUser<'_>) -> Result<(), NftSetUserError> {
+        let owner_id =
+            self.token_owner(&action.token_id)
+                .ok_or_else(|| TokenDoesNotExistError {
+                    token_id: action.token_id.clone(),
+                })?;
+
+        if owner_id != action.current_owner_id.as_ref() {
+            return Err(TokenNotOwnedByExpectedOwnerError {
+                expected_owner_id: action.current_owner_id.clone().into(),
+                owner_id,
+                token_id: action.token_id.clone(),
+            }
+            .into());
+        }
+
+        if action.expires_at <= env::block_timestamp_ms() {
+            return Err(RentalExpiryInPastError {
+                token_id: action.token_id.clone(),
+                expires_at: action.expires_at,
+            }
+            .into());
+        }
+
+        if let Some(rental) = self.rental_of(&action.token_id) {
+            if rental.user_id != action.user_id.as_ref() || rental.expires_at.0 > action.expires_at
+            {
+                return Err(RentalActiveError {
+                    token_id: action.token_id.clone(),
+                    user_id: rental.user_id,
+                    expires_at: rental.expires_at.0,
+                }
+                .into());
+            }
+        }
+
+        Self::SetUserHook::hook(self, action, |contract| {
+            let rental = TokenRental {
+                user_id: action.user_id.clone().into(),
+                expires_at: U64(action.expires_at),
+            };
+
+            contract.set_user_unchecked(&action.token_id, &rental);
+
+            NftRentalEvent::UpdateUser {
+                token_id: action.token_id.clone(),
+                user_id: Some(rental.user_id),
+                expires_at: Some(rental.expires_at),
+            }
+            .emit();
+
+            Ok(())
+        })
+    }
+
+    fn set_user_unchecked(&mut self, token_id: &TokenId, rental: &TokenRental) {
+        Self::slot_rental(token_id).write(rental);
+    }
+
+    fn clear_user(&mut self, token_id: &TokenId) -> Option<TokenRental> {
+        let rental = Self::slot_rental(token_id).take()?;
+
+        if rental.is_active(env::block_timestamp_ms()) {
+            NftRentalEvent::UpdateUser {
+                token_id: token_id.clone(),
+                user_id: None,
+                expires_at: None,
+            }
+            .emit();
+        }
+
+        Some(rental)
+    }
+}
+
+// separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
+mod ext {
+    #![allow(missing_docs)]
+
+    use near_sdk::{ext_contract, json_types::U64, AccountId};
+
+    use crate::standard::nep171::TokenId;
+
+    /// External (public) methods for [`NftRental`](super::NftRental).
+    #[ext_contract(ext_nft_rental)]
+    pub trait NftRentalExternal {
+        /// Assigns the user of a token until `expires_at` (milliseconds since
+        /// the Unix epoch). Only callable by the owner of the token. Requires
+        /// exactly 1 yoctoNEAR attached.
+        fn nft_set_user(&mut self, token_id: TokenId, user_id: AccountId, expires_at: U64);
+
+        /// Returns the user of a token, if it is rented out.
+        fn nft_user_of(&self, token_id: TokenId) -> Option<AccountId>;
+
+        /// Returns when the rental of a token expires, if it is rented out.
+        fn nft_user_expires(&self, token_id: TokenId) -> Option<U64>;
+    }
+}
@@ -34,6 +34,8 @@ use near_sdk::Gas;
 /// Default value for the name of the function that will be called after
 /// upgrade (usually a migrate function).
 pub const DEFAULT_POST_UPGRADE_METHOD_NAME: &str = "migrate";
+/// Name of the function that starts a batched migration.
+pub const BATCHED_MIGRATION_START_METHOD_NAME: &str = "migrate_start";
 /// Default input to send to the post-upgrade function.
 pub const DEFAULT_POST_UPGRADE_METHOD_ARGS: Vec<u8> = vec![];
 /// Guarantee the post-upgrade function receives at least this much gas by
@@ -55,6 +57,24 @@ pub struct PostUpgrade {
     pub minimum_gas: Gas,
 }
 
+impl PostUpgrade {
+    /// Starts a [batched migration](crate::migrate::BatchedMigrateController)
+    /// after the upgrade, and performs its first step with the given `limit`.
+    ///
+    /// # Panics
+    ///
+    /// If JSON serialization fails.
+    #[must_use]
+    pub fn batched_migration(limit: u32) -> Self {
+        Self {
+            method: BATCHED_MIGRATION_START_METHOD_NAME.to_string(),
+            args: near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({ "limit": limit }))
+                .unwrap(),
+            ..Self::default()
+        }
+    }
+}
+
 impl Default for PostUpgrade {
     fn default() -> Self {
         Self {
@@ -93,9 +93,98 @@ pub fn assert_nonzero_deposit() {
     );
 }
 
+/// Calculates `a * b / denominator` without intermediate overflow, rounding
+/// the result up if `round_up` is `true`, and down otherwise. Returns `None`
+/// if `denominator` is zero or the result does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk_contract_tools::utils::mul_div;
+///
+/// assert_eq!(mul_div(u128::MAX, 6, 9, false), Some(u128::MAX / 3 * 2));
+/// assert_eq!(mul_div(10, 1, 3, false), Some(3));
+/// assert_eq!(mul_div(10, 1, 3, true), Some(4));
+/// assert_eq!(mul_div(u128::MAX, 2, 1, false), None);
+/// ```
+#[must_use]
+pub fn mul_div(a: u128, b: u128, denominator: u128, round_up: bool) -> Option<u128> {
+    const LOW_MASK: u128 = u64::MAX as u128;
+
+    if denominator == 0 {
+        return None;
+    }
+
+    // 256-bit product as (high, low)
+    let (a_hi, a_lo) = (a >> 64, a & LOW_MASK);
+    let (b_hi, b_lo) = (b >> 64, b & LOW_MASK);
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+    let mid = (lo_lo >> 64) + (lo_hi & LOW_MASK) + (hi_lo & LOW_MASK);
+    let low = (lo_lo & LOW_MASK) | (mid << 64);
+    let high = hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64);
+
+    let (quotient, remainder) = if high == 0 {
+        (low / denominator, low % denominator)
+    } else if high >= denominator {
+        return None;
+    } else {
+        // binary long division; the remainder is always less than the denominator
+        let mut quotient = 0u128;
+        let mut remainder = high;
+        for i in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((low >> i) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= denominator {
+                remainder = remainder.wrapping_sub(denominator);
+                quotient |= 1;
+            }
+        }
+        (quotient, remainder)
+    };
+
+    if round_up && remainder != 0 {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::prefix_key;
+    use super::{mul_div, prefix_key};
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(0, 5, 7, true), Some(0));
+        assert_eq!(mul_div(5, 7, 0, false), None);
+        assert_eq!(mul_div(7, 3, 2, false), Some(10));
+        assert_eq!(mul_div(7, 3, 2, true), Some(11));
+        assert_eq!(
+            mul_div(u128::MAX, u128::MAX, u128::MAX, false),
+            Some(u128::MAX)
+        );
+        assert_eq!(
+            mul_div(u128::MAX, u128::MAX - 1, u128::MAX, true),
+            Some(u128::MAX - 1)
+        );
+        assert_eq!(
+            mul_div(u128::MAX - 1, u128::MAX, u128::MAX - 2, false),
+            None,
+        );
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90, false), Some(1 << 110),);
+        assert_eq!(
+            mul_div((1 << 100) + 1, 1 << 100, 1 << 90, true),
+            Some((1 << 110) + (1 << 10)),
+        );
+        assert_eq!(
+            mul_div((1 << 100) + 1, (1 << 100) + 1, 1 << 90, true),
+            Some((1 << 110) + (1 << 11) + 1),
+        );
+    }
 
     #[test]
     fn test_prefix_key() {
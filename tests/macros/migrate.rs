@@ -1,5 +1,9 @@
-use near_sdk::{env, near, PanicOnDefault};
-use near_sdk_contract_tools::{migrate::MigrateHook, Migrate};
+use near_sdk::{env, near, test_utils::get_logs, PanicOnDefault};
+use near_sdk_contract_tools::{
+    migrate::{MigrateEvent, MigrateHook, MigrateInternal},
+    standard::nep297::Event,
+    Migrate,
+};
 
 mod old {
     use super::*;
@@ -41,7 +45,241 @@ fn default_from() {
 
     assert_eq!(old.foo, 99);
 
+    assert_eq!(MyContract::schema_version(), 0);
+
     let migrated = MyContract::migrate();
 
     assert_eq!(migrated.bar, 99);
+    assert_eq!(migrated.migrate_schema_version(), 1);
+    assert_eq!(
+        get_logs(),
+        vec![MigrateEvent::Migrate {
+            from_version: 0,
+            to_version: 1,
+        }
+        .to_event_string()],
+    );
+}
+
+mod versioned {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env};
+
+    use super::*;
+
+    #[derive(Migrate, PanicOnDefault)]
+    #[migrate(from = "old::Old")]
+    #[near(contract_state)]
+    struct Contract {
+        pub bar: u64,
+    }
+
+    impl MigrateHook for Contract {
+        const FROM_VERSION: u32 = 2;
+        const TO_VERSION: u32 = 5;
+
+        fn on_migrate(old: old::Old) -> Self {
+            Self { bar: old.foo + 1 }
+        }
+    }
+
+    #[test]
+    fn declared_versions() {
+        testing_env!(VMContextBuilder::new().build());
+        env::state_write(&old::Old::new(7));
+        Contract::slot_schema_version().write(&2);
+
+        let migrated = Contract::migrate();
+
+        assert_eq!(migrated.bar, 8);
+        assert_eq!(migrated.migrate_schema_version(), 5);
+        assert!(get_logs().contains(
+            &MigrateEvent::Migrate {
+                from_version: 2,
+                to_version: 5,
+            }
+            .to_event_string()
+        ));
+    }
+
+    #[test]
+    #[should_panic = "Cannot migrate from schema version 0: expected version 2"]
+    fn unexpected_version() {
+        testing_env!(VMContextBuilder::new().build());
+        env::state_write(&old::Old::new(7));
+        Contract::slot_schema_version().remove();
+
+        let _ = Contract::migrate();
+    }
+
+    #[test]
+    #[should_panic = "Cannot migrate from schema version 5: expected version 2"]
+    fn already_migrated() {
+        testing_env!(VMContextBuilder::new().build());
+        env::state_write(&old::Old::new(7));
+        Contract::slot_schema_version().write(&2);
+
+        let migrated = Contract::migrate();
+        env::state_write(&old::Old::new(migrated.bar));
+
+        let _ = Contract::migrate();
+    }
+}
+
+mod batched {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, AccountId};
+    use near_sdk_contract_tools::{
+        migrate::*,
+        slot::{Slot, SlotMap},
+    };
+
+    use super::*;
+
+    #[derive(Migrate, PanicOnDefault)]
+    #[migrate(batched, storage_key = "b\"m\".as_slice()")]
+    #[near(contract_state)]
+    struct Contract {
+        pub count: u32,
+    }
+
+    impl Contract {
+        fn records() -> SlotMap<u32, u64> {
+            SlotMap::new(b"r".as_slice())
+        }
+
+        fn slot_completed() -> Slot<bool> {
+            Slot::new(b"c".as_slice())
+        }
+
+        pub fn read(&self, id: u32) -> Option<u64> {
+            Self::require_not_migrating();
+            Self::records().get(&id).filter(|_| id < self.count)
+        }
+    }
+
+    impl BatchedMigrateHook for Contract {
+        type Cursor = u32;
+
+        fn migrate_batch(&mut self, cursor: Option<u32>, limit: u32) -> Option<u32> {
+            let start = cursor.unwrap_or(0);
+            let end = (start + limit).min(self.count);
+            let mut records = Self::records();
+
+            for id in start..end {
+                let value = records.get(&id).unwrap();
+                records.insert(&id, &(value * 10));
+            }
+
+            (end < self.count).then_some(end)
+        }
+
+        fn on_migration_complete(&mut self) {
+            Self::slot_completed().write(&true);
+        }
+    }
+
+    fn contract_id() -> AccountId {
+        "contract".parse().unwrap()
+    }
+
+    fn call(predecessor: AccountId) {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_id())
+            .predecessor_account_id(predecessor)
+            .build());
+    }
+
+    fn setup() -> Contract {
+        call(contract_id());
+        let mut records = Contract::records();
+        for id in 0..5 {
+            records.insert(&id, &u64::from(id));
+        }
+        Contract { count: 5 }
+    }
+
+    #[test]
+    fn migrate_in_steps() {
+        let mut contract = setup();
+
+        assert_eq!(contract.migrate_status(), MigrationStatus::Idle);
+        assert_eq!(
+            contract.migrate_start(2),
+            MigrationStatus::InProgress { steps: 1 },
+        );
+
+        call("anyone".parse().unwrap());
+        assert_eq!(
+            contract.migrate_step(2),
+            MigrationStatus::InProgress { steps: 2 },
+        );
+        assert_eq!(Contract::slot_completed().read(), None);
+        assert_eq!(contract.migrate_step(2), MigrationStatus::Complete);
+
+        assert_eq!(Contract::slot_completed().read(), Some(true));
+        assert_eq!(Contract::slot_migration_cursor().read(), None);
+        for id in 0..5 {
+            assert_eq!(contract.read(id), Some(u64::from(id) * 10));
+        }
+    }
+
+    #[test]
+    #[should_panic = "Migration in progress"]
+    fn blocks_methods_during_migration() {
+        let mut contract = setup();
+
+        contract.migrate_start(2);
+        let _ = contract.read(0);
+    }
+
+    #[test]
+    #[should_panic = "Migration in progress"]
+    fn start_during_migration_fail() {
+        let mut contract = setup();
+
+        contract.migrate_start(2);
+        contract.migrate_start(2);
+    }
+
+    #[test]
+    fn completion_records_schema_version() {
+        let mut contract = setup();
+
+        contract.migrate_start(2);
+        assert_eq!(contract.migrate_schema_version(), 0);
+        contract.migrate_step(2);
+        contract.migrate_step(2);
+
+        assert_eq!(contract.migrate_schema_version(), 1);
+        assert_eq!(
+            Contract::slot_schema_version(),
+            Slot::root(b"m".as_slice()).field::<u32>(vec![2u8]),
+        );
+        assert_eq!(
+            get_logs(),
+            vec![MigrateEvent::Migrate {
+                from_version: 0,
+                to_version: 1,
+            }
+            .to_event_string()],
+        );
+    }
+
+    #[test]
+    #[should_panic = "Cannot migrate from schema version 1: expected version 0"]
+    fn restart_after_completion_fail() {
+        let mut contract = setup();
+
+        contract.migrate_start(5);
+        assert_eq!(contract.migrate_status(), MigrationStatus::Complete);
+
+        contract.migrate_start(5);
+    }
+
+    #[test]
+    #[should_panic = "No migration in progress"]
+    fn step_requires_migration() {
+        let mut contract = setup();
+
+        contract.migrate_step(2);
+    }
 }
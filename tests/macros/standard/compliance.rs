@@ -0,0 +1,287 @@
+use near_sdk::{
+    near,
+    test_utils::{get_logs, VMContextBuilder},
+    testing_env, AccountId, NearToken, PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    ft::*,
+    owner::Owner,
+    standard::{
+        nep141::{
+            compliance::{Compliance, ComplianceEvent, ComplianceExternal, ComplianceHook},
+            FtTransferData, Nep141Event, TransferFeePolicy,
+        },
+        nep297::Event,
+    },
+    Compliance, Nep141, Owner,
+};
+
+#[derive(Owner, Nep141, Compliance, PanicOnDefault)]
+#[nep141(all_hooks = "ComplianceHook")]
+#[compliance(authority = "owner")]
+#[near(contract_state)]
+struct RegulatedToken {}
+
+#[near]
+impl RegulatedToken {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        Owner::init(&mut contract, &owner());
+
+        contract
+    }
+}
+
+fn owner() -> AccountId {
+    "owner".parse().unwrap()
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn bob() -> AccountId {
+    "bob".parse().unwrap()
+}
+
+fn treasury() -> AccountId {
+    "treasury".parse().unwrap()
+}
+
+fn setup() -> RegulatedToken {
+    let mut contract = RegulatedToken::new();
+
+    contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+    contract.mint(&Nep141Mint::new(100, bob())).unwrap();
+
+    contract
+}
+
+fn predecessor(account_id: AccountId) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(NearToken::from_yoctonear(1))
+        .build());
+}
+
+#[test]
+fn freeze_and_unfreeze() {
+    let mut contract = setup();
+
+    predecessor(owner());
+    contract.cmpl_freeze(alice());
+    assert!(contract.cmpl_is_frozen(alice()));
+    assert!(!contract.cmpl_is_frozen(bob()));
+
+    contract.cmpl_unfreeze(alice());
+    assert!(!contract.cmpl_is_frozen(alice()));
+
+    predecessor(alice());
+    contract.ft_transfer(bob(), 10.into(), None);
+    assert_eq!(contract.ft_balance_of(bob()).0, 110);
+}
+
+#[test]
+#[should_panic = "Account alice is frozen."]
+fn frozen_sender_cannot_transfer() {
+    let mut contract = setup();
+    contract.freeze(&alice());
+
+    predecessor(alice());
+    contract.ft_transfer(bob(), 10.into(), None);
+}
+
+#[test]
+#[should_panic = "Account alice is frozen."]
+fn frozen_receiver_cannot_receive() {
+    let mut contract = setup();
+    contract.freeze(&alice());
+
+    predecessor(bob());
+    contract.ft_transfer(alice(), 10.into(), None);
+}
+
+#[test]
+#[should_panic = "Account alice is frozen."]
+fn frozen_account_cannot_be_minted_to() {
+    let mut contract = setup();
+    contract.freeze(&alice());
+
+    contract.mint(&Nep141Mint::new(1, alice())).unwrap();
+}
+
+#[test]
+fn force_transfer_and_clawback_bypass_freeze() {
+    let mut contract = setup();
+
+    predecessor(owner());
+    contract.cmpl_freeze(alice());
+    contract.cmpl_force_transfer(alice(), owner(), 40.into(), Some("order 1".to_string()));
+    contract.cmpl_clawback(alice(), 60.into(), None);
+
+    assert_eq!(contract.ft_balance_of(alice()).0, 0);
+    assert_eq!(contract.ft_balance_of(owner()).0, 40);
+    assert_eq!(contract.ft_total_supply().0, 140);
+    assert!(contract.is_frozen(&alice()));
+}
+
+#[test]
+fn force_transfer_is_not_charged_fees() {
+    let mut contract = setup();
+    contract.set_transfer_fee_policy(Some(
+        &TransferFeePolicy::new(treasury())
+            .basis_points(1000)
+            .flat_fee(1),
+    ));
+
+    predecessor(owner());
+    contract.cmpl_force_transfer(alice(), owner(), 40.into(), None);
+
+    assert_eq!(contract.ft_balance_of(alice()).0, 60);
+    assert_eq!(contract.ft_balance_of(owner()).0, 40);
+    assert_eq!(contract.ft_balance_of(treasury()).0, 0);
+    assert_eq!(
+        get_logs(),
+        vec![
+            Nep141Event::FtTransfer(vec![FtTransferData {
+                old_owner_id: alice().into(),
+                new_owner_id: owner().into(),
+                amount: 40.into(),
+                memo: None,
+            }])
+            .to_event_string(),
+            ComplianceEvent::ForceTransfer {
+                sender_id: alice(),
+                receiver_id: owner(),
+                amount: 40.into(),
+                memo: None,
+            }
+            .to_event_string(),
+        ],
+    );
+}
+
+#[test]
+#[should_panic = "Owner only"]
+fn freeze_requires_authority() {
+    let mut contract = setup();
+
+    predecessor(alice());
+    contract.cmpl_freeze(bob());
+}
+
+mod hooks {
+    use near_sdk::log;
+    use near_sdk_contract_tools::hook::Hook;
+
+    use super::*;
+
+    pub struct LogHook;
+
+    impl<C> Hook<C, Nep141Transfer<'_>> for LogHook {
+        fn hook<R>(contract: &mut C, _: &Nep141Transfer<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+            log!("transfer hook");
+            f(contract)
+        }
+    }
+
+    impl<C> Hook<C, Nep141Burn<'_>> for LogHook {
+        fn hook<R>(contract: &mut C, _: &Nep141Burn<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+            log!("burn hook");
+            f(contract)
+        }
+    }
+
+    impl<C> Hook<C, Nep141Mint<'_>> for LogHook {
+        fn hook<R>(contract: &mut C, _: &Nep141Mint<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+            f(contract)
+        }
+    }
+
+    #[derive(Owner, Nep141, Compliance, PanicOnDefault)]
+    #[nep141(all_hooks = "(ComplianceHook, LogHook)")]
+    #[compliance(authority = "owner")]
+    #[near(contract_state)]
+    struct HookedToken {}
+
+    #[test]
+    fn force_transfer_and_clawback_invoke_hooks() {
+        let mut contract = HookedToken {};
+        Owner::init(&mut contract, &owner());
+        contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+
+        predecessor(owner());
+        contract.cmpl_freeze(alice());
+        contract.cmpl_force_transfer(alice(), bob(), 40.into(), None);
+        contract.cmpl_clawback(alice(), 60.into(), None);
+
+        let logs = get_logs();
+        assert!(logs.contains(&"transfer hook".to_string()));
+        assert!(logs.contains(&"burn hook".to_string()));
+        assert_eq!(contract.ft_balance_of(bob()).0, 40);
+        assert_eq!(contract.ft_total_supply().0, 40);
+    }
+
+    #[test]
+    #[should_panic = "Account alice is frozen."]
+    fn freeze_restored_after_force_transfer() {
+        let mut contract = HookedToken {};
+        Owner::init(&mut contract, &owner());
+        contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+
+        predecessor(owner());
+        contract.cmpl_freeze(alice());
+        contract.cmpl_force_transfer(alice(), bob(), 40.into(), None);
+
+        predecessor(alice());
+        contract.ft_transfer(bob(), 10.into(), None);
+    }
+}
+
+mod role_authority {
+    use near_sdk::BorshStorageKey;
+    use near_sdk_contract_tools::{rbac::Rbac, Rbac};
+
+    use super::*;
+
+    #[derive(BorshStorageKey)]
+    #[near]
+    enum Role {
+        Regulator,
+    }
+
+    #[derive(Rbac, Nep141, Compliance, PanicOnDefault)]
+    #[nep141(all_hooks = "ComplianceHook")]
+    #[compliance(authority = "role(Role::Regulator)")]
+    #[rbac(roles = "Role")]
+    #[near(contract_state)]
+    struct RoleToken {}
+
+    fn setup() -> RoleToken {
+        let mut contract = RoleToken {};
+        contract.add_role(&owner(), &Role::Regulator);
+        contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+        contract
+    }
+
+    #[test]
+    fn role_can_freeze() {
+        let mut contract = setup();
+
+        predecessor(owner());
+        contract.cmpl_freeze(alice());
+
+        assert!(contract.cmpl_is_frozen(alice()));
+    }
+
+    #[test]
+    #[should_panic = "Unauthorized role"]
+    fn freeze_requires_role() {
+        let mut contract = setup();
+
+        predecessor(bob());
+        contract.cmpl_freeze(alice());
+    }
+}
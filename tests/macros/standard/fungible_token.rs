@@ -92,3 +92,203 @@ mod tests {
         );
     }
 }
+
+mod auto_register {
+    use near_sdk::{
+        mock::MockAction,
+        near,
+        test_utils::{get_created_receipts, VMContextBuilder},
+        testing_env, AccountId, NearToken, PanicOnDefault,
+    };
+    use near_sdk_contract_tools::ft::*;
+
+    #[derive(FungibleToken, PanicOnDefault)]
+    #[fungible_token(auto_register)]
+    #[near(contract_state)]
+    struct Contract {}
+
+    #[near]
+    impl Contract {
+        #[init]
+        pub fn new() -> Self {
+            let mut contract = Self {};
+
+            contract.set_metadata(&ContractMetadata::new("Auto Register", "AUTO", 24));
+            contract.set_storage_balance_bounds(&StorageBalanceBounds {
+                min: NearToken::from_millinear(10),
+                max: None,
+            });
+
+            contract
+        }
+    }
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob".parse().unwrap()
+    }
+
+    fn call(predecessor: AccountId, deposit: NearToken) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(predecessor)
+            .attached_deposit(deposit)
+            .build());
+    }
+
+    fn setup() -> Contract {
+        let mut ft = Contract::new();
+
+        call(alice(), NearToken::from_near(1));
+        ft.storage_deposit(None, None);
+        ft.deposit_unchecked(&alice(), 100).unwrap();
+
+        ft
+    }
+
+    #[test]
+    fn registers_receiver_from_attached_deposit() {
+        let mut ft = setup();
+        let alice_balance = ft.storage_balance_of(alice()).unwrap();
+        let min = ft.storage_balance_bounds().min;
+
+        call(alice(), min.saturating_add(NearToken::from_yoctonear(1)));
+        ft.ft_transfer(bob(), 40.into(), None);
+
+        assert_eq!(ft.ft_balance_of(bob()).0, 40);
+        assert!(ft.storage_balance_of(bob()).is_some());
+        assert_eq!(
+            ft.storage_balance_of(alice()).unwrap().available,
+            alice_balance.available,
+        );
+    }
+
+    #[test]
+    fn registers_receiver_from_sender_storage_balance() {
+        let mut ft = setup();
+        let alice_balance = ft.storage_balance_of(alice()).unwrap();
+        let min = ft.storage_balance_bounds().min;
+
+        call(alice(), NearToken::from_yoctonear(1));
+        ft.ft_transfer(bob(), 40.into(), None);
+
+        assert_eq!(ft.ft_balance_of(bob()).0, 40);
+        assert!(ft.storage_balance_of(bob()).is_some());
+        assert_eq!(
+            ft.storage_balance_of(alice()).unwrap().available,
+            alice_balance.available.saturating_sub(min),
+        );
+    }
+
+    #[test]
+    fn registered_receiver_is_not_charged() {
+        let mut ft = setup();
+
+        call(bob(), NearToken::from_near(1));
+        ft.storage_deposit(None, None);
+        let alice_balance = ft.storage_balance_of(alice()).unwrap();
+
+        call(alice(), NearToken::from_yoctonear(1));
+        ft.ft_transfer(bob(), 40.into(), None);
+
+        assert_eq!(ft.ft_balance_of(bob()).0, 40);
+        assert_eq!(
+            ft.storage_balance_of(alice()).unwrap().available,
+            alice_balance.available,
+        );
+    }
+
+    #[test]
+    fn registered_receiver_refunds_deposit() {
+        let mut ft = setup();
+
+        call(bob(), NearToken::from_near(1));
+        ft.storage_deposit(None, None);
+
+        call(alice(), NearToken::from_millinear(5));
+        ft.ft_transfer(bob(), 40.into(), None);
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, alice());
+        assert!(matches!(
+            receipts[0].actions[..],
+            [MockAction::Transfer { deposit, .. }]
+                if deposit == NearToken::from_millinear(5).saturating_sub(NearToken::from_yoctonear(1)),
+        ));
+    }
+
+    #[test]
+    #[should_panic = "Requires attached deposit of at least 1 yoctoNEAR"]
+    fn requires_deposit() {
+        let mut ft = setup();
+
+        call(alice(), NearToken::from_yoctonear(0));
+        ft.ft_transfer(bob(), 40.into(), None);
+    }
+
+    #[test]
+    #[should_panic = "Receiver registration error"]
+    fn unregistered_sender_cannot_pay() {
+        let mut ft = setup();
+
+        call(bob(), NearToken::from_yoctonear(1));
+        ft.ft_transfer("carol".parse().unwrap(), 10.into(), None);
+    }
+}
+
+mod transfer_on_force_unregister {
+    use near_sdk::{
+        near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken, PanicOnDefault,
+    };
+    use near_sdk_contract_tools::ft::*;
+
+    #[derive(FungibleToken, PanicOnDefault)]
+    #[fungible_token(transfer_on_force_unregister)]
+    #[near(contract_state)]
+    struct Contract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn treasury() -> AccountId {
+        "treasury".parse().unwrap()
+    }
+
+    fn setup() -> Contract {
+        testing_env!(VMContextBuilder::new().build());
+        let mut ft = Contract {};
+
+        for account_id in [alice(), treasury()] {
+            ft.deposit_to_storage_account(&account_id, NearToken::from_millinear(100))
+                .unwrap();
+        }
+        ft.mint(&Nep141Mint::new(100, alice())).unwrap();
+
+        ft
+    }
+
+    #[test]
+    fn transfers_to_beneficiary() {
+        let mut ft = setup();
+        ft.set_force_unregister_beneficiary(Some(&treasury()));
+
+        ft.force_unregister_storage_account(&alice()).unwrap();
+
+        assert_eq!(ft.balance_of(&alice()), 0);
+        assert_eq!(ft.balance_of(&treasury()), 100);
+        assert_eq!(ft.total_supply(), 100);
+        assert!(ft.get_storage_balance(&alice()).is_err());
+    }
+
+    #[test]
+    #[should_panic = "No beneficiary set for forced unregistration"]
+    fn requires_beneficiary() {
+        let mut ft = setup();
+
+        ft.force_unregister_storage_account(&alice()).unwrap();
+    }
+}
@@ -1,5 +1,14 @@
+pub mod compliance;
 pub mod fungible_token;
 pub mod nep141;
+pub mod nep141_shares;
 pub mod nep145;
 pub mod nep148;
 pub mod nep171;
+pub mod nep181;
+pub mod nep393;
+pub mod nft_rental;
+pub mod nft_series;
+pub mod nft_voucher;
+pub mod supply_cap;
+pub mod wrapped_near;
@@ -1,6 +1,13 @@
 use near_sdk::{
-    borsh, collections::Vector, env, json_types::U128, log, near, test_utils::VMContextBuilder,
-    testing_env, AccountId, NearToken, PanicOnDefault, PromiseOrValue,
+    borsh,
+    collections::Vector,
+    env,
+    json_types::U128,
+    log,
+    mock::MockAction,
+    near,
+    test_utils::{self, VMContextBuilder},
+    testing_env, AccountId, NearToken, PanicOnDefault, PromiseOrValue, PromiseResult,
 };
 use near_sdk_contract_tools::{hook::Hook, standard::nep141::*, Nep141};
 
@@ -105,3 +112,217 @@ fn nep141_transfer() {
     assert_eq!(ft.ft_balance_of(bob).0, 70);
     assert_eq!(ft.ft_total_supply().0, 120);
 }
+
+#[test]
+fn nep141_transfer_with_fee() {
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+    let treasury: AccountId = "treasury".parse().unwrap();
+
+    ft.deposit_unchecked(&alice, 10_000).unwrap();
+    ft.set_transfer_fee_policy(Some(
+        &TransferFeePolicy::new(treasury.clone())
+            .basis_points(250)
+            .flat_fee(5),
+    ));
+
+    let context = VMContextBuilder::new()
+        .predecessor_account_id(alice.clone())
+        .attached_deposit(NearToken::from_yoctonear(1u128))
+        .build();
+
+    testing_env!(context);
+
+    ft.ft_transfer(bob.clone(), 1000.into(), None);
+
+    assert_eq!(ft.ft_balance_of(alice.clone()).0, 9000);
+    assert_eq!(ft.ft_balance_of(bob.clone()).0, 970);
+    assert_eq!(ft.ft_balance_of(treasury.clone()).0, 30);
+    assert_eq!(ft.ft_total_supply().0, 10_000);
+
+    assert_eq!(
+        test_utils::get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice","new_owner_id":"bob","amount":"970"},{"old_owner_id":"alice","new_owner_id":"treasury","amount":"30","memo":"transfer fee"}]}"#,
+        ],
+    );
+
+    ft.set_transfer_fee_exempt(&bob, true);
+    ft.ft_transfer(bob.clone(), 1000.into(), None);
+
+    assert_eq!(ft.ft_balance_of(bob.clone()).0, 1970);
+    assert_eq!(ft.ft_balance_of(treasury).0, 30);
+}
+
+fn setup_transfer_call_with_fee() -> FungibleToken {
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+    let treasury: AccountId = "treasury".parse().unwrap();
+
+    ft.deposit_unchecked(&alice, 10_000).unwrap();
+    ft.set_transfer_fee_policy(Some(
+        &TransferFeePolicy::new(treasury)
+            .basis_points(250)
+            .flat_fee(5),
+    ));
+
+    let context = VMContextBuilder::new()
+        .predecessor_account_id(alice)
+        .attached_deposit(NearToken::from_yoctonear(1u128))
+        .build();
+
+    testing_env!(context);
+
+    let _ = ft.ft_transfer_call(bob, 1000.into(), None, "msg".to_string());
+
+    ft
+}
+
+fn resolve_transfer_call(ft: &mut FungibleToken, unused_amount: u128) -> u128 {
+    let context = VMContextBuilder::new()
+        .predecessor_account_id(env::current_account_id())
+        .build();
+
+    testing_env!(
+        context,
+        near_sdk::test_vm_config(),
+        near_sdk::RuntimeFeesConfig::test(),
+        std::collections::HashMap::default(),
+        vec![PromiseResult::Successful(
+            near_sdk::serde_json::to_vec(&U128(unused_amount)).unwrap()
+        )],
+    );
+
+    ft.ft_resolve_transfer("alice".parse().unwrap(), "bob".parse().unwrap(), 970.into())
+        .0
+}
+
+#[test]
+fn nep141_transfer_call_with_fee() {
+    let ft = setup_transfer_call_with_fee();
+
+    assert_eq!(ft.ft_balance_of("alice".parse().unwrap()).0, 9000);
+    assert_eq!(ft.ft_balance_of("bob".parse().unwrap()).0, 970);
+    assert_eq!(ft.ft_balance_of("treasury".parse().unwrap()).0, 30);
+
+    assert_eq!(
+        test_utils::get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice","new_owner_id":"bob","amount":"970"},{"old_owner_id":"alice","new_owner_id":"treasury","amount":"30","memo":"transfer fee"}]}"#,
+        ],
+    );
+
+    // the receiver and the resolver are both told the amount after fees
+    let receipts = test_utils::get_created_receipts();
+    let calls = receipts
+        .iter()
+        .flat_map(|receipt| &receipt.actions)
+        .filter_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name, args, ..
+            } => Some((
+                String::from_utf8(method_name.clone()).unwrap(),
+                near_sdk::serde_json::from_slice::<near_sdk::serde_json::Value>(args).unwrap(),
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        calls,
+        vec![
+            (
+                "ft_on_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "sender_id": "alice",
+                    "amount": "970",
+                    "msg": "msg",
+                }),
+            ),
+            (
+                "ft_resolve_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "sender_id": "alice",
+                    "receiver_id": "bob",
+                    "amount": "970",
+                }),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn nep141_resolve_transfer_full_refund_with_fee() {
+    let mut ft = setup_transfer_call_with_fee();
+
+    let used_amount = resolve_transfer_call(&mut ft, 970);
+
+    assert_eq!(used_amount, 0);
+    // refunds are not charged, but the fee is not returned
+    assert_eq!(ft.ft_balance_of("alice".parse().unwrap()).0, 9970);
+    assert_eq!(ft.ft_balance_of("bob".parse().unwrap()).0, 0);
+    assert_eq!(ft.ft_balance_of("treasury".parse().unwrap()).0, 30);
+    assert_eq!(ft.ft_total_supply().0, 10_000);
+
+    assert_eq!(
+        test_utils::get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"alice","amount":"970"}]}"#,
+        ],
+    );
+}
+
+#[test]
+fn nep141_resolve_transfer_partial_refund_with_fee() {
+    let mut ft = setup_transfer_call_with_fee();
+
+    let used_amount = resolve_transfer_call(&mut ft, 500);
+
+    assert_eq!(used_amount, 470);
+    assert_eq!(ft.ft_balance_of("alice".parse().unwrap()).0, 9500);
+    assert_eq!(ft.ft_balance_of("bob".parse().unwrap()).0, 470);
+    assert_eq!(ft.ft_balance_of("treasury".parse().unwrap()).0, 30);
+    assert_eq!(ft.ft_total_supply().0, 10_000);
+
+    assert_eq!(
+        test_utils::get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"alice","amount":"500"}]}"#,
+        ],
+    );
+}
+
+#[test]
+fn nep141_transfer_fee_exceeds_amount() {
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+
+    ft.deposit_unchecked(&alice, 100).unwrap();
+    ft.set_transfer_fee_policy(Some(&TransferFeePolicy::new(bob.clone()).flat_fee(10)));
+
+    let transfer = Nep141Transfer::new(5, alice.clone(), "charlie".parse::<AccountId>().unwrap());
+    assert!(matches!(
+        ft.transfer(&transfer),
+        Err(TransferError::FeeExceedsAmount(_)),
+    ));
+
+    // transfers to the fee collector are not charged
+    ft.transfer(&Nep141Transfer::new(5, alice.clone(), bob.clone()))
+        .unwrap();
+    assert_eq!(ft.ft_balance_of(bob).0, 5);
+}
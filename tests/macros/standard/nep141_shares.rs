@@ -0,0 +1,153 @@
+use near_sdk::{
+    near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken, PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    ft::{Nep141, Nep141Burn, Nep141Mint},
+    standard::nep141::shares::{Nep141SharesController, Nep141SharesExternal, Rounding},
+    Nep141Shares,
+};
+
+#[derive(Nep141Shares, PanicOnDefault)]
+#[near(contract_state)]
+struct StakedToken {}
+
+#[near]
+impl StakedToken {
+    #[init]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn bob() -> AccountId {
+    "bob".parse().unwrap()
+}
+
+fn predecessor(account_id: AccountId) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(NearToken::from_yoctonear(1))
+        .build());
+}
+
+#[test]
+fn rebase_changes_balances() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+    contract.mint(&Nep141Mint::new(300, bob())).unwrap();
+
+    assert_eq!(contract.ft_total_shares().0, 400);
+    assert_eq!(contract.ft_shares_of(alice()).0, 100);
+
+    // rewards accrue to the pool
+    contract.set_pooled_amount(800);
+
+    assert_eq!(contract.ft_total_supply().0, 800);
+    assert_eq!(contract.ft_balance_of(alice()).0, 200);
+    assert_eq!(contract.ft_balance_of(bob()).0, 600);
+    assert_eq!(contract.ft_total_shares().0, 400);
+
+    // new deposits receive shares at the current rate
+    contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+    assert_eq!(contract.ft_shares_of(alice()).0, 150);
+    assert_eq!(contract.ft_balance_of(alice()).0, 300);
+    assert_eq!(contract.ft_total_supply().0, 900);
+}
+
+#[test]
+fn transfer_rounds_in_favor_of_receiver() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(3, alice())).unwrap();
+    contract.set_pooled_amount(10);
+
+    // 1 token is 0.3 shares, rounded up to 1 share (3.33 tokens)
+    assert_eq!(contract.amount_to_shares(1, Rounding::Up), 1);
+    assert_eq!(contract.amount_to_shares(1, Rounding::Down), 0);
+
+    predecessor(alice());
+    contract.ft_transfer(bob(), 1.into(), None);
+
+    assert_eq!(contract.ft_shares_of(bob()).0, 1);
+    assert_eq!(contract.ft_balance_of(bob()).0, 3);
+    assert_eq!(contract.ft_balance_of(alice()).0, 6);
+
+    // an account can always transfer its entire balance
+    contract.ft_transfer(bob(), 6.into(), None);
+    assert_eq!(contract.ft_shares_of(alice()).0, 0);
+    assert_eq!(contract.ft_balance_of(bob()).0, 10);
+}
+
+#[test]
+fn burn_rounds_up() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(3, alice())).unwrap();
+    contract.mint(&Nep141Mint::new(3, bob())).unwrap();
+    contract.set_pooled_amount(20);
+
+    contract.burn(&Nep141Burn::new(1, alice())).unwrap();
+
+    assert_eq!(contract.ft_shares_of(alice()).0, 2);
+    assert_eq!(contract.ft_total_shares().0, 5);
+    assert_eq!(contract.ft_total_supply().0, 19);
+    assert_eq!(contract.ft_balance_of(bob()).0, 11);
+}
+
+#[test]
+#[should_panic = "Balance of the sender is insufficient"]
+fn transfer_more_than_balance_fail() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(3, alice())).unwrap();
+    contract.set_pooled_amount(10);
+
+    predecessor(alice());
+    contract.ft_transfer(bob(), 11.into(), None);
+}
+
+#[test]
+fn empty_pool_shares_are_worthless() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+    contract.set_pooled_amount(0);
+
+    assert_eq!(contract.ft_balance_of(alice()).0, 0);
+    assert_eq!(contract.shares_to_amount(100, Rounding::Up), 0);
+    assert_eq!(contract.amount_to_shares(0, Rounding::Up), 0);
+}
+
+#[test]
+fn deposit_into_empty_pool_fail() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+    contract.set_pooled_amount(0);
+
+    let err = contract.mint(&Nep141Mint::new(50, bob())).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Cannot deposit 50 into an empty pool with 100 outstanding shares.",
+    );
+    assert_eq!(contract.ft_shares_of(bob()).0, 0);
+    assert_eq!(contract.ft_total_supply().0, 0);
+}
+
+#[test]
+#[should_panic = "Balance of the sender is insufficient"]
+fn transfer_from_empty_pool_fail() {
+    let mut contract = StakedToken::new();
+
+    contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+    contract.set_pooled_amount(0);
+
+    predecessor(alice());
+    contract.ft_transfer(bob(), 1.into(), None);
+}
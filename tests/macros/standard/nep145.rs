@@ -196,3 +196,613 @@ mod tests {
         );
     }
 }
+
+mod events {
+    use near_sdk::{
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env,
+    };
+    use near_sdk_contract_tools::standard::{
+        nep145::event::{StorageEvent, MAX_STORAGE_ACCOUNTING_LOG_LENGTH},
+        nep297::Event,
+    };
+
+    use super::*;
+
+    fn alice() -> AccountId {
+        "alice.near".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob.near".parse().unwrap()
+    }
+
+    fn call(predecessor: AccountId, deposit: NearToken) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(predecessor)
+            .attached_deposit(deposit)
+            .build());
+    }
+
+    #[test]
+    fn storage_accounting_log_length_bound() {
+        let longest: AccountId = "a".repeat(64).parse().unwrap();
+        let amount = NearToken::from_yoctonear(u128::MAX);
+
+        let lock = StorageEvent::Lock {
+            account_id: longest.clone(),
+            amount,
+            bytes: u64::MAX,
+            payer_id: Some(longest.clone()),
+        };
+        let unlock = StorageEvent::Unlock {
+            account_id: longest.clone(),
+            amount,
+            bytes: u64::MAX,
+            payer_id: Some(longest),
+        };
+
+        for event in [lock, unlock] {
+            assert!(event.to_event_string().len() * 2 <= MAX_STORAGE_ACCOUNTING_LOG_LENGTH);
+        }
+    }
+
+    #[test]
+    fn deposit_on_behalf_of_other() {
+        let mut contract = Contract::new();
+        let amount = NearToken::from_millinear(10);
+
+        call(bob(), amount);
+        Nep145::storage_deposit(&mut contract, Some(alice()), None);
+
+        assert_eq!(
+            get_logs(),
+            vec![
+                StorageEvent::Register {
+                    account_id: alice(),
+                }
+                .to_event_string(),
+                StorageEvent::Deposit {
+                    account_id: alice(),
+                    amount,
+                    payer_id: Some(bob()),
+                }
+                .to_event_string(),
+            ],
+        );
+
+        call(alice(), amount);
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        assert_eq!(
+            get_logs(),
+            vec![StorageEvent::Deposit {
+                account_id: alice(),
+                amount,
+                payer_id: None,
+            }
+            .to_event_string()],
+        );
+    }
+
+    #[test]
+    fn lock_unlock_withdraw_unregister() {
+        let mut contract = Contract::new();
+        let byte_cost = env::storage_byte_cost();
+        let amount = NearToken::from_millinear(10);
+
+        call(alice(), amount);
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        call(alice(), NearToken::from_yoctonear(1));
+        Nep145Controller::lock_storage(&mut contract, &alice(), byte_cost.saturating_mul(10))
+            .unwrap();
+        Nep145Controller::unlock_storage(&mut contract, &alice(), byte_cost.saturating_mul(10))
+            .unwrap();
+        Nep145::storage_withdraw(&mut contract, Some(NearToken::from_millinear(4)));
+        Nep145::storage_unregister(&mut contract, None);
+
+        assert_eq!(
+            get_logs(),
+            vec![
+                StorageEvent::Lock {
+                    account_id: alice(),
+                    amount: byte_cost.saturating_mul(10),
+                    bytes: 10,
+                    payer_id: None,
+                }
+                .to_event_string(),
+                StorageEvent::Unlock {
+                    account_id: alice(),
+                    amount: byte_cost.saturating_mul(10),
+                    bytes: 10,
+                    payer_id: None,
+                }
+                .to_event_string(),
+                StorageEvent::Withdraw {
+                    account_id: alice(),
+                    amount: NearToken::from_millinear(4),
+                }
+                .to_event_string(),
+                StorageEvent::Unregister {
+                    account_id: alice(),
+                    amount: NearToken::from_millinear(6),
+                    force: false,
+                }
+                .to_event_string(),
+            ],
+        );
+    }
+}
+
+mod sponsorship {
+    use near_sdk::{
+        env, near,
+        store::LookupMap,
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env, AccountId, NearToken, PanicOnDefault,
+    };
+    use near_sdk_contract_tools::{
+        standard::{
+            nep145::{event::StorageEvent, *},
+            nep297::Event,
+        },
+        Nep145,
+    };
+
+    #[derive(Nep145, PanicOnDefault)]
+    #[nep145(sponsorship)]
+    #[near(contract_state)]
+    pub struct Contract {
+        pub storage: LookupMap<AccountId, Vec<u64>>,
+    }
+
+    #[near]
+    impl Contract {
+        #[init]
+        pub fn new() -> Self {
+            let mut contract = Self {
+                storage: LookupMap::new(b"s"),
+            };
+
+            Nep145Controller::set_storage_balance_bounds(
+                &mut contract,
+                &StorageBalanceBounds {
+                    min: NearToken::from_yoctonear(0),
+                    max: None,
+                },
+            );
+
+            contract
+        }
+
+        pub fn set_storage(&mut self, num: u64) {
+            let storage_usage_start = env::storage_usage();
+
+            let predecessor = env::predecessor_account_id();
+
+            if num == 0 {
+                self.storage.remove(&predecessor);
+            } else {
+                self.storage.insert(predecessor.clone(), (0..num).collect());
+            }
+
+            self.storage.flush();
+
+            Nep145Controller::storage_accounting(self, &predecessor, storage_usage_start)
+                .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+        }
+    }
+
+    fn alice() -> AccountId {
+        "alice.near".parse().unwrap()
+    }
+
+    fn sponsor() -> AccountId {
+        "sponsor.near".parse().unwrap()
+    }
+
+    fn call(predecessor: AccountId, deposit: NearToken) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(predecessor)
+            .attached_deposit(deposit)
+            .build());
+    }
+
+    fn setup(policy: Option<SponsorPolicy>, account_cap: Option<NearToken>) -> Contract {
+        let mut contract = Contract::new();
+
+        call(sponsor(), NearToken::from_near(1));
+        Nep145Sponsorship::storage_sponsor_deposit(&mut contract, policy, account_cap);
+
+        contract
+    }
+
+    #[test]
+    fn sponsor_covers_shortfall() {
+        let mut contract = setup(None, None);
+        let initial_available = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor())
+            .unwrap()
+            .available;
+        let small = NearToken::from_millinear(1);
+
+        call(alice(), small);
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        call(sponsor(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_add(&mut contract, alice());
+
+        call(alice(), NearToken::from_yoctonear(0));
+        contract.set_storage(100);
+
+        let balance = Nep145::storage_balance_of(&contract, alice()).unwrap();
+        assert_eq!(balance.total, small);
+        assert!(balance.available.is_zero());
+
+        let sponsorship = Nep145Sponsorship::storage_sponsorship_of(&contract, alice()).unwrap();
+        assert_eq!(sponsorship.sponsor_id, sponsor());
+        assert!(!sponsorship.locked.is_zero());
+
+        let pool = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor()).unwrap();
+        assert_eq!(
+            pool.locked,
+            sponsorship.locked.saturating_add(sponsorship.registration),
+        );
+        assert_eq!(
+            pool.available.saturating_add(pool.locked),
+            initial_available,
+        );
+    }
+
+    #[test]
+    fn sponsor_lock_event() {
+        let mut contract = setup(None, None);
+
+        call(alice(), NearToken::from_yoctonear(0));
+        Nep145::storage_deposit(&mut contract, None, Some(true));
+
+        call(sponsor(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_add(&mut contract, alice());
+
+        call(alice(), NearToken::from_yoctonear(0));
+        contract.set_storage(100);
+
+        let sponsorship = Nep145Sponsorship::storage_sponsorship_of(&contract, alice()).unwrap();
+
+        assert_eq!(
+            get_logs(),
+            vec![StorageEvent::Lock {
+                account_id: alice(),
+                amount: sponsorship.locked,
+                bytes: u64::try_from(
+                    sponsorship.locked.as_yoctonear() / env::storage_byte_cost().as_yoctonear(),
+                )
+                .unwrap(),
+                payer_id: Some(sponsor()),
+            }
+            .to_event_string()],
+        );
+    }
+
+    #[test]
+    fn release_credits_sponsor_first() {
+        let mut contract = setup(None, None);
+        let initial_available = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor())
+            .unwrap()
+            .available;
+        let small = NearToken::from_millinear(1);
+
+        call(alice(), small);
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        call(sponsor(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_add(&mut contract, alice());
+
+        call(alice(), NearToken::from_yoctonear(0));
+        contract.set_storage(100);
+        contract.set_storage(0);
+
+        let sponsorship = Nep145Sponsorship::storage_sponsorship_of(&contract, alice()).unwrap();
+        assert!(sponsorship.locked.is_zero());
+
+        let pool = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor()).unwrap();
+        assert_eq!(pool.locked, sponsorship.registration);
+        assert_eq!(
+            pool.available.saturating_add(pool.locked),
+            initial_available,
+        );
+
+        let balance = Nep145::storage_balance_of(&contract, alice()).unwrap();
+        assert!(balance.available <= small);
+        assert!(!balance.available.is_zero());
+    }
+
+    #[test]
+    #[should_panic = "insufficient balance"]
+    fn account_cap_limits_sponsor() {
+        let mut contract = setup(None, Some(NearToken::from_yoctonear(1)));
+
+        call(sponsor(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_add(&mut contract, alice());
+
+        call(alice(), NearToken::from_yoctonear(0));
+        contract.set_storage(100);
+    }
+
+    #[test]
+    fn open_pool_join() {
+        let mut contract = setup(Some(SponsorPolicy::Open), None);
+
+        call(alice(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_join(&mut contract, sponsor());
+
+        call(alice(), NearToken::from_yoctonear(0));
+        contract.set_storage(100);
+
+        let balance = Nep145::storage_balance_of(&contract, alice()).unwrap();
+        assert!(balance.total.is_zero());
+        assert!(
+            !Nep145Sponsorship::storage_sponsorship_of(&contract, alice())
+                .unwrap()
+                .locked
+                .is_zero()
+        );
+    }
+
+    #[test]
+    #[should_panic = "Sponsor pool sponsor.near is not open"]
+    fn designated_pool_join_fail() {
+        let mut contract = setup(None, None);
+
+        call(alice(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_join(&mut contract, sponsor());
+    }
+
+    #[test]
+    #[should_panic = "Sponsor deposit error: Account sponsor.near has insufficient balance"]
+    fn create_pool_zero_deposit_fail() {
+        let mut contract = Contract::new();
+
+        call(sponsor(), NearToken::from_yoctonear(0));
+        Nep145Sponsorship::storage_sponsor_deposit(&mut contract, None, None);
+    }
+
+    #[test]
+    #[should_panic = "Account sponsor.near has insufficient balance"]
+    fn sponsor_pays_for_registration() {
+        let mut contract = setup(None, None);
+
+        call(sponsor(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_withdraw(&mut contract, None);
+
+        Nep145Sponsorship::storage_sponsor_add(&mut contract, alice());
+    }
+
+    #[test]
+    fn unregister_releases_registration() {
+        let mut contract = setup(Some(SponsorPolicy::Open), None);
+        let initial_available = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor())
+            .unwrap()
+            .available;
+
+        call(alice(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_join(&mut contract, sponsor());
+
+        let sponsorship = Nep145Sponsorship::storage_sponsorship_of(&contract, alice()).unwrap();
+        assert!(!sponsorship.registration.is_zero());
+
+        let pool = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor()).unwrap();
+        assert_eq!(pool.locked, sponsorship.registration);
+        assert_eq!(
+            pool.available,
+            initial_available.saturating_sub(sponsorship.registration),
+        );
+
+        Nep145::storage_unregister(&mut contract, None);
+
+        assert!(Nep145Sponsorship::storage_sponsorship_of(&contract, alice()).is_none());
+        let pool = Nep145Sponsorship::storage_sponsor_pool(&contract, sponsor()).unwrap();
+        assert!(pool.locked.is_zero());
+        assert_eq!(pool.available, initial_available);
+    }
+
+    #[test]
+    fn sponsor_pool_events() {
+        let mut contract = setup(None, None);
+
+        assert_eq!(
+            get_logs(),
+            vec![StorageEvent::SponsorDeposit {
+                sponsor_id: sponsor(),
+                amount: NearToken::from_near(1),
+                payer_id: None,
+            }
+            .to_event_string()],
+        );
+
+        call(alice(), NearToken::from_yoctonear(0));
+        Nep145Controller::deposit_to_sponsor_pool(
+            &mut contract,
+            &sponsor(),
+            NearToken::from_millinear(10),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_logs(),
+            vec![StorageEvent::SponsorDeposit {
+                sponsor_id: sponsor(),
+                amount: NearToken::from_millinear(10),
+                payer_id: Some(alice()),
+            }
+            .to_event_string()],
+        );
+
+        call(sponsor(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_withdraw(
+            &mut contract,
+            Some(NearToken::from_millinear(5)),
+        );
+
+        assert_eq!(
+            get_logs(),
+            vec![StorageEvent::SponsorWithdraw {
+                sponsor_id: sponsor(),
+                amount: NearToken::from_millinear(5),
+            }
+            .to_event_string()],
+        );
+    }
+
+    #[test]
+    #[should_panic = "Attempt to unregister from storage with locked balance"]
+    fn unregister_with_sponsored_storage_fail() {
+        let mut contract = setup(Some(SponsorPolicy::Open), None);
+
+        call(alice(), NearToken::from_yoctonear(1));
+        Nep145Sponsorship::storage_sponsor_join(&mut contract, sponsor());
+
+        call(alice(), NearToken::from_yoctonear(0));
+        contract.set_storage(100);
+
+        call(alice(), NearToken::from_yoctonear(1));
+        Nep145::storage_unregister(&mut contract, None);
+    }
+}
+
+mod attribution {
+    use near_sdk::{
+        env, near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken, PanicOnDefault,
+    };
+    use near_sdk_contract_tools::{
+        standard::{
+            nep141::*,
+            nep145::{hooks::*, *},
+        },
+        Nep141, Nep145,
+    };
+
+    fn alice() -> AccountId {
+        "alice.near".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob.near".parse().unwrap()
+    }
+
+    fn register<C: Nep145Controller>(contract: &mut C, account_id: &AccountId, amount: NearToken) {
+        contract
+            .deposit_to_storage_account(account_id, amount)
+            .unwrap();
+    }
+
+    mod sender_pays {
+        use super::*;
+
+        #[derive(Nep141, Nep145, PanicOnDefault)]
+        #[nep141(all_hooks = "AttributedStorageAccountingHook<Nep141StorageAttribution<Sender>>")]
+        #[near(contract_state)]
+        pub struct Contract {}
+
+        #[near]
+        impl Contract {
+            #[init]
+            pub fn new() -> Self {
+                let mut contract = Self {};
+
+                Nep145Controller::set_storage_balance_bounds(
+                    &mut contract,
+                    &StorageBalanceBounds {
+                        min: NearToken::from_yoctonear(0),
+                        max: None,
+                    },
+                );
+
+                contract
+            }
+        }
+    }
+
+    mod contract_pays {
+        use super::*;
+
+        #[derive(Nep141, Nep145, PanicOnDefault)]
+        #[nep141(
+            all_hooks = "AttributedStorageAccountingHook<Nep141StorageAttribution<ContractPays, ContractPays>>"
+        )]
+        #[near(contract_state)]
+        pub struct Contract {}
+    }
+
+    #[test]
+    fn sender_pays_for_receiver_record() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = sender_pays::Contract::new();
+
+        register(&mut contract, &alice(), NearToken::from_millinear(100));
+        register(&mut contract, &bob(), NearToken::from_yoctonear(0));
+
+        contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+
+        let before = contract.get_storage_balance(&alice()).unwrap();
+
+        contract
+            .transfer(&Nep141Transfer::new(10, alice(), bob()))
+            .unwrap();
+
+        let after = contract.get_storage_balance(&alice()).unwrap();
+        assert!(after.available < before.available);
+        assert!(contract
+            .get_storage_balance(&bob())
+            .unwrap()
+            .available
+            .is_zero());
+
+        contract
+            .transfer(&Nep141Transfer::new(10, alice(), bob()))
+            .unwrap();
+
+        assert_eq!(contract.get_storage_balance(&alice()).unwrap(), after);
+        assert_eq!(contract.balance_of(&bob()), 20);
+    }
+
+    #[test]
+    fn attributed_record_delta_is_exact() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = sender_pays::Contract::new();
+
+        register(&mut contract, &alice(), NearToken::from_millinear(100));
+        register(&mut contract, &bob(), NearToken::from_yoctonear(0));
+
+        contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+
+        let before = contract.get_storage_balance(&alice()).unwrap();
+        let storage_usage_start = env::storage_usage();
+
+        contract
+            .transfer(&Nep141Transfer::new(10, alice(), bob()))
+            .unwrap();
+
+        let used = env::storage_usage() - storage_usage_start;
+        let after = contract.get_storage_balance(&alice()).unwrap();
+
+        assert_eq!(
+            before.available.saturating_sub(after.available),
+            env::storage_byte_cost().saturating_mul(u128::from(used)),
+        );
+    }
+
+    #[test]
+    fn contract_pays_without_registration() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = contract_pays::Contract {};
+
+        contract.mint(&Nep141Mint::new(100, alice())).unwrap();
+        contract
+            .transfer(&Nep141Transfer::new(10, alice(), bob()))
+            .unwrap();
+
+        assert_eq!(contract.balance_of(&bob()), 10);
+        assert!(contract.get_storage_balance(&bob()).is_err());
+    }
+}
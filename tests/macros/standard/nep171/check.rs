@@ -0,0 +1,167 @@
+use near_sdk::{
+    near, test_utils::VMContextBuilder, testing_env, AccountId, AccountIdRef, BorshStorageKey,
+    PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    nft::{nep171::DefaultCheckExternalTransfer, *},
+    pause::Pause,
+    rbac::Rbac,
+    standard::nep171::{check::*, error::Nep171TransferError, Nep171TransferAuthorization},
+    Pause, Rbac,
+};
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn bob() -> AccountId {
+    "bob".parse().unwrap()
+}
+
+fn issuer() -> AccountId {
+    "issuer".parse().unwrap()
+}
+
+fn transfer(sender_id: &AccountId, receiver_id: &AccountId) -> Nep171Transfer<'static> {
+    Nep171Transfer::new(
+        "t".to_string(),
+        sender_id.clone(),
+        receiver_id.clone(),
+        Nep171TransferAuthorization::Owner,
+    )
+}
+
+mod soulbound {
+    use super::*;
+
+    #[derive(Nep171, Pause, PanicOnDefault)]
+    #[nep171(check_external_transfer = "(Soulbound, Pausable)")]
+    #[near(contract_state)]
+    struct SoulboundContract {}
+
+    #[test]
+    fn soulbound() {
+        let mut contract = SoulboundContract {};
+        contract
+            .mint(&Nep171Mint::new(vec!["t".to_string()], alice()))
+            .unwrap();
+
+        assert!(matches!(
+            contract.external_transfer(&transfer(&alice(), &bob())),
+            Err(Nep171TransferError::TokenIsSoulbound(_)),
+        ));
+
+        contract
+            .burn(&Nep171Burn::new(vec!["t".to_string()], alice()))
+            .unwrap();
+    }
+}
+
+mod pausable {
+    use super::*;
+
+    #[derive(Nep171, Pause, PanicOnDefault)]
+    #[nep171(check_external_transfer = "(DefaultCheckExternalTransfer, Pausable)")]
+    #[near(contract_state)]
+    struct PausableContract {}
+
+    #[test]
+    fn composed_with_pause() {
+        let mut contract = PausableContract {};
+        contract
+            .mint(&Nep171Mint::new(vec!["t".to_string()], alice()))
+            .unwrap();
+
+        assert!(matches!(
+            contract.external_transfer(&transfer(&bob(), &alice())),
+            Err(Nep171TransferError::TokenNotOwnedByExpectedOwner(_)),
+        ));
+
+        contract.pause();
+        assert!(matches!(
+            contract.external_transfer(&transfer(&alice(), &bob())),
+            Err(Nep171TransferError::ContractPaused(_)),
+        ));
+
+        contract.unpause();
+        contract
+            .external_transfer(&transfer(&alice(), &bob()))
+            .unwrap();
+        assert_eq!(contract.token_owner(&"t".to_string()), Some(bob()));
+    }
+}
+
+mod issuer {
+    use super::*;
+
+    #[derive(BorshStorageKey)]
+    #[near]
+    enum Role {
+        Issuer,
+    }
+
+    #[derive(Nep171, Rbac, PanicOnDefault)]
+    #[nep171(check_external_transfer = "TransferableByIssuer")]
+    #[rbac(roles = "Role")]
+    #[near(contract_state)]
+    struct IssuerContract {}
+
+    impl Nep171Issuer for IssuerContract {
+        fn is_issuer(&self, account_id: &AccountIdRef) -> bool {
+            Self::has_role(&account_id.to_owned(), &Role::Issuer)
+        }
+    }
+
+    #[test]
+    fn transferable_by_issuer() {
+        let mut contract = IssuerContract {};
+        contract.add_role(&issuer(), &Role::Issuer);
+        contract
+            .mint(&Nep171Mint::new(vec!["t".to_string()], alice()))
+            .unwrap();
+
+        assert!(matches!(
+            contract.external_transfer(&transfer(&alice(), &bob())),
+            Err(Nep171TransferError::SenderNotIssuer(_)),
+        ));
+
+        contract
+            .external_transfer(&transfer(&issuer(), &bob()))
+            .unwrap();
+        assert_eq!(contract.token_owner(&"t".to_string()), Some(bob()));
+    }
+}
+
+mod lock {
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[nep171(check_external_transfer = "(DefaultCheckExternalTransfer, TransferableUntilLock)")]
+    #[near(contract_state)]
+    struct LockContract {}
+
+    impl Nep171TransferLock for LockContract {
+        fn transfer_lock_timestamp(&self, _token_id: &TokenId) -> Option<u64> {
+            Some(1_000)
+        }
+    }
+
+    #[test]
+    fn transferable_until_lock() {
+        let mut contract = LockContract {};
+        contract
+            .mint(&Nep171Mint::new(vec!["t".to_string()], alice()))
+            .unwrap();
+
+        testing_env!(VMContextBuilder::new().block_timestamp(999).build());
+        contract
+            .external_transfer(&transfer(&alice(), &bob()))
+            .unwrap();
+
+        testing_env!(VMContextBuilder::new().block_timestamp(1_000).build());
+        assert!(matches!(
+            contract.external_transfer(&transfer(&bob(), &alice())),
+            Err(Nep171TransferError::TokenTransferLocked(_)),
+        ));
+    }
+}
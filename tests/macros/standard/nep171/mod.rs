@@ -3,6 +3,7 @@
 use near_sdk::{env, near, store, AccountId, PanicOnDefault};
 use near_sdk_contract_tools::{hook::Hook, nft::*};
 
+mod check;
 mod hooks;
 mod manual_integration;
 mod no_hooks;
@@ -103,6 +104,553 @@ impl NonFungibleToken {
     }
 }
 
+mod event_version {
+    use near_sdk::{
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env,
+    };
+
+    use super::*;
+
+    mod v1_0_0 {
+        use super::*;
+
+        #[derive(Nep171, Nep177, PanicOnDefault)]
+        #[nep171(event_version = "1.0.0")]
+        #[near(contract_state)]
+        pub struct Contract {}
+    }
+
+    mod v1_1_0 {
+        use super::*;
+
+        #[derive(Nep171, Nep177, PanicOnDefault)]
+        #[nep171(event_version = "1.1.0")]
+        #[near(contract_state)]
+        pub struct Contract {}
+    }
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    #[test]
+    fn metadata_update_events_require_v1_1_0() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = v1_0_0::Contract {};
+
+        contract
+            .mint_with_metadata(&"t".to_string(), &alice(), &TokenMetadata::new())
+            .unwrap();
+        contract
+            .set_token_metadata(&"t".to_string(), &TokenMetadata::new().title("New"))
+            .unwrap();
+        contract.set_contract_metadata(&ContractMetadata::new("Name", "SYM", None));
+
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains(r#""version":"1.0.0","event":"nft_mint""#));
+    }
+
+    #[test]
+    fn advertised_version() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = v1_1_0::Contract {};
+
+        contract
+            .mint_with_metadata(&"t".to_string(), &alice(), &TokenMetadata::new())
+            .unwrap();
+        contract
+            .set_token_metadata(&"t".to_string(), &TokenMetadata::new().title("New"))
+            .unwrap();
+        contract.set_contract_metadata(&ContractMetadata::new("Name", "SYM", None));
+
+        let logs = get_logs();
+        assert_eq!(logs.len(), 4);
+        assert!(logs
+            .iter()
+            .all(|log| log.contains(r#""standard":"nep171","version":"1.1.0""#)));
+        assert!(logs[2].contains(r#""event":"nft_metadata_update""#));
+        assert!(logs[3].contains(r#""event":"contract_metadata_update""#));
+    }
+}
+
+mod approval_expiry {
+    use near_sdk::{
+        json_types::U64,
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env, NearToken,
+    };
+    use near_sdk_contract_tools::standard::nep178::{Nep178, Nep178Expiry};
+
+    use super::*;
+
+    #[derive(Nep171, Nep177, Nep178, PanicOnDefault)]
+    #[nep171(
+        all_hooks = "nep178::TokenApprovals",
+        check_external_transfer = "nep178::TokenApprovals",
+        token_data = "(nep177::TokenMetadata, nep178::TokenApprovals)"
+    )]
+    #[near(contract_state)]
+    pub struct Contract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn market() -> AccountId {
+        "market".parse().unwrap()
+    }
+
+    fn approve(contract: &mut Contract, expires_at: Option<u64>) -> ApprovalId {
+        contract
+            .approve(&Nep178Approve {
+                token_id: "t".to_string(),
+                current_owner_id: alice().into(),
+                account_id: market().into(),
+                expires_at,
+            })
+            .unwrap()
+    }
+
+    fn setup(now_ms: u64) -> Contract {
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(now_ms * 1_000_000)
+            .build());
+        let mut contract = Contract {};
+        contract
+            .mint_with_metadata(&"t".to_string(), &alice(), &TokenMetadata::new())
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn expired_approval_is_ignored() {
+        let mut contract = setup(1_000);
+        let approval_id = approve(&mut contract, Some(2_000));
+
+        assert_eq!(
+            contract.get_approval_id_for(&"t".to_string(), &market()),
+            Some(approval_id),
+        );
+        assert_eq!(
+            contract.get_approval_expiry(&"t".to_string(), &market()),
+            Some(2_000),
+        );
+        assert!(get_logs().last().unwrap().contains(
+            r#""standard":"x-nft-approval","version":"1.0.0","event":"approve","data":{"token_id":"t","owner_id":"alice","account_id":"market","approval_id":0,"expires_at":"2000"}"#
+        ));
+
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(2_000 * 1_000_000)
+            .build());
+
+        assert_eq!(
+            contract.get_approval_id_for(&"t".to_string(), &market()),
+            None,
+        );
+        assert!(contract.get_approvals_for(&"t".to_string()).is_empty());
+        assert!(!contract.nft_is_approved("t".to_string(), market(), None));
+    }
+
+    #[test]
+    fn reapprove_after_expiry() {
+        let mut contract = setup(1_000);
+        approve(&mut contract, Some(2_000));
+
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(3_000 * 1_000_000)
+            .build());
+
+        let approval_id = approve(&mut contract, None);
+
+        assert_eq!(approval_id, 1);
+        assert_eq!(
+            contract.get_approval_id_for(&"t".to_string(), &market()),
+            Some(1),
+        );
+        assert_eq!(
+            contract.get_approval_expiry(&"t".to_string(), &market()),
+            None,
+        );
+    }
+
+    #[test]
+    fn external_approve_with_expiry() {
+        let mut contract = setup(1_000);
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(1_000 * 1_000_000)
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+
+        let _ = contract.nft_approve_with_expiry("t".to_string(), market(), U64(2_000), None);
+        assert_eq!(
+            contract.get_approval_expiry(&"t".to_string(), &market()),
+            Some(2_000),
+        );
+
+        // the standard method creates approvals that do not expire
+        let other: AccountId = "other".parse().unwrap();
+        let _ = contract.nft_approve("t".to_string(), other.clone(), None);
+        assert_eq!(contract.get_approval_expiry(&"t".to_string(), &other), None,);
+        assert!(contract.nft_is_approved("t".to_string(), other, Some(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "ApprovalExpiryInPast")]
+    fn expiry_in_past() {
+        let mut contract = setup(1_000);
+        approve(&mut contract, Some(1_000));
+    }
+}
+
+mod approval_storage {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, NearToken};
+    use near_sdk_contract_tools::standard::nep145::{Nep145Controller, StorageBalanceBounds};
+
+    use super::*;
+
+    #[derive(NonFungibleToken, PanicOnDefault)]
+    #[non_fungible_token(max_approvals = "1")]
+    #[near(contract_state)]
+    pub struct Contract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn approve(contract: &mut Contract, account_id: &str) -> Result<ApprovalId, String> {
+        contract
+            .approve(&Nep178Approve {
+                token_id: "t".to_string(),
+                current_owner_id: alice().into(),
+                account_id: account_id.parse::<AccountId>().unwrap().into(),
+                expires_at: None,
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    fn setup() -> Contract {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = Contract {};
+        contract.set_storage_balance_bounds(&StorageBalanceBounds {
+            min: NearToken::from_yoctonear(0),
+            max: None,
+        });
+        contract
+            .deposit_to_storage_account(&alice(), NearToken::from_millinear(100))
+            .unwrap();
+        contract
+            .mint_with_metadata(&"t".to_string(), &alice(), &TokenMetadata::new())
+            .unwrap();
+        contract
+    }
+
+    fn available(contract: &Contract) -> NearToken {
+        contract.get_storage_balance(&alice()).unwrap().available
+    }
+
+    fn revoke(contract: &mut Contract, account_id: &str) {
+        contract
+            .revoke(&Nep178Revoke {
+                token_id: "t".to_string(),
+                current_owner_id: alice().into(),
+                account_id: account_id.parse::<AccountId>().unwrap().into(),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn owner_pays_for_approvals() {
+        let mut contract = setup();
+        let before = available(&contract);
+
+        approve(&mut contract, "market").unwrap();
+        let approved = available(&contract);
+        assert!(approved < before);
+
+        // The approval record of the token (including the next approval ID)
+        // is kept, so only the approval itself is refunded.
+        revoke(&mut contract, "market");
+        let revoked = available(&contract);
+        assert!(revoked > approved);
+
+        approve(&mut contract, "market").unwrap();
+        assert_eq!(available(&contract), approved);
+
+        revoke(&mut contract, "market");
+        assert_eq!(available(&contract), revoked);
+    }
+
+    #[test]
+    fn revoke_all_refunds_owner() {
+        let mut contract = setup();
+
+        approve(&mut contract, "market").unwrap();
+        revoke(&mut contract, "market");
+        let revoked = available(&contract);
+
+        approve(&mut contract, "market").unwrap();
+        contract
+            .revoke_all(&Nep178RevokeAll {
+                token_id: "t".to_string(),
+                current_owner_id: alice().into(),
+            })
+            .unwrap();
+
+        assert_eq!(available(&contract), revoked);
+    }
+
+    #[test]
+    fn configurable_max_approvals() {
+        let mut contract = setup();
+
+        approve(&mut contract, "market").unwrap();
+
+        assert_eq!(
+            approve(&mut contract, "other").unwrap_err(),
+            "Too many approvals for token t, maximum is 1.",
+        );
+    }
+}
+
+mod batch_transfer {
+    use near_sdk::{
+        log,
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env,
+    };
+    use near_sdk_contract_tools::standard::nep171::{
+        error::Nep171BatchTransferError,
+        event::{Nep171Event, Nep171EventVersion, NftTransferLog, MAX_TOTAL_LOG_LENGTH},
+        Nep171TransferAuthorization,
+    };
+
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[nep171(transfer_hook = "Self", transfer_hook_log_length = "16")]
+    #[near(contract_state)]
+    pub struct Contract {}
+
+    impl Hook<Contract, Nep171Transfer<'_>> for Contract {
+        fn hook<R>(
+            contract: &mut Contract,
+            args: &Nep171Transfer<'_>,
+            f: impl FnOnce(&mut Contract) -> R,
+        ) -> R {
+            let r = f(contract);
+            log!("transferred {}", args.token_id);
+            r
+        }
+    }
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob".parse().unwrap()
+    }
+
+    fn transfer(token_id: &str) -> Nep171Transfer<'static> {
+        Nep171Transfer {
+            token_id: token_id.to_string(),
+            authorization: Nep171TransferAuthorization::Owner,
+            sender_id: alice().into(),
+            receiver_id: bob().into(),
+            memo: None,
+            msg: None,
+            revert: false,
+        }
+    }
+
+    fn setup() -> Contract {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = Contract {};
+        contract
+            .mint(&Nep171Mint::new(
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                alice(),
+            ))
+            .unwrap();
+        testing_env!(VMContextBuilder::new().build());
+        contract
+    }
+
+    #[test]
+    fn grouped_event() {
+        let mut contract = setup();
+
+        contract
+            .external_batch_transfer(&[transfer("a"), transfer("b"), transfer("c")])
+            .unwrap();
+
+        for token_id in ["a", "b", "c"] {
+            assert_eq!(contract.token_owner(&token_id.to_string()), Some(bob()));
+        }
+
+        assert_eq!(
+            get_logs(),
+            vec![
+                "transferred a".to_string(),
+                "transferred b".to_string(),
+                "transferred c".to_string(),
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.2.0","event":"nft_transfer","data":[{"old_owner_id":"alice","new_owner_id":"bob","token_ids":["a","b","c"]}]}"#.to_string(),
+            ],
+        );
+    }
+
+    #[test]
+    fn atomic() {
+        let mut contract = setup();
+
+        let result = contract.external_batch_transfer(&[transfer("a"), transfer("z")]);
+
+        assert!(matches!(result, Err(Nep171BatchTransferError::Transfer(_))));
+        assert_eq!(contract.token_owner(&"a".to_string()), Some(alice()));
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    fn duplicate_token() {
+        let mut contract = setup();
+
+        let result = contract.external_batch_transfer(&[transfer("a"), transfer("a")]);
+
+        assert!(matches!(
+            result,
+            Err(Nep171BatchTransferError::DuplicateTokenId(_)),
+        ));
+        assert_eq!(contract.token_owner(&"a".to_string()), Some(alice()));
+    }
+
+    #[test]
+    fn oversized_batch() {
+        let mut contract = setup();
+        let token_ids = (0..600)
+            .map(|i| format!("token_{i:03}"))
+            .collect::<Vec<_>>();
+        for chunk in token_ids.chunks(100) {
+            contract
+                .mint(&Nep171Mint::new(chunk.to_vec(), alice()))
+                .unwrap();
+            testing_env!(VMContextBuilder::new().build());
+        }
+
+        let transfers = token_ids
+            .iter()
+            .map(|token_id| transfer(token_id))
+            .collect::<Vec<_>>();
+
+        // the event alone fits, but not with the logs reserved for the hook
+        let events = Nep171Event::split_transfers(
+            vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: alice().into(),
+                new_owner_id: bob().into(),
+                token_ids: token_ids.iter().map(Into::into).collect(),
+                memo: None,
+            }],
+            Nep171EventVersion::V1_2_0,
+            MAX_TOTAL_LOG_LENGTH,
+        );
+        assert_eq!(events.len(), 1);
+
+        let result = contract.external_batch_transfer(&transfers);
+
+        let Err(Nep171BatchTransferError::LogLengthExceeded(e)) = result else {
+            panic!("Expected log length error, got {result:?}");
+        };
+        assert_eq!(e.limit, MAX_TOTAL_LOG_LENGTH);
+        assert!(e.length > MAX_TOTAL_LOG_LENGTH);
+
+        for token_id in &token_ids {
+            assert_eq!(contract.token_owner(token_id), Some(alice()));
+        }
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    fn split_long_events() {
+        let token_ids = (0..50).map(|i| format!("token_{i}")).collect::<Vec<_>>();
+        let logs = vec![
+            NftTransferLog {
+                authorized_id: None,
+                old_owner_id: alice().into(),
+                new_owner_id: bob().into(),
+                token_ids: token_ids[..40].iter().map(Into::into).collect(),
+                memo: None,
+            },
+            NftTransferLog {
+                authorized_id: None,
+                old_owner_id: bob().into(),
+                new_owner_id: alice().into(),
+                token_ids: token_ids[40..].iter().map(Into::into).collect(),
+                memo: None,
+            },
+        ];
+
+        let events = Nep171Event::split_transfers(logs, Nep171EventVersion::V1_2_0, 300);
+
+        assert!(events.len() > 1);
+
+        let mut split_token_ids = vec![];
+        for event in &events {
+            assert!(
+                event
+                    .to_versioned_log_string(Nep171EventVersion::V1_2_0)
+                    .len()
+                    <= 300
+            );
+            let Nep171Event::NftTransfer(logs) = event else {
+                panic!("Unexpected event");
+            };
+            split_token_ids.extend(logs.iter().flat_map(|log| log.token_ids.iter().cloned()));
+        }
+
+        assert_eq!(split_token_ids, token_ids);
+    }
+
+    #[test]
+    fn split_events_are_full() {
+        // quotes are escaped, so the serialized length differs from the ID
+        let token_ids = (0..200).map(|i| format!("\"{i}\"")).collect::<Vec<_>>();
+        let logs = vec![NftTransferLog {
+            authorized_id: None,
+            old_owner_id: alice().into(),
+            new_owner_id: bob().into(),
+            token_ids: token_ids.iter().map(Into::into).collect(),
+            memo: Some("memo".into()),
+        }];
+
+        let events = Nep171Event::split_transfers(logs, Nep171EventVersion::V1_2_0, 500);
+
+        let mut next_token_id = 0;
+        for event in &events {
+            let Nep171Event::NftTransfer(logs) = event else {
+                panic!("Unexpected event");
+            };
+            assert_eq!(logs.len(), 1);
+            next_token_id += logs[0].token_ids.len();
+
+            let length = event
+                .to_versioned_log_string(Nep171EventVersion::V1_2_0)
+                .len();
+            assert!(length <= 500);
+
+            // the next token would not have fit
+            if let Some(token_id) = token_ids.get(next_token_id) {
+                let token_id_length = near_sdk::serde_json::to_string(token_id).unwrap().len();
+                assert!(length + token_id_length + 1 > 500);
+            }
+        }
+
+        assert_eq!(next_token_id, token_ids.len());
+    }
+}
+
 mod tests {
     use near_sdk::{
         test_utils::{get_logs, VMContextBuilder},
@@ -175,3 +723,204 @@ mod tests {
         );
     }
 }
+
+mod transfer_on_force_unregister {
+    use near_sdk::{
+        env,
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env, NearToken,
+    };
+    use near_sdk_contract_tools::standard::{
+        nep145::{event::StorageEvent, Nep145Controller, StorageBalanceBounds},
+        nep171::event::{Nep171Event, NftTransferLog},
+        nep297::Event,
+    };
+
+    use super::*;
+
+    #[derive(NonFungibleToken, PanicOnDefault)]
+    #[non_fungible_token(transfer_on_force_unregister)]
+    #[near(contract_state)]
+    pub struct Contract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn treasury() -> AccountId {
+        "treasury".parse().unwrap()
+    }
+
+    fn market() -> AccountId {
+        "market".parse().unwrap()
+    }
+
+    fn setup() -> Contract {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = Contract {};
+        contract.set_storage_balance_bounds(&StorageBalanceBounds {
+            min: NearToken::from_yoctonear(0),
+            max: None,
+        });
+        for account_id in [alice(), treasury()] {
+            contract
+                .deposit_to_storage_account(&account_id, NearToken::from_millinear(100))
+                .unwrap();
+        }
+        for (token_id, owner_id) in [("a", alice()), ("b", alice()), ("c", treasury())] {
+            contract
+                .mint_with_metadata(&token_id.to_string(), &owner_id, &TokenMetadata::new())
+                .unwrap();
+        }
+        contract
+            .approve(&Nep178Approve {
+                token_id: "a".to_string(),
+                current_owner_id: alice().into(),
+                account_id: market().into(),
+                expires_at: None,
+            })
+            .unwrap();
+        testing_env!(VMContextBuilder::new().build());
+        contract
+    }
+
+    #[test]
+    fn transfers_to_beneficiary() {
+        let mut contract = setup();
+        contract.set_force_unregister_beneficiary(Some(&treasury()));
+        let alice_balance = contract.get_storage_balance(&alice()).unwrap();
+
+        contract.force_unregister_storage_account(&alice()).unwrap();
+
+        for token_id in ["a", "b"] {
+            assert_eq!(
+                contract.token_owner(&token_id.to_string()),
+                Some(treasury())
+            );
+        }
+        assert!(contract.get_storage_balance(&alice()).is_err());
+        assert!(contract.get_approvals_for(&"a".to_string()).is_empty());
+        assert_eq!(
+            contract.with_tokens_for_owner(&alice(), near_sdk::collections::UnorderedSet::len),
+            0
+        );
+        assert_eq!(
+            contract.with_tokens_for_owner(&treasury(), near_sdk::collections::UnorderedSet::len),
+            3
+        );
+
+        // each transfer is charged to the receiver; transferring "a" also
+        // frees its approval
+        let byte_cost = env::storage_byte_cost();
+        assert_eq!(
+            get_logs(),
+            vec![
+                StorageEvent::Unlock {
+                    account_id: treasury(),
+                    amount: byte_cost.saturating_mul(195),
+                    bytes: 195,
+                    payer_id: None,
+                }
+                .to_event_string(),
+                StorageEvent::Lock {
+                    account_id: treasury(),
+                    amount: byte_cost.saturating_mul(9),
+                    bytes: 9,
+                    payer_id: None,
+                }
+                .to_event_string(),
+                Nep171Event::NftTransfer(vec![NftTransferLog {
+                    authorized_id: None,
+                    old_owner_id: alice().into(),
+                    new_owner_id: treasury().into(),
+                    token_ids: vec!["a".into(), "b".into()],
+                    memo: Some("storage forced unregistration".into()),
+                }])
+                .to_event_string(),
+                StorageEvent::Unregister {
+                    account_id: alice(),
+                    amount: alice_balance.available,
+                    force: true,
+                }
+                .to_event_string(),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic = "No beneficiary set for forced unregistration"]
+    fn requires_beneficiary() {
+        let mut contract = setup();
+
+        contract.force_unregister_storage_account(&alice()).unwrap();
+    }
+}
+
+mod cached_batch_mint {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, Gas};
+    use near_sdk_contract_tools::slot::Slot;
+
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[near(contract_state)]
+    pub struct Contract {}
+
+    impl Contract {
+        fn slot_next_id() -> Slot<u64> {
+            Slot::new(b"next_id".as_slice())
+        }
+
+        fn mint_one(&mut self, id: u64, owner_id: &AccountId) {
+            self.mint(&Nep171Mint::new(vec![id.to_string()], owner_id))
+                .unwrap();
+        }
+
+        pub fn mint_batch(&mut self, owner_id: &AccountId, count: u64) {
+            let mut next_id = Self::slot_next_id();
+            for _ in 0..count {
+                let id = next_id.read().unwrap_or(0);
+                self.mint_one(id, owner_id);
+                next_id.write(&(id + 1));
+            }
+        }
+
+        pub fn mint_batch_cached(&mut self, owner_id: &AccountId, count: u64) {
+            let mut next_id = Self::slot_next_id().cached();
+            for _ in 0..count {
+                let id = next_id.get().copied().unwrap_or(0);
+                self.mint_one(id, owner_id);
+                next_id.write(id + 1);
+            }
+        }
+    }
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn measure(f: impl FnOnce(&mut Contract)) -> (Gas, Contract) {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = Contract {};
+        let start = env::used_gas();
+        f(&mut contract);
+        (env::used_gas().saturating_sub(start), contract)
+    }
+
+    #[test]
+    fn cached_batch_mint_uses_less_gas() {
+        let (uncached, contract) = measure(|c| c.mint_batch(&alice(), 20));
+        assert_eq!(contract.token_owner(&"19".to_string()), Some(alice()));
+        assert_eq!(Contract::slot_next_id().read(), Some(20));
+
+        // storage persists between test environments, so minting continues
+        let (cached, contract) = measure(|c| c.mint_batch_cached(&alice(), 20));
+        assert_eq!(contract.token_owner(&"39".to_string()), Some(alice()));
+        assert_eq!(Contract::slot_next_id().read(), Some(40));
+
+        assert!(
+            cached < uncached,
+            "cached: {cached:?}, uncached: {uncached:?}",
+        );
+    }
+}
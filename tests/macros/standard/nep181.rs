@@ -0,0 +1,189 @@
+use near_sdk::{
+    json_types::U64, near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken,
+    PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    nft::*,
+    standard::{
+        nep145::{Nep145Controller, StorageBalanceBounds},
+        nep181::index::{Nep181Index, Nep181IndexExternal, TokenIndex},
+    },
+};
+
+#[derive(NonFungibleToken, PanicOnDefault)]
+#[non_fungible_token(index = "BySeries", index = "ByMintTime", index = "ByOwner")]
+#[near(contract_state)]
+pub struct Contract {}
+
+/// Token IDs are of the form `"{series}:{edition}"`.
+pub struct BySeries;
+
+impl TokenIndex<Contract> for BySeries {
+    const NAME: &'static str = "series";
+
+    fn keys(_contract: &Contract, token_id: &TokenId) -> Vec<String> {
+        token_id
+            .split_once(':')
+            .map(|(series, _)| vec![series.to_string()])
+            .unwrap_or_default()
+    }
+}
+
+pub struct ByMintTime;
+
+impl TokenIndex<Contract> for ByMintTime {
+    const NAME: &'static str = "mint_time";
+
+    fn keys(contract: &Contract, token_id: &TokenId) -> Vec<String> {
+        contract
+            .token_metadata(token_id)
+            .and_then(|metadata| metadata.issued_at)
+            .map(|issued_at| vec![format!("{:020}", issued_at.0)])
+            .unwrap_or_default()
+    }
+}
+
+pub struct ByOwner;
+
+impl TokenIndex<Contract> for ByOwner {
+    const NAME: &'static str = "owner";
+
+    fn keys(contract: &Contract, token_id: &TokenId) -> Vec<String> {
+        contract
+            .token_owner(token_id)
+            .map(|owner_id| vec![owner_id.to_string()])
+            .unwrap_or_default()
+    }
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn bob() -> AccountId {
+    "bob".parse().unwrap()
+}
+
+fn setup() -> Contract {
+    testing_env!(VMContextBuilder::new().build());
+    let mut contract = Contract {};
+    contract.set_storage_balance_bounds(&StorageBalanceBounds {
+        min: NearToken::from_yoctonear(0),
+        max: None,
+    });
+    for account_id in [alice(), bob()] {
+        contract
+            .deposit_to_storage_account(&account_id, NearToken::from_near(1))
+            .unwrap();
+    }
+    contract
+}
+
+fn mint(contract: &mut Contract, token_id: &str, issued_at: u64) {
+    contract
+        .mint_with_metadata(
+            &token_id.to_string(),
+            &alice(),
+            &TokenMetadata::new().issued_at(U64(issued_at)),
+        )
+        .unwrap();
+}
+
+fn token_ids(tokens: Vec<Token>) -> Vec<String> {
+    tokens.into_iter().map(|token| token.token_id).collect()
+}
+
+#[test]
+fn index_by_key() {
+    let mut contract = setup();
+    mint(&mut contract, "1:1", 0);
+    mint(&mut contract, "2:1", 0);
+    mint(&mut contract, "1:2", 0);
+
+    assert_eq!(
+        contract
+            .nft_supply_by_index("series".to_string(), Some("1".to_string()))
+            .0,
+        2,
+    );
+    assert_eq!(
+        token_ids(contract.nft_tokens_by_index(
+            "series".to_string(),
+            Some("1".to_string()),
+            None,
+            None,
+        )),
+        vec!["1:1", "1:2"],
+    );
+
+    contract
+        .burn(&Nep171Burn::new(vec!["1:1".to_string()], alice()))
+        .unwrap();
+
+    assert_eq!(contract.index_supply("series", Some("1")), 1);
+    assert_eq!(contract.index_supply("series", None), 2);
+    assert!(contract.token_keys("series", &"1:1".to_string()).is_empty());
+}
+
+#[test]
+fn sorted_by_key() {
+    let mut contract = setup();
+    mint(&mut contract, "c", 30);
+    mint(&mut contract, "a", 10);
+    mint(&mut contract, "b", 200);
+
+    assert_eq!(
+        token_ids(contract.nft_tokens_by_index("mint_time".to_string(), None, None, None)),
+        vec!["a", "c", "b"],
+    );
+    assert_eq!(
+        token_ids(contract.nft_tokens_by_index(
+            "mint_time".to_string(),
+            None,
+            Some(1.into()),
+            Some(1),
+        )),
+        vec!["c"],
+    );
+}
+
+#[test]
+fn reindex_on_transfer() {
+    let mut contract = setup();
+    mint(&mut contract, "a", 0);
+
+    contract
+        .external_transfer(&Nep171Transfer {
+            token_id: "a".to_string(),
+            authorization: nep171::Nep171TransferAuthorization::Owner,
+            sender_id: alice().into(),
+            receiver_id: bob().into(),
+            memo: None,
+            msg: None,
+            revert: false,
+        })
+        .unwrap();
+
+    assert_eq!(contract.index_supply("owner", Some("alice")), 0);
+    assert_eq!(
+        contract.index_tokens("owner", Some("bob"), 0, None),
+        vec!["a".to_string()],
+    );
+    assert_eq!(contract.token_keys("owner", &"a".to_string()), vec!["bob"]);
+}
+
+#[test]
+#[should_panic(expected = "Unknown index")]
+fn unknown_index() {
+    let contract = setup();
+    contract.nft_supply_by_index("trait".to_string(), None);
+}
+
+#[test]
+#[should_panic(expected = "from_index is out of range")]
+fn from_index_out_of_range() {
+    let mut contract = setup();
+    mint(&mut contract, "a", 0);
+
+    contract.nft_tokens_by_index("mint_time".to_string(), None, Some(u128::MAX.into()), None);
+}
@@ -0,0 +1,257 @@
+use near_sdk::{
+    env, near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken, PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    owner::Owner,
+    standard::nep393::{Nep393, TokenMetadata, MAX_SBT_TOKENS_SCANNED},
+    Nep393, Owner,
+};
+
+#[derive(Owner, Nep393, PanicOnDefault)]
+#[nep393(authority = "owner")]
+#[near(contract_state)]
+struct Credentials {}
+
+#[near]
+impl Credentials {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        Owner::init(&mut contract, &owner());
+
+        contract
+    }
+}
+
+fn owner() -> AccountId {
+    "owner".parse().unwrap()
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn bob() -> AccountId {
+    "bob".parse().unwrap()
+}
+
+fn carol() -> AccountId {
+    "carol".parse().unwrap()
+}
+
+fn call(account_id: AccountId, block_timestamp_ms: u64) {
+    call_with_deposit(account_id, block_timestamp_ms, NearToken::from_near(1));
+}
+
+fn call_with_deposit(account_id: AccountId, block_timestamp_ms: u64, deposit: NearToken) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(deposit)
+        .block_timestamp(block_timestamp_ms * 1_000_000)
+        .build());
+}
+
+fn setup() -> Credentials {
+    call(owner(), 1_000);
+    let mut contract = Credentials::new();
+
+    let token_ids = contract.sbt_mint(vec![
+        (
+            alice(),
+            vec![
+                TokenMetadata::new(1),
+                TokenMetadata::new(2).expires_at(5_000),
+            ],
+        ),
+        (bob(), vec![TokenMetadata::new(1)]),
+    ]);
+    assert_eq!(token_ids, vec![1, 2, 3]);
+
+    contract
+}
+
+#[test]
+fn mint_and_query() {
+    let contract = setup();
+    let issuer = env::current_account_id();
+
+    assert_eq!(contract.sbt_supply(issuer.clone()), 3);
+    assert_eq!(contract.sbt_supply_by_class(issuer.clone(), 1), 2);
+    assert_eq!(contract.sbt_supply_by_class(issuer.clone(), 2), 1);
+    assert_eq!(
+        contract.sbt_supply_by_owner(alice(), issuer.clone(), None),
+        2
+    );
+    assert_eq!(
+        contract.sbt_supply_by_owner(bob(), issuer.clone(), Some(2)),
+        0
+    );
+
+    let token = contract.sbt(issuer.clone(), 2).unwrap();
+    assert_eq!(token.owner, alice());
+    assert_eq!(token.metadata.class, 2);
+    assert_eq!(token.metadata.issued_at, Some(1_000));
+    assert_eq!(token.metadata.expires_at, Some(5_000));
+
+    let by_owner = contract.sbt_tokens_by_owner(alice(), None, Some(2), None, false);
+    assert_eq!(by_owner.len(), 1);
+    assert_eq!(by_owner[0].0, issuer);
+    assert_eq!(
+        by_owner[0].1.iter().map(|t| t.token).collect::<Vec<_>>(),
+        vec![2],
+    );
+
+    let tokens = contract.sbt_tokens(issuer, Some(2), Some(5), false);
+    assert_eq!(
+        tokens.iter().map(|t| t.token).collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+
+    let other: AccountId = "other".parse().unwrap();
+    assert_eq!(contract.sbt_supply(other.clone()), 0);
+    assert!(contract.sbt(other, 1).is_none());
+}
+
+#[test]
+fn revoke_renew_and_burn() {
+    let mut contract = setup();
+    let issuer = env::current_account_id();
+
+    call_with_deposit(owner(), 2_000, NearToken::from_yoctonear(1));
+    contract.sbt_revoke(vec![1], false);
+    assert_eq!(
+        contract.sbt(issuer.clone(), 1).unwrap().metadata.expires_at,
+        Some(2_000)
+    );
+    assert!(
+        contract.sbt_tokens_by_owner(alice(), None, None, None, false)[0]
+            .1
+            .iter()
+            .all(|t| t.token != 1)
+    );
+    assert_eq!(
+        contract.sbt_tokens(issuer.clone(), None, None, true).len(),
+        3
+    );
+
+    contract.sbt_renew(vec![1], 10_000);
+    assert_eq!(
+        contract.sbt_tokens(issuer.clone(), None, None, false).len(),
+        3
+    );
+
+    call_with_deposit(owner(), 6_000, NearToken::from_yoctonear(1));
+    assert_eq!(
+        contract.sbt_tokens(issuer.clone(), None, None, false).len(),
+        2
+    );
+
+    contract.sbt_revoke(vec![1, 3], true);
+    assert!(contract.sbt(issuer.clone(), 1).is_none());
+    assert_eq!(contract.sbt_supply(issuer.clone()), 1);
+    assert_eq!(contract.sbt_supply_by_class(issuer.clone(), 1), 0);
+    assert_eq!(contract.sbt_supply_by_owner(bob(), issuer, None), 0);
+}
+
+#[test]
+fn recover() {
+    let mut contract = setup();
+    let issuer = env::current_account_id();
+
+    call(owner(), 2_000);
+    assert_eq!(contract.sbt_recover(alice(), carol()), (2, true));
+
+    assert_eq!(
+        contract.sbt_supply_by_owner(alice(), issuer.clone(), None),
+        0
+    );
+    assert_eq!(
+        contract.sbt_supply_by_owner(carol(), issuer.clone(), None),
+        2
+    );
+    assert_eq!(contract.sbt_supply(issuer.clone()), 3);
+    assert_eq!(contract.sbt(issuer, 1).unwrap().owner, carol());
+}
+
+#[test]
+#[should_panic(expected = "Account bob already owns a token of class 1")]
+fn recover_class_conflict() {
+    let mut contract = setup();
+
+    call(owner(), 2_000);
+    contract.sbt_recover(alice(), bob());
+}
+
+#[test]
+#[should_panic(expected = "Account alice already owns a token of class 2")]
+fn mint_duplicate_class() {
+    let mut contract = setup();
+
+    call(owner(), 2_000);
+    contract.sbt_mint(vec![(alice(), vec![TokenMetadata::new(2)])]);
+}
+
+#[test]
+#[should_panic(expected = "Owner only")]
+fn mint_requires_issuer() {
+    let mut contract = setup();
+
+    call(alice(), 2_000);
+    contract.sbt_mint(vec![(alice(), vec![TokenMetadata::new(3)])]);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn revoke_requires_one_yocto() {
+    let mut contract = setup();
+
+    call(owner(), 2_000);
+    contract.sbt_revoke(vec![1], false);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn renew_requires_one_yocto() {
+    let mut contract = setup();
+
+    call(owner(), 2_000);
+    contract.sbt_renew(vec![1], 10_000);
+}
+
+#[test]
+fn tokens_scan_is_bounded() {
+    let mut contract = setup();
+    let issuer = env::current_account_id();
+
+    // mint past the scan bound, then burn all but the last token
+    let accounts = (0..MAX_SBT_TOKENS_SCANNED)
+        .map(|i| format!("account_{i}").parse::<AccountId>().unwrap())
+        .collect::<Vec<_>>();
+    for chunk in accounts.chunks(100) {
+        call(owner(), 2_000);
+        contract.sbt_mint(
+            chunk
+                .iter()
+                .map(|account_id| (account_id.clone(), vec![TokenMetadata::new(1)]))
+                .collect(),
+        );
+    }
+    let last_token_id = MAX_SBT_TOKENS_SCANNED + 3;
+
+    for chunk in (1..last_token_id).collect::<Vec<_>>().chunks(100) {
+        call_with_deposit(owner(), 2_000, NearToken::from_yoctonear(1));
+        contract.sbt_revoke(chunk.to_vec(), true);
+    }
+
+    // the first page reaches the bound without finding a token
+    assert!(contract
+        .sbt_tokens(issuer.clone(), None, Some(1), true)
+        .is_empty());
+
+    let tokens = contract.sbt_tokens(issuer, Some(MAX_SBT_TOKENS_SCANNED + 1), Some(1), true);
+    assert_eq!(
+        tokens.iter().map(|t| t.token).collect::<Vec<_>>(),
+        vec![last_token_id],
+    );
+}
@@ -0,0 +1,119 @@
+use near_sdk::{
+    json_types::U64, near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken,
+    PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    nft::*,
+    standard::nft_rental::{NftRentalExternal, TokenRental},
+    NftRental,
+};
+
+#[derive(Nep171, NftRental, PanicOnDefault)]
+#[nep171(
+    transfer_hook = "TokenRental",
+    burn_hook = "TokenRental",
+    token_data = "TokenRental"
+)]
+#[near(contract_state)]
+struct GameItems {}
+
+#[near]
+impl GameItems {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        contract
+            .mint(&Nep171Mint::new(vec!["sword".to_string()], alice()))
+            .unwrap();
+
+        contract
+    }
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn bob() -> AccountId {
+    "bob".parse().unwrap()
+}
+
+fn carol() -> AccountId {
+    "carol".parse().unwrap()
+}
+
+fn call(account_id: AccountId, block_timestamp_ms: u64) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(NearToken::from_yoctonear(1))
+        .block_timestamp(block_timestamp_ms * 1_000_000)
+        .build());
+}
+
+fn sword() -> TokenId {
+    "sword".to_string()
+}
+
+#[test]
+fn set_user_until_expiry() {
+    let mut contract = GameItems::new();
+
+    call(alice(), 1_000);
+    contract.nft_set_user(sword(), bob(), U64(5_000));
+
+    assert_eq!(contract.nft_user_of(sword()), Some(bob()));
+    assert_eq!(contract.nft_user_expires(sword()), Some(U64(5_000)));
+    assert_eq!(
+        contract.nft_token(sword()).unwrap().extensions_metadata["rental"],
+        near_sdk::serde_json::json!({ "user_id": "bob", "expires_at": "5000" }),
+    );
+
+    // Current user may be extended.
+    contract.nft_set_user(sword(), bob(), U64(6_000));
+    assert_eq!(contract.nft_user_expires(sword()), Some(U64(6_000)));
+
+    call(alice(), 6_000);
+    assert_eq!(contract.nft_user_of(sword()), None);
+    assert!(contract.nft_token(sword()).unwrap().extensions_metadata["rental"].is_null());
+
+    // Expired rental may be replaced.
+    contract.nft_set_user(sword(), carol(), U64(7_000));
+    assert_eq!(contract.nft_user_of(sword()), Some(carol()));
+}
+
+#[test]
+#[should_panic(expected = "Token sword is rented to bob until 5000")]
+fn owner_cannot_replace_active_user() {
+    let mut contract = GameItems::new();
+
+    call(alice(), 1_000);
+    contract.nft_set_user(sword(), bob(), U64(5_000));
+    contract.nft_set_user(sword(), carol(), U64(9_000));
+}
+
+#[test]
+#[should_panic(expected = "Token `sword` is owned by `alice` instead of expected `bob`")]
+fn only_owner_sets_user() {
+    let mut contract = GameItems::new();
+
+    call(bob(), 1_000);
+    contract.nft_set_user(sword(), bob(), U64(5_000));
+}
+
+#[test]
+fn transfer_and_burn_clear_user() {
+    let mut contract = GameItems::new();
+
+    call(alice(), 1_000);
+    contract.nft_set_user(sword(), bob(), U64(5_000));
+    contract.nft_transfer(carol(), sword(), None, None);
+    assert_eq!(contract.nft_user_of(sword()), None);
+
+    call(carol(), 1_000);
+    contract.nft_set_user(sword(), bob(), U64(5_000));
+    contract
+        .burn(&Nep171Burn::new(vec![sword()], carol()))
+        .unwrap();
+    assert_eq!(contract.nft_user_of(sword()), None);
+}
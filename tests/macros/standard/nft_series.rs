@@ -0,0 +1,167 @@
+use near_sdk::{
+    env,
+    json_types::U64,
+    mock::MockAction,
+    near,
+    test_utils::{get_created_receipts, VMContextBuilder},
+    testing_env, AccountId, NearToken, PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    nft::*,
+    standard::nep177::series::{NftSeries, NftSeriesExternal},
+    NftSeries,
+};
+
+#[derive(Nep171, Nep177, NftSeries, PanicOnDefault)]
+#[near(contract_state)]
+struct SeriesContract {}
+
+#[near]
+impl SeriesContract {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        contract.set_contract_metadata(&ContractMetadata::new("Drops", "DROP", None));
+        contract.create_series(
+            &creator(),
+            &TokenMetadata::new().title("Sunrise"),
+            Some(2),
+            Some(NearToken::from_near(1)),
+        );
+        contract.create_series(&creator(), &TokenMetadata::new().title("Free"), None, None);
+
+        contract
+    }
+}
+
+fn creator() -> AccountId {
+    "creator".parse().unwrap()
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn call(account_id: AccountId, deposit: NearToken) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(deposit)
+        .block_timestamp(5_000_000)
+        .build());
+}
+
+#[test]
+fn mint_editions() {
+    let mut contract = SeriesContract::new();
+
+    call(alice(), NearToken::from_near(2));
+    let first = contract.nft_mint_from_series(0, None);
+    let second = contract.nft_mint_from_series(0, Some(creator()));
+
+    assert_eq!(first, "0:1");
+    assert_eq!(second, "0:2");
+    assert_eq!(contract.nft_token(first.clone()).unwrap().owner_id, alice());
+    assert_eq!(contract.nft_token(second).unwrap().owner_id, creator());
+
+    let metadata = contract.token_metadata(&first).unwrap();
+    assert_eq!(metadata.title.as_deref(), Some("Sunrise"));
+    assert_eq!(metadata.copies, Some(U64(2)));
+    assert_eq!(metadata.issued_at, Some(U64(5)));
+
+    let series = contract.nft_get_series(0).unwrap();
+    assert_eq!(series.minted, U64(2));
+    assert!(series.is_sold_out());
+}
+
+#[test]
+fn enumerate_series() {
+    let contract = SeriesContract::new();
+
+    assert_eq!(contract.nft_series_count(), 2);
+    assert_eq!(contract.nft_series(None, None).len(), 2);
+    assert_eq!(
+        contract
+            .nft_series(Some(1), Some(10))
+            .into_iter()
+            .map(|s| s.series_id)
+            .collect::<Vec<_>>(),
+        vec![1],
+    );
+    assert!(contract.nft_series(Some(2), None).is_empty());
+    assert!(contract.nft_get_series(2).is_none());
+}
+
+#[test]
+#[should_panic = "Series 0 is sold out (2 minted)"]
+fn mint_sold_out_fail() {
+    let mut contract = SeriesContract::new();
+
+    call(alice(), NearToken::from_near(3));
+    contract.nft_mint_from_series(0, None);
+    contract.nft_mint_from_series(0, None);
+    contract.nft_mint_from_series(0, None);
+}
+
+#[test]
+#[should_panic = "is less than price"]
+fn mint_insufficient_deposit_fail() {
+    let mut contract = SeriesContract::new();
+
+    call(alice(), NearToken::from_millinear(500));
+    contract.nft_mint_from_series(0, None);
+}
+
+#[test]
+#[should_panic = "Series 7 does not exist"]
+fn mint_nonexistent_series_fail() {
+    let mut contract = SeriesContract::new();
+
+    call(alice(), NearToken::from_near(0));
+    contract.nft_mint_from_series(7, None);
+}
+
+#[test]
+#[should_panic = "Insufficient deposit"]
+fn mint_free_without_storage_fee_fail() {
+    let mut contract = SeriesContract::new();
+
+    call(alice(), NearToken::from_near(0));
+    contract.nft_mint_from_series(1, None);
+}
+
+#[test]
+fn mint_pays_storage_and_price() {
+    let mut contract = SeriesContract::new();
+    let deposit = NearToken::from_near(2);
+
+    call(alice(), deposit);
+    let storage_usage_start = env::storage_usage();
+    contract.nft_mint_from_series(0, None);
+    let storage_fee = env::storage_byte_cost()
+        .saturating_mul(u128::from(env::storage_usage() - storage_usage_start));
+
+    let transfers = get_created_receipts()
+        .into_iter()
+        .map(|receipt| {
+            let [MockAction::Transfer { deposit, .. }] = receipt.actions[..] else {
+                panic!("Unexpected receipt");
+            };
+            (receipt.receiver_id, deposit)
+        })
+        .collect::<Vec<_>>();
+
+    assert!(!storage_fee.is_zero());
+    assert_eq!(
+        transfers,
+        vec![
+            (
+                alice(),
+                deposit
+                    .saturating_sub(NearToken::from_near(1))
+                    .saturating_sub(storage_fee),
+            ),
+            (creator(), NearToken::from_near(1)),
+        ],
+    );
+}
@@ -0,0 +1,237 @@
+use near_crypto::{KeyType, SecretKey, Signature};
+use near_sdk::{
+    env, json_types::U64, near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken,
+    PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    nft::*,
+    owner::Owner,
+    standard::nep177::voucher::{hash_metadata, MintVoucher, NftVoucherExternal},
+    NftVoucher, Owner,
+};
+
+#[derive(Owner, Nep171, Nep177, NftVoucher, PanicOnDefault)]
+#[nft_voucher(authority = "owner")]
+#[near(contract_state)]
+struct LazyMint {}
+
+#[near]
+impl LazyMint {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        Owner::init(&mut contract, &owner());
+
+        contract
+    }
+}
+
+fn owner() -> AccountId {
+    "owner".parse().unwrap()
+}
+
+fn creator() -> AccountId {
+    "creator".parse().unwrap()
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn creator_key() -> SecretKey {
+    SecretKey::from_seed(KeyType::ED25519, "creator")
+}
+
+fn call(account_id: AccountId, deposit: NearToken) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(deposit)
+        .block_timestamp(1_000_000_000)
+        .build());
+}
+
+fn setup() -> LazyMint {
+    call(owner(), NearToken::from_yoctonear(1));
+    let mut contract = LazyMint::new();
+
+    contract.nft_set_creator_key(
+        creator(),
+        Some(creator_key().public_key().to_string().parse().unwrap()),
+    );
+
+    contract
+}
+
+fn metadata() -> TokenMetadata {
+    TokenMetadata::new().title("Lazy")
+}
+
+fn voucher(expires_at: u64) -> MintVoucher {
+    MintVoucher {
+        token_id: "lazy-1".to_string(),
+        creator_id: creator(),
+        metadata_hash: hash_metadata(&metadata()).into(),
+        price: NearToken::from_near(1),
+        expires_at: U64(expires_at),
+    }
+}
+
+fn sign(secret_key: &SecretKey, voucher: &MintVoucher) -> Vec<u8> {
+    let Signature::ED25519(signature) =
+        secret_key.sign(&voucher.signing_message(&env::current_account_id()))
+    else {
+        unreachable!()
+    };
+
+    signature.to_bytes().to_vec()
+}
+
+#[test]
+fn redeem_voucher() {
+    let mut contract = setup();
+    let voucher = voucher(5_000);
+    let signature = sign(&creator_key(), &voucher);
+
+    call(alice(), NearToken::from_near(2));
+    let token_id = contract.nft_redeem_voucher(voucher.clone(), metadata(), signature.into(), None);
+
+    assert_eq!(token_id, "lazy-1");
+    assert_eq!(
+        contract.nft_token(token_id.clone()).unwrap().owner_id,
+        alice()
+    );
+    assert_eq!(contract.token_metadata(&token_id), Some(metadata()));
+    assert!(contract.nft_is_voucher_consumed(voucher));
+}
+
+#[test]
+#[should_panic(expected = "Voucher of token lazy-1 has already been redeemed")]
+fn replay() {
+    let mut contract = setup();
+    let voucher = voucher(5_000);
+    let signature = sign(&creator_key(), &voucher);
+
+    call(alice(), NearToken::from_near(1));
+    contract.nft_redeem_voucher(voucher.clone(), metadata(), signature.clone().into(), None);
+    contract
+        .burn_with_metadata(&voucher.token_id, &alice())
+        .unwrap();
+    contract.nft_redeem_voucher(voucher, metadata(), signature.into(), None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid signature for voucher of token lazy-1")]
+fn wrong_signer() {
+    let mut contract = setup();
+    let voucher = voucher(5_000);
+    let signature = sign(&SecretKey::from_seed(KeyType::ED25519, "mallory"), &voucher);
+
+    call(alice(), NearToken::from_near(1));
+    contract.nft_redeem_voucher(voucher, metadata(), signature.into(), None);
+}
+
+#[test]
+#[should_panic(expected = "Metadata does not match the voucher of token lazy-1")]
+fn metadata_mismatch() {
+    let mut contract = setup();
+    let voucher = voucher(5_000);
+    let signature = sign(&creator_key(), &voucher);
+
+    call(alice(), NearToken::from_near(1));
+    contract.nft_redeem_voucher(
+        voucher,
+        TokenMetadata::new().title("Other"),
+        signature.into(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Voucher of token lazy-1 expired at 1000")]
+fn expired() {
+    let mut contract = setup();
+    let voucher = voucher(1_000);
+    let signature = sign(&creator_key(), &voucher);
+
+    call(alice(), NearToken::from_near(1));
+    contract.nft_redeem_voucher(voucher, metadata(), signature.into(), None);
+}
+
+mod payout {
+    use near_sdk_contract_tools::standard::nep177::voucher::{NftVoucher, VoucherPayout};
+
+    use super::*;
+
+    fn treasury() -> AccountId {
+        "treasury".parse().unwrap()
+    }
+
+    pub struct TreasuryPayout<const PERCENT: u128>;
+
+    impl<C, const PERCENT: u128> VoucherPayout<C> for TreasuryPayout<PERCENT> {
+        fn payout(_contract: &C, voucher: &MintVoucher) -> Vec<(AccountId, NearToken)> {
+            vec![(
+                treasury(),
+                voucher.price.saturating_mul(PERCENT).saturating_div(100),
+            )]
+        }
+    }
+
+    #[derive(Owner, Nep171, Nep177, NftVoucher, PanicOnDefault)]
+    #[nft_voucher(authority = "owner", payout = "TreasuryPayout<10>")]
+    #[near(contract_state)]
+    struct Royalty {}
+
+    mod excessive {
+        use super::*;
+
+        #[derive(Owner, Nep171, Nep177, NftVoucher, PanicOnDefault)]
+        #[nft_voucher(authority = "owner", payout = "TreasuryPayout<150>")]
+        #[near(contract_state)]
+        pub struct Excessive {}
+    }
+
+    fn setup<C: Owner + NftVoucher>(mut contract: C) -> C {
+        call(owner(), NearToken::from_yoctonear(1));
+        Owner::init(&mut contract, &owner());
+        contract
+            .set_creator_key(
+                &creator(),
+                Some(&creator_key().public_key().to_string().parse().unwrap()),
+            )
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn remainder_to_creator() {
+        let mut contract = setup(Royalty {});
+        let voucher = voucher(5_000);
+        let signature = sign(&creator_key(), &voucher);
+
+        call(alice(), NearToken::from_near(1));
+        let payouts = contract
+            .redeem_voucher(&voucher, &metadata(), &signature, &alice())
+            .unwrap();
+
+        assert_eq!(
+            payouts,
+            vec![
+                (treasury(), NearToken::from_millinear(100)),
+                (creator(), NearToken::from_millinear(900)),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Payouts for voucher of token lazy-1 exceed its price")]
+    fn excessive_payout() {
+        let mut contract = setup(excessive::Excessive {});
+        let voucher = voucher(5_000);
+        let signature = sign(&creator_key(), &voucher);
+
+        call(alice(), NearToken::from_near(1));
+        contract.nft_redeem_voucher(voucher, metadata(), signature.into(), None);
+    }
+}
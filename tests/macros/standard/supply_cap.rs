@@ -0,0 +1,96 @@
+use near_sdk::{near, test_utils::VMContextBuilder, testing_env, AccountId, PanicOnDefault};
+use near_sdk_contract_tools::{
+    ft::*,
+    standard::nep141::supply_cap::{MintRateLimit, SupplyCap, SupplyCapExternal, SupplyCapHook},
+    Nep141, SupplyCap,
+};
+
+#[derive(Nep141, SupplyCap, PanicOnDefault)]
+#[nep141(mint_hook = "SupplyCapHook")]
+#[near(contract_state)]
+struct CappedToken {}
+
+#[near]
+impl CappedToken {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        contract.set_max_supply(Some(1000));
+        contract.set_mint_rate_limit(Some(&MintRateLimit::new(300, 5)));
+
+        contract
+    }
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn set_epoch(epoch_height: u64) {
+    testing_env!(VMContextBuilder::new().epoch_height(epoch_height).build());
+}
+
+#[test]
+fn mint_within_limits() {
+    set_epoch(0);
+    let mut contract = CappedToken::new();
+
+    contract.mint(&Nep141Mint::new(200, alice())).unwrap();
+
+    assert_eq!(contract.ft_max_supply().unwrap().0, 1000);
+    assert_eq!(contract.ft_total_supply().0, 200);
+    assert_eq!(contract.minted_in_current_window(), 200);
+    assert_eq!(contract.remaining_supply(), Some(800));
+    assert_eq!(contract.remaining_mint_budget(), Some(100));
+    assert_eq!(contract.ft_remaining_mintable_supply().unwrap().0, 100);
+}
+
+#[test]
+fn mint_budget_resets_every_window() {
+    set_epoch(3);
+    let mut contract = CappedToken::new();
+
+    contract.mint(&Nep141Mint::new(300, alice())).unwrap();
+    assert_eq!(contract.remaining_mint_budget(), Some(0));
+    assert!(contract.check_mint(1).is_err());
+
+    set_epoch(5);
+    assert_eq!(contract.minted_in_current_window(), 0);
+    contract.mint(&Nep141Mint::new(300, alice())).unwrap();
+
+    assert_eq!(contract.ft_total_supply().0, 600);
+}
+
+#[test]
+fn burn_frees_supply() {
+    set_epoch(0);
+    let mut contract = CappedToken::new();
+    contract.set_mint_rate_limit(None);
+
+    contract.mint(&Nep141Mint::new(1000, alice())).unwrap();
+    assert_eq!(contract.remaining_mintable_supply(), Some(0));
+
+    contract.burn(&Nep141Burn::new(250, alice())).unwrap();
+    assert_eq!(contract.remaining_mintable_supply(), Some(250));
+}
+
+#[test]
+#[should_panic = "would exceed the maximum supply 1000"]
+fn mint_over_max_supply_fail() {
+    set_epoch(0);
+    let mut contract = CappedToken::new();
+    contract.set_mint_rate_limit(None);
+
+    contract.mint(&Nep141Mint::new(1001, alice())).unwrap();
+}
+
+#[test]
+#[should_panic = "would exceed the mint budget 300 of window 0"]
+fn mint_over_budget_fail() {
+    set_epoch(0);
+    let mut contract = CappedToken::new();
+
+    contract.mint(&Nep141Mint::new(200, alice())).unwrap();
+    contract.mint(&Nep141Mint::new(101, alice())).unwrap();
+}
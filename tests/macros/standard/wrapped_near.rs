@@ -0,0 +1,99 @@
+use near_sdk::{
+    near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken, PanicOnDefault,
+};
+use near_sdk_contract_tools::{
+    ft::*,
+    standard::nep141::wrapped_near::{WrappedNear, WrappedNearExternal},
+    WrappedNear,
+};
+
+const REGISTRATION_FEE: NearToken = NearToken::from_millinear(10);
+
+#[derive(FungibleToken, WrappedNear, PanicOnDefault)]
+#[near(contract_state)]
+struct WrappedNearContract {}
+
+#[near]
+impl WrappedNearContract {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        contract.set_storage_balance_bounds(&StorageBalanceBounds {
+            min: REGISTRATION_FEE,
+            max: None,
+        });
+
+        contract
+    }
+}
+
+fn alice() -> AccountId {
+    "alice".parse().unwrap()
+}
+
+fn call(account_id: AccountId, deposit: NearToken) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(account_id)
+        .attached_deposit(deposit)
+        .build());
+}
+
+#[test]
+fn deposit_registers_and_mints() {
+    let mut contract = WrappedNearContract::new();
+
+    call(alice(), NearToken::from_near(1));
+    let minted = contract.near_deposit();
+
+    let expected = NearToken::from_near(1)
+        .saturating_sub(REGISTRATION_FEE)
+        .as_yoctonear();
+    assert_eq!(minted.0, expected);
+    assert_eq!(contract.ft_balance_of(alice()).0, expected);
+    assert_eq!(contract.ft_total_supply().0, expected);
+    assert!(contract.storage_balance_of(alice()).is_some());
+
+    call(alice(), NearToken::from_near(2));
+    let minted = contract.near_deposit();
+
+    assert_eq!(minted.0, NearToken::from_near(2).as_yoctonear());
+    assert_eq!(contract.ft_total_supply().0, expected + minted.0);
+}
+
+#[test]
+fn withdraw_burns() {
+    let mut contract = WrappedNearContract::new();
+
+    call(alice(), NearToken::from_near(1));
+    let minted = contract.near_deposit().0;
+
+    call(alice(), NearToken::from_yoctonear(1));
+    let _ = contract.near_withdraw(100.into());
+
+    assert_eq!(contract.ft_balance_of(alice()).0, minted - 100);
+    assert_eq!(contract.ft_total_supply().0, minted - 100);
+}
+
+#[test]
+#[should_panic = "must attach at least"]
+fn deposit_below_registration_fee_fail() {
+    let mut contract = WrappedNearContract::new();
+
+    call(alice(), NearToken::from_millinear(1));
+    contract.near_deposit();
+}
+
+#[test]
+#[should_panic = "does not have enough balance"]
+fn withdraw_more_than_balance_fail() {
+    let mut contract = WrappedNearContract::new();
+
+    call(alice(), NearToken::from_near(1));
+    let minted = contract
+        .deposit_near(&alice(), NearToken::from_near(1))
+        .unwrap();
+
+    call(alice(), NearToken::from_yoctonear(1));
+    let _ = contract.near_withdraw((minted + 1).into());
+}
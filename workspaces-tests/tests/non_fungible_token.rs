@@ -1002,6 +1002,16 @@ async fn transfer_approval_too_many_approvals_fail() {
     let alice = &accounts[0];
     let bob = &accounts[1];
 
+    // approvals are paid for by the token owner
+    alice
+        .call(contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(ONE_NEAR.saturating_div(10))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
     let mut set = JoinSet::new();
 
     for i in 0..32 {
@@ -1039,6 +1049,7 @@ async fn transfer_approval_too_many_approvals_fail() {
         "Smart contract panicked: {}",
         Nep178ApproveError::TooManyApprovals(TooManyApprovalsError {
             token_id: "token_0".to_string(),
+            max_approvals: 32,
         }),
     );
 